@@ -0,0 +1,121 @@
+use log::info;
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{ERROR_FILE_NOT_FOUND, ERROR_SUCCESS};
+use windows::Win32::System::Registry::{
+    RegDeleteKeyValueW, RegGetValueW, RegSetKeyValueW, HKEY_CURRENT_USER, REG_SZ, RRF_RT_REG_SZ,
+};
+
+/// Per-user key whose values Windows launches at login.
+const RUN_KEY: PCWSTR = w!(r"Software\Microsoft\Windows\CurrentVersion\Run");
+
+/// Name of this application's value under [`RUN_KEY`].
+const RUN_VALUE: PCWSTR = w!("MultiManager");
+
+/// Returns the command line registered to start the application at login, if any.
+pub fn registered_command() -> Option<String> {
+    unsafe {
+        let mut size = 0u32;
+        let status = RegGetValueW(
+            HKEY_CURRENT_USER,
+            RUN_KEY,
+            RUN_VALUE,
+            RRF_RT_REG_SZ,
+            None,
+            None,
+            Some(&mut size),
+        );
+        if status != ERROR_SUCCESS || size == 0 {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; size as usize / 2];
+        let status = RegGetValueW(
+            HKEY_CURRENT_USER,
+            RUN_KEY,
+            RUN_VALUE,
+            RRF_RT_REG_SZ,
+            None,
+            Some(buffer.as_mut_ptr().cast()),
+            Some(&mut size),
+        );
+        if status != ERROR_SUCCESS {
+            return None;
+        }
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Some(String::from_utf16_lossy(&buffer[..len]))
+    }
+}
+
+/// Adds or removes the registry value that starts the current executable at login.
+pub fn set_start_with_windows(enabled: bool) -> Result<(), String> {
+    if enabled {
+        let command = current_command()?;
+        let wide: Vec<u16> = command.encode_utf16().chain(Some(0)).collect();
+        let status = unsafe {
+            RegSetKeyValueW(
+                HKEY_CURRENT_USER,
+                RUN_KEY,
+                RUN_VALUE,
+                REG_SZ.0,
+                Some(wide.as_ptr().cast()),
+                (wide.len() * 2) as u32,
+            )
+        };
+        if status != ERROR_SUCCESS {
+            return Err(format!(
+                "Failed to add the startup entry: {}",
+                windows::core::Error::from(status.to_hresult())
+            ));
+        }
+        info!("Registered '{}' to start with Windows.", command);
+    } else {
+        let status = unsafe { RegDeleteKeyValueW(HKEY_CURRENT_USER, RUN_KEY, RUN_VALUE) };
+        if status != ERROR_SUCCESS && status != ERROR_FILE_NOT_FOUND {
+            return Err(format!(
+                "Failed to remove the startup entry: {}",
+                windows::core::Error::from(status.to_hresult())
+            ));
+        }
+        info!("Removed the start with Windows entry.");
+    }
+    Ok(())
+}
+
+/// Makes the registry match `desired`, re-pointing an existing entry at the
+/// current executable if it has moved.
+pub fn reconcile_start_with_windows(desired: bool) -> Result<(), String> {
+    let registered = registered_command();
+    if desired {
+        if registered.as_deref() != Some(current_command()?.as_str()) {
+            set_start_with_windows(true)?;
+        }
+    } else if registered.is_some() {
+        set_start_with_windows(false)?;
+    }
+    Ok(())
+}
+
+/// Command line stored in the Run key for the running executable.
+fn current_command() -> Result<String, String> {
+    let exe =
+        std::env::current_exe().map_err(|e| format!("Failed to locate the executable: {}", e))?;
+    Ok(quote_command(&exe.to_string_lossy()))
+}
+
+/// Quotes `path` so paths containing spaces launch correctly.
+fn quote_command(path: &str) -> String {
+    format!("\"{}\"", path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_quoted() {
+        assert_eq!(
+            quote_command(r"C:\Program Files\Multi Manager\multi-manager.exe"),
+            r#""C:\Program Files\Multi Manager\multi-manager.exe""#
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use log::warn;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of backups kept per file when no setting overrides it.
+pub const DEFAULT_MAX_BACKUPS: usize = 10;
+
+/// Name of the folder, next to the saved file, that holds its backups.
+const BACKUP_DIR: &str = "backups";
+
+/// Writes `contents` to `path` by writing a temporary file first and renaming it
+/// over the destination, so a failed write never leaves a truncated file behind.
+pub fn write_atomically(path: &str, contents: &[u8]) -> io::Result<()> {
+    let tmp_path = format!("{}.tmp", path);
+    let result = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Copies the current contents of `path` into the `backups/` folder next to it
+/// under a timestamped name, then prunes all but the newest `max_backups`.
+///
+/// Returns the backup path, or `None` if `path` does not exist yet, backups are
+/// disabled (`max_backups == 0`), or the newest backup already has the same
+/// contents.
+pub fn backup_file(path: &str, max_backups: usize) -> io::Result<Option<PathBuf>> {
+    let source = Path::new(path);
+    if max_backups == 0 || !source.is_file() {
+        return Ok(None);
+    }
+
+    let contents = fs::read(source)?;
+    let existing = list_backups(path);
+    if let Some(newest) = existing.first() {
+        if fs::read(newest).is_ok_and(|previous| previous == contents) {
+            return Ok(None);
+        }
+    }
+
+    let dir = backup_dir(source);
+    fs::create_dir_all(&dir)?;
+    let backup_path = dir.join(format!(
+        "{}-{}.{}",
+        file_stem(source),
+        current_timestamp(),
+        file_extension(source)
+    ));
+    fs::write(&backup_path, &contents)?;
+
+    for old in list_backups(path).into_iter().skip(max_backups) {
+        if let Err(e) = fs::remove_file(&old) {
+            warn!("Failed to remove old backup '{}': {}", old.display(), e);
+        }
+    }
+
+    Ok(Some(backup_path))
+}
+
+/// Lists the backups of `path`, newest first.
+pub fn list_backups(path: &str) -> Vec<PathBuf> {
+    let source = Path::new(path);
+    let prefix = format!("{}-", file_stem(source));
+    let extension = file_extension(source);
+
+    let mut backups: Vec<PathBuf> = fs::read_dir(backup_dir(source))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|candidate| {
+                    candidate
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| {
+                            name.starts_with(&prefix) && name.ends_with(&format!(".{}", extension))
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    // Timestamps sort lexicographically, so reverse name order is newest first.
+    backups.sort();
+    backups.reverse();
+    backups
+}
+
+fn backup_dir(source: &Path) -> PathBuf {
+    source
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(BACKUP_DIR)
+}
+
+fn file_stem(source: &Path) -> String {
+    source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "backup".to_string())
+}
+
+fn file_extension(source: &Path) -> String {
+    source
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+        .unwrap_or_else(|| "json".to_string())
+}
+
+/// Returns the current time as a sortable UTC `YYYYMMDD-HHMMSS` string.
+pub fn current_timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format_timestamp(secs)
+}
+
+/// Formats seconds since the Unix epoch as a sortable UTC `YYYYMMDD-HHMMSS` string.
+fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil-from-days conversion (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        (rem % 3_600) / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "multi_manager_backup_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("should create temp dir");
+        dir
+    }
+
+    #[test]
+    fn formats_utc_timestamps() {
+        assert_eq!(format_timestamp(0), "19700101-000000");
+        assert_eq!(format_timestamp(951_782_400), "20000229-000000");
+        assert_eq!(format_timestamp(1_700_000_000), "20231114-221320");
+    }
+
+    #[test]
+    fn atomic_write_replaces_contents() {
+        let dir = temp_dir("atomic");
+        let path = dir.join("workspaces.json");
+        let path = path.to_str().unwrap();
+
+        write_atomically(path, b"first").unwrap();
+        write_atomically(path, b"second").unwrap();
+
+        assert_eq!(fs::read(path).unwrap(), b"second");
+        assert!(!Path::new(&format!("{}.tmp", path)).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn backups_skip_duplicates_and_prune() {
+        let dir = temp_dir("prune");
+        let path = dir.join("workspaces.json");
+        let path = path.to_str().unwrap();
+
+        assert!(backup_file(path, 2).unwrap().is_none());
+
+        fs::write(path, "one").unwrap();
+        assert!(backup_file(path, 2).unwrap().is_some());
+        assert!(backup_file(path, 2).unwrap().is_none());
+        assert_eq!(list_backups(path).len(), 1);
+
+        // Fake older backups so pruning has something to remove.
+        let backups = dir.join(BACKUP_DIR);
+        fs::write(backups.join("workspaces-19990101-000000.json"), "old").unwrap();
+        fs::write(backups.join("workspaces-19980101-000000.json"), "older").unwrap();
+        fs::write(path, "two").unwrap();
+        backup_file(path, 2).unwrap();
+
+        let remaining = list_backups(path);
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(fs::read_to_string(&remaining[0]).unwrap(), "two");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
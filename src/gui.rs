@@ -1,52 +1,217 @@
-use crate::settings::{save_settings, Settings};
+use crate::autostart::{registered_command, set_start_with_windows};
+use crate::backup::{current_timestamp, list_backups};
+use crate::hotkey::HotkeyRegistration;
+use crate::monitor::primary_screen_size;
+use crate::paths::{app_dir, log_path, resolve_data_path, resolve_legacy_data_path};
+use crate::settings::{save_settings, Settings, Theme, HOTKEY_POLL_RANGE_MS};
+use crate::tray::{hide_main_window, start_tray, stop_tray};
+use crate::undo::UndoHistory;
 use crate::utils::*;
 use crate::window_bindings::{
-    apply_window_bindings, load_window_bindings, save_window_bindings, BindingApplicationStats,
-    WindowBindingError,
+    apply_window_bindings, load_window_bindings, match_missing_windows, save_window_bindings,
+    BindingApplicationStats, LiveWindow, WindowBindingError,
 };
 use crate::window_manager::{
-    capture_all_desktops, check_hotkeys, get_active_window, move_all_to_origin,
-    poll_recapture_keys, restore_all_desktops, send_all_windows_home, RecaptureAction,
+    capture_all_desktops, check_hotkeys, frame_bounds_compensation, gather_workspace_windows,
+    get_active_window, hotkey_has_modifier, hotkey_layout_warning, list_capturable_windows,
+    move_all_to_origin, normalize_hotkey, poll_recapture_keys, push_recent,
+    rescue_offscreen_windows, restore_all_desktops, send_all_windows_home,
+    set_frame_bounds_compensation, toggle_workspace_windows, window_fingerprint, CaptureFilter,
+    DesktopProgress, MoveAnimation, RecaptureAction,
 };
 use crate::workspace::*;
+use crate::workspace_diff::{diff_workspaces, Rect, WorkspaceDiff};
 use eframe::egui::ViewportBuilder;
 use eframe::egui::{self, menu, TopBottomPanel};
 use eframe::NativeOptions;
 use eframe::{self, App as EframeApp};
-use log::{debug, info, warn};
+use log::{debug, error, info, warn};
 use poll_promise::Promise;
 use rfd::FileDialog;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::WindowsAndMessaging::IsWindow;
+use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
 
 #[derive(Clone)]
 pub struct App {
     pub app_title_name: String,
     pub workspaces: Arc<Mutex<Vec<Workspace>>>,
     pub last_hotkey_info: Arc<Mutex<Option<(String, Instant)>>>,
+    /// Names of recently toggled workspaces, most recent first. Names rather
+    /// than indices keep the list valid when workspaces are reordered,
+    /// inserted or reloaded.
+    pub recent: Arc<Mutex<VecDeque<String>>>,
     pub hotkey_promise: Arc<Mutex<Option<Promise<()>>>>,
+    /// Set on exit to make the hotkey thread leave its loop.
+    pub hotkey_thread_stop: Arc<AtomicBool>,
     pub initial_validation_done: Arc<Mutex<bool>>,
-    pub registered_hotkeys: Arc<Mutex<HashMap<String, usize>>>,
+    pub registered_hotkeys: Arc<Mutex<HashMap<String, HotkeyRegistration>>>,
     pub rename_dialog: Option<(usize, String)>,
     pub hotkey_dialog: Option<(usize, String)>,
+    pub import_dialog: Option<ImportWindowsDialog>,
+    pub add_windows_dialog: Option<AddWindowsDialog>,
+    pub rescale_dialog: Option<RescaleDialog>,
+    pub workspace_comparison: Option<WorkspaceComparison>,
     pub all_expanded: bool,
     pub expand_all_signal: Option<bool>,
+    /// Text typed into the window search field.
+    pub window_search: String,
+    /// Workspace to expand and scroll to on the next frame, picked from the search results.
+    pub scroll_to_workspace: Option<usize>,
     pub show_settings: bool,
+    pub show_status: bool,
+    /// Shows the list of hotkeys; toggled with F1.
+    pub show_hotkey_help: bool,
     pub auto_save: bool,
     pub unsaved_changes: bool,
     pub save_on_exit: bool,
     pub log_level: String,
+    pub log_max_size_mb: u64,
+    pub log_keep_files: u32,
     pub last_layout_file: Option<String>,
     pub last_workspace_file: Option<String>,
     pub last_bindings_file: Option<String>,
+    /// Folder file dialogs open in; see [`Settings::data_dir`].
+    pub data_dir: Option<String>,
     pub developer_debugging: bool,
+    pub binding_match_threshold: f64,
+    pub max_backups: usize,
+    pub pretty_json: bool,
+    pub move_animation: Arc<Mutex<MoveAnimation>>,
+    pub verify_moves: Arc<Mutex<bool>>,
+    pub hotkeys_enabled: Arc<Mutex<bool>>,
+    pub hotkeys_toggle_key: Arc<Mutex<String>>,
+    /// Milliseconds the hotkey thread sleeps between checks.
+    pub hotkey_poll_ms: Arc<Mutex<u64>>,
+    pub minimize_to_tray: bool,
+    pub start_minimized: bool,
+    pub start_with_windows: bool,
+    /// Handle of the main window once it has been created.
+    pub main_window: Option<isize>,
+    pub theme: Theme,
+    pub origin_exclude_processes: Vec<String>,
+    pub origin_current_desktop_only: bool,
     pub recapture_queue: Vec<(usize, usize)>,
     pub recapture_active: bool,
+    /// Seconds a countdown capture waits before grabbing the foreground window.
+    pub capture_countdown_secs: u32,
+    /// Pause after each desktop switch in "Restore All Desktops", in milliseconds.
+    pub desktop_switch_delay_ms: u64,
+    /// Running countdown capture and when it fires.
+    pub capture_countdown: Option<(CountdownCapture, Instant)>,
+    /// Running "Rebind Launching Windows" scan.
+    pub auto_rebind: Option<AutoRebind>,
+    /// Running "Save All Desktops" or "Restore All Desktops".
+    pub desktop_job: Option<DesktopJob>,
+    /// Whether the "Save All Desktops" filter dialog is open.
+    pub show_capture_filter: bool,
+    /// Title substrings or executable names "Save All Desktops" records.
+    pub desktop_capture_include: Vec<String>,
+    /// Title substrings or executable names "Save All Desktops" skips.
+    pub desktop_capture_exclude: Vec<String>,
+    pub last_deleted: Option<(usize, Workspace)>,
+    pub last_deleted_at: Option<Instant>,
+    pub undo_history: UndoHistory<Vec<Workspace>>,
+}
+
+/// What a countdown capture does with the foreground window once it fires.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CountdownCapture {
+    /// Rebind the window at the front of `recapture_queue`.
+    Recapture,
+    /// Add the window to the workspace at this index.
+    AddWindow(usize),
+}
+
+/// Progress of "Rebind Launching Windows", which keeps scanning for missing
+/// windows while their applications start.
+#[derive(Clone, Copy)]
+pub struct AutoRebind {
+    /// When to give up on windows that have not appeared.
+    pub deadline: Instant,
+    /// When to scan next.
+    pub next_scan: Instant,
+    /// Windows bound so far.
+    pub rebound: usize,
+}
+
+/// Trims `entries` and drops blank ones, as edited in multi-line settings fields.
+fn non_blank_entries(entries: &[String]) -> Vec<String> {
+    entries
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// A desktop capture or restore running on a background thread.
+#[derive(Clone)]
+pub struct DesktopJob {
+    /// Menu action that started the job, used as the window title.
+    pub title: &'static str,
+    pub progress: Arc<DesktopProgress>,
+    /// Resolves to the message shown when the job ends.
+    pub promise: Arc<Mutex<Promise<Result<String, String>>>>,
+}
+
+/// How often "Rebind Launching Windows" scans for new windows.
+const AUTO_REBIND_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long "Rebind Launching Windows" waits for missing windows to appear.
+const AUTO_REBIND_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// File extensions offered in workspace save/load dialogs.
+const WORKSPACE_FILE_EXTENSIONS: &[&str] = &["json", "toml", "yaml", "yml"];
+
+/// How long the "Undo delete" button stays available after deleting a workspace.
+const UNDO_DELETE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// State of the "Import Windows" dialog.
+#[derive(Clone)]
+pub struct ImportWindowsDialog {
+    /// Index of the workspace windows are copied into.
+    pub target: usize,
+    /// Index of the workspace windows are copied from, once chosen.
+    pub source: Option<usize>,
+    /// Indices of the source windows selected for import.
+    pub selected: Vec<usize>,
+}
+
+/// State of the "Add Windows" picker.
+#[derive(Clone)]
+pub struct AddWindowsDialog {
+    /// Index of the workspace the picked windows are added to.
+    pub target: usize,
+    /// Visible top-level windows as `(HWND, title)`, listed when the dialog opened.
+    pub candidates: Vec<(isize, String)>,
+    /// Indices into `candidates` of the windows to add.
+    pub selected: Vec<usize>,
+}
+
+/// State of the "Rescale Layout" dialog.
+#[derive(Clone)]
+pub struct RescaleDialog {
+    /// Screen size `(width, height)` the layout was captured on.
+    pub from: (i32, i32),
+    /// Screen size `(width, height)` to fit the layout to.
+    pub to: (i32, i32),
+}
+
+/// Result of "Compare Workspaces", shown until its window is closed.
+#[derive(Clone)]
+pub struct WorkspaceComparison {
+    /// File treated as the older version.
+    pub older: String,
+    /// File treated as the newer version.
+    pub newer: String,
+    pub diff: WorkspaceDiff,
 }
 
 pub struct WorkspaceControlContext<'a> {
@@ -105,27 +270,43 @@ pub struct WorkspaceControlContext<'a> {
 pub fn run_gui(mut app: App) {
     {
         let mut workspaces = app.workspaces.lock().unwrap();
-        let path = app
-            .last_workspace_file
-            .clone()
-            .unwrap_or_else(|| "workspaces.json".to_string());
-        *workspaces = load_workspaces(&path, &app);
+        let path = app.last_workspace_file.clone().unwrap_or_else(|| {
+            resolve_legacy_data_path(app.data_dir.as_deref(), "workspaces.json")
+        });
+        *workspaces = match load_workspaces(&path, &app) {
+            Ok(loaded) => loaded,
+            Err(LoadError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+                info!("Workspace file '{}' not found; starting empty.", path);
+                Vec::new()
+            }
+            Err(err) => {
+                error!("Failed to load workspaces from '{}': {}", path, err);
+                show_error_box(
+                    &format!("Failed to load workspaces from '{}':\n{}", path, err),
+                    "Load Workspaces",
+                );
+                Vec::new()
+            }
+        };
     }
 
     {
-        let bindings_path = app
-            .last_bindings_file
-            .clone()
-            .unwrap_or_else(|| "window_handles.json".to_string());
+        let bindings_path = app.last_bindings_file.clone().unwrap_or_else(|| {
+            resolve_legacy_data_path(app.data_dir.as_deref(), "window_handles.json")
+        });
         app.attempt_restore_bindings(&bindings_path);
     }
 
     app.validate_initial_hotkeys();
 
     let app_for_promise = app.clone();
-    let hotkey_promise = Promise::spawn_thread("Hotkey Checker", move || loop {
-        check_hotkeys(&app_for_promise);
-        thread::sleep(Duration::from_millis(100));
+    let hotkey_promise = Promise::spawn_thread("Hotkey Checker", move || {
+        while !app_for_promise.hotkey_thread_stop.load(Ordering::Relaxed) {
+            check_hotkeys(&app_for_promise);
+            let poll_ms = *app_for_promise.hotkey_poll_ms.lock().unwrap();
+            thread::sleep(Duration::from_millis(poll_ms));
+        }
+        info!("Hotkey checker stopped.");
     });
     *app.hotkey_promise.lock().unwrap() = Some(hotkey_promise);
 
@@ -137,22 +318,77 @@ pub fn run_gui(mut app: App) {
     let icon_rgba = image.into_raw();
 
     let options = NativeOptions {
-        viewport: ViewportBuilder::default().with_icon(egui::IconData {
-            rgba: icon_rgba,
-            width,
-            height,
-        }),
+        viewport: ViewportBuilder::default()
+            .with_icon(egui::IconData {
+                rgba: icon_rgba,
+                width,
+                height,
+            })
+            .with_visible(!app.start_minimized),
         ..Default::default()
     };
 
+    let theme = app.theme;
     eframe::run_native(
         &app.app_title_name.clone(),
         options,
-        Box::new(|_cc| Ok(Box::new(app))),
+        Box::new(move |cc| {
+            apply_theme(&cc.egui_ctx, theme);
+            if let Ok(RawWindowHandle::Win32(handle)) =
+                cc.window_handle().map(|handle| handle.as_raw())
+            {
+                app.main_window = Some(handle.hwnd.get());
+                start_tray(app.clone(), handle.hwnd.get());
+            } else {
+                warn!("Main window handle unavailable; the tray icon is disabled.");
+            }
+            Ok(Box::new(app))
+        }),
     )
     .expect("Failed to run GUI");
 }
 
+/// Switches the GUI to the light or dark visuals selected by `theme`.
+///
+/// The theme preference is pinned as well, so egui does not swap the visuals
+/// back when the OS setting changes while a fixed theme is selected.
+fn apply_theme(ctx: &egui::Context, theme: Theme) {
+    let (preference, visuals) = if theme.is_dark() {
+        (egui::Theme::Dark, egui::Visuals::dark())
+    } else {
+        (egui::Theme::Light, egui::Visuals::light())
+    };
+    ctx.set_theme(preference);
+    ctx.set_visuals(visuals);
+}
+
+/// Formats `elapsed` as a short human readable duration, e.g. "42s" or "3m 5s".
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+/// Summarizes the outcome of applying window bindings for display to the user.
+fn format_binding_stats(stats: BindingApplicationStats) -> String {
+    let mut message = format!(
+        "Restored {}, invalidated {}, unmatched {}",
+        stats.restored, stats.invalidated, stats.unmatched
+    );
+    if stats.fuzzy_matched > 0 {
+        message.push_str(&format!(
+            " ({} matched by similar title)",
+            stats.fuzzy_matched
+        ));
+    }
+    message
+}
+
 fn log_binding_stats(path: &str, stats: BindingApplicationStats) {
     if stats.restored > 0 {
         info!(
@@ -168,6 +404,15 @@ fn log_binding_stats(path: &str, stats: BindingApplicationStats) {
         );
     }
 
+    if stats.fuzzy_matched > 0 {
+        info!(
+            "{} binding{} from '{}' matched by similar window title.",
+            stats.fuzzy_matched,
+            if stats.fuzzy_matched == 1 { "" } else { "s" },
+            path
+        );
+    }
+
     if stats.invalidated > 0 {
         warn!(
             "{} binding{} from '{}' referenced invalid handles and were marked for recapture.",
@@ -225,10 +470,40 @@ impl EframeApp for App {
         let mut new_workspace: Option<Workspace> = None;
         let mut workspace_to_delete: Option<usize> = None;
 
+        if !self.undo_history.has_baseline() {
+            let workspaces = self.workspaces.lock().unwrap().clone();
+            self.undo_history.reset(&workspaces);
+        }
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.confirm_close(ctx);
+        }
+        // Leave Ctrl+Z/Ctrl+Y to text fields while one is being edited.
+        if !ctx.wants_keyboard_input() {
+            if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z)) {
+                self.undo();
+            } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y)) {
+                self.redo();
+            }
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::S)) {
+            self.save_workspaces();
+            info!("Workspaces saved via Ctrl+S.");
+        } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::O)) {
+            self.prompt_load_workspaces();
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F1)) {
+            self.show_hotkey_help = !self.show_hotkey_help;
+        }
+
         self.render_menu_bar(ctx);
 
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            self.render_status_bar(ui);
+        });
+
         egui::CentralPanel::default().show(ctx, |ui| {
             self.render_header(ui, &mut save_flag, &mut new_workspace);
+            self.render_window_search(ui);
             ui.separator();
             self.render_workspace_list(ui, &mut workspace_to_delete);
         });
@@ -236,6 +511,9 @@ impl EframeApp for App {
         if self.recapture_active {
             self.process_recapture_all(ctx);
         }
+        self.process_add_window_countdown(ctx);
+        self.process_auto_rebind(ctx);
+        self.process_desktop_job(ctx);
 
         if save_flag {
             self.save_workspaces();
@@ -250,29 +528,110 @@ impl EframeApp for App {
         if self.show_settings {
             self.render_settings_window(ctx);
         }
+        if self.show_status {
+            self.render_status_window(ctx);
+        }
+        if self.show_hotkey_help {
+            self.render_hotkey_help(ctx);
+        }
+        if self.show_capture_filter {
+            self.render_capture_filter(ctx);
+        }
+
+        if self.undo_history.take_changed() {
+            self.push_undo_snapshot();
+        }
 
         if self.auto_save && self.unsaved_changes {
             self.save_workspaces();
         }
+
+        if self.minimize_to_tray && ctx.input(|i| i.viewport().minimized) == Some(true) {
+            if let Some(hwnd) = self.main_window {
+                hide_main_window(HWND(hwnd as *mut c_void));
+            }
+        }
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.stop_hotkey_thread();
         if self.save_on_exit {
             self.save_workspaces();
         }
-        save_settings(&Settings {
+        save_settings(&self.current_settings());
+        stop_tray();
+    }
+}
+
+impl App {
+    /// Resolves the default file `name` against the configured data folder.
+    fn data_path(&self, name: &str) -> String {
+        resolve_data_path(self.data_dir.as_deref(), name)
+    }
+
+    /// Creates a file dialog that opens in the configured data folder, if any.
+    fn file_dialog(&self) -> FileDialog {
+        match self
+            .data_dir
+            .as_deref()
+            .filter(|dir| !dir.trim().is_empty())
+        {
+            Some(dir) => FileDialog::new().set_directory(dir.trim()),
+            None => FileDialog::new(),
+        }
+    }
+
+    /// Creates a file dialog suggesting `default_path`, opening in its folder
+    /// when it has one and in the data folder otherwise.
+    fn file_dialog_for(&self, default_path: &str) -> FileDialog {
+        let path = std::path::Path::new(default_path);
+        let dialog = match path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            Some(dir) => FileDialog::new().set_directory(dir),
+            None => self.file_dialog(),
+        };
+        match path.file_name() {
+            Some(name) => dialog.set_file_name(name.to_string_lossy()),
+            None => dialog,
+        }
+    }
+
+    /// Builds a [`Settings`] value from the current application state so it can be persisted.
+    fn current_settings(&self) -> Settings {
+        let animation = *self.move_animation.lock().unwrap();
+        Settings {
             save_on_exit: self.save_on_exit,
             auto_save: self.auto_save,
             log_level: self.log_level.clone(),
+            log_max_size_mb: self.log_max_size_mb,
+            log_keep_files: self.log_keep_files,
             last_layout_file: self.last_layout_file.clone(),
             last_workspace_file: self.last_workspace_file.clone(),
             last_bindings_file: self.last_bindings_file.clone(),
+            data_dir: self.data_dir.as_deref().map(|dir| dir.trim().to_string()),
             developer_debugging: self.developer_debugging,
-        });
+            binding_match_threshold: self.binding_match_threshold,
+            max_backups: self.max_backups,
+            pretty_json: self.pretty_json,
+            animate_moves: animation.enabled,
+            animation_ms: animation.duration_ms,
+            theme: self.theme,
+            origin_exclude_processes: non_blank_entries(&self.origin_exclude_processes),
+            origin_current_desktop_only: self.origin_current_desktop_only,
+            verify_moves: *self.verify_moves.lock().unwrap(),
+            frame_bounds_compensation: frame_bounds_compensation(),
+            hotkeys_enabled: *self.hotkeys_enabled.lock().unwrap(),
+            hotkeys_toggle_key: self.hotkeys_toggle_key.lock().unwrap().trim().to_string(),
+            hotkey_poll_ms: *self.hotkey_poll_ms.lock().unwrap(),
+            minimize_to_tray: self.minimize_to_tray,
+            start_minimized: self.start_minimized,
+            start_with_windows: self.start_with_windows,
+            capture_countdown_secs: self.capture_countdown_secs,
+            desktop_switch_delay_ms: self.desktop_switch_delay_ms,
+            desktop_capture_include: non_blank_entries(&self.desktop_capture_include),
+            desktop_capture_exclude: non_blank_entries(&self.desktop_capture_exclude),
+        }
     }
-}
 
-impl App {
     /// Renders the application's menu bar with a "File" menu.
     ///
     /// The menu contains a single "Settings" item that sets
@@ -282,60 +641,74 @@ impl App {
             menu::bar(ui, |ui| {
                 ui.menu_button("File", |ui| {
                     ui.menu_button("Desktop Management", |ui| {
-                        if ui.button("Save All Desktops").clicked() {
-                            let default_path = self
-                                .last_layout_file
-                                .clone()
-                                .unwrap_or_else(|| "desktop_layout.json".to_string());
-                            let chosen = rfd::FileDialog::new()
-                                .set_file_name(&default_path)
-                                .save_file()
-                                .map(|p| p.to_string_lossy().to_string())
-                                .unwrap_or(default_path);
-                            capture_all_desktops(&chosen);
-                            self.last_layout_file = Some(chosen.clone());
-                            save_settings(&Settings {
-                                save_on_exit: self.save_on_exit,
-                                auto_save: self.auto_save,
-                                log_level: self.log_level.clone(),
-                                last_layout_file: self.last_layout_file.clone(),
-                                last_workspace_file: self.last_workspace_file.clone(),
-                                last_bindings_file: self.last_bindings_file.clone(),
-                                developer_debugging: self.developer_debugging,
-                            });
-                            show_message_box("Desktops saved", "Save");
+                        let idle = self.desktop_job.is_none();
+                        if ui
+                            .add_enabled(idle, egui::Button::new("Save All Desktops..."))
+                            .on_hover_text("Choose which windows to record, then save the layout")
+                            .clicked()
+                        {
+                            self.show_capture_filter = true;
                             ui.close_menu();
                         }
-                        if ui.button("Restore All Desktops").clicked() {
+                        if ui
+                            .add_enabled(idle, egui::Button::new("Restore All Desktops"))
+                            .clicked()
+                        {
                             let default_path = self
                                 .last_layout_file
                                 .clone()
-                                .unwrap_or_else(|| "desktop_layout.json".to_string());
-                            let chosen = rfd::FileDialog::new()
-                                .set_file_name(&default_path)
+                                .unwrap_or_else(|| self.data_path("desktop_layout.json"));
+                            let chosen = self.file_dialog_for(&default_path)
                                 .pick_file()
                                 .map(|p| p.to_string_lossy().to_string())
                                 .unwrap_or(default_path);
-                            restore_all_desktops(&chosen);
                             self.last_layout_file = Some(chosen.clone());
-                            save_settings(&Settings {
-                                save_on_exit: self.save_on_exit,
-                                auto_save: self.auto_save,
-                                log_level: self.log_level.clone(),
-                                last_layout_file: self.last_layout_file.clone(),
-                                last_workspace_file: self.last_workspace_file.clone(),
-                                last_bindings_file: self.last_bindings_file.clone(),
-                                developer_debugging: self.developer_debugging,
+                            save_settings(&self.current_settings());
+                            let delay = Duration::from_millis(self.desktop_switch_delay_ms);
+                            self.start_desktop_job("Restore All Desktops", move |progress| {
+                                restore_all_desktops(&chosen, false, delay, progress).map(
+                                    |summary| {
+                                        format!(
+                                            "{}{}",
+                                            summary.stats,
+                                            if summary.cancelled { " (cancelled)" } else { "" }
+                                        )
+                                    },
+                                )
                             });
                             ui.close_menu();
                         }
                         if ui.button("Move All to Origin").clicked() {
-                            move_all_to_origin();
+                            move_all_to_origin(&self.current_settings());
+                            ui.close_menu();
+                        }
+                        if ui
+                            .button("Rescue Off-Screen Windows")
+                            .on_hover_text(
+                                "Move workspace windows stranded outside every monitor to the primary monitor",
+                            )
+                            .clicked()
+                        {
+                            let rescued = {
+                                let workspaces = self.workspaces.lock().unwrap();
+                                rescue_offscreen_windows(&workspaces)
+                            };
+                            show_message_box(
+                                &format!(
+                                    "Rescued {} off-screen window{}.",
+                                    rescued,
+                                    if rescued == 1 { "" } else { "s" }
+                                ),
+                                "Rescue Off-Screen Windows",
+                            );
                             ui.close_menu();
                         }
                     });
                     ui.menu_button("Workspace Management", |ui| {
-                        if ui.button("Save Workspaces...").clicked() {
+                        if ui
+                            .add(egui::Button::new("Save Workspaces...").shortcut_text("Ctrl+S"))
+                            .clicked()
+                        {
                             self.save_workspaces();
                             show_message_box("Workspaces saved successfully!", "Save");
                             ui.close_menu();
@@ -344,9 +717,9 @@ impl App {
                             let default_path = self
                                 .last_workspace_file
                                 .clone()
-                                .unwrap_or_else(|| "workspaces.json".to_string());
-                            if let Some(chosen) = rfd::FileDialog::new()
-                                .set_file_name(&default_path)
+                                .unwrap_or_else(|| self.data_path("workspaces.json"));
+                            if let Some(chosen) = self.file_dialog_for(&default_path)
+                                .add_filter("Workspaces", WORKSPACE_FILE_EXTENSIONS)
                                 .save_file()
                                 .map(|p| p.to_string_lossy().to_string())
                             {
@@ -355,28 +728,94 @@ impl App {
                             }
                             ui.close_menu();
                         }
-                        if ui.button("Load Workspaces...").clicked() {
-                            let default_path = self
-                                .last_workspace_file
-                                .clone()
-                                .unwrap_or_else(|| "workspaces.json".to_string());
-                            if let Some(chosen) = rfd::FileDialog::new()
-                                .set_file_name(&default_path)
+                        if ui
+                            .add(egui::Button::new("Load Workspaces...").shortcut_text("Ctrl+O"))
+                            .clicked()
+                        {
+                            self.prompt_load_workspaces();
+                            ui.close_menu();
+                        }
+                        if ui
+                            .button("Snapshot Current Layout")
+                            .on_hover_text(
+                                "Create a workspace holding every visible window where it is now",
+                            )
+                            .clicked()
+                        {
+                            let name = format!("Snapshot {}", current_timestamp());
+                            let snapshot = snapshot_workspace(name, self.main_window);
+                            info!(
+                                "Captured {} windows into workspace '{}'.",
+                                snapshot.windows.len(),
+                                snapshot.name
+                            );
+                            self.add_workspace(snapshot);
+                            ui.close_menu();
+                        }
+                        if ui
+                            .button("Rescale Layout...")
+                            .on_hover_text(
+                                "Scale every saved position for a screen of a different size",
+                            )
+                            .clicked()
+                        {
+                            let screen = primary_screen_size();
+                            self.rescale_dialog = Some(RescaleDialog {
+                                from: screen,
+                                to: screen,
+                            });
+                            ui.close_menu();
+                        }
+                        if ui.button("Merge Workspaces...").clicked() {
+                            if let Some(chosen) = self.file_dialog()
+                                .add_filter("Workspaces", WORKSPACE_FILE_EXTENSIONS)
                                 .pick_file()
                                 .map(|p| p.to_string_lossy().to_string())
                             {
-                                self.load_workspaces_from_file(&chosen);
+                                self.merge_workspaces_from_file(&chosen);
                             }
                             ui.close_menu();
                         }
+                        if ui
+                            .button("Compare Workspaces...")
+                            .on_hover_text("Show what changed between two workspace files")
+                            .clicked()
+                        {
+                            self.prompt_compare_workspaces();
+                            ui.close_menu();
+                        }
+                        ui.menu_button("Restore from backup", |ui| {
+                            let workspace_path = self
+                                .last_workspace_file
+                                .clone()
+                                .unwrap_or_else(|| self.data_path("workspaces.json"));
+                            let backups = list_backups(&workspace_path);
+                            if backups.is_empty() {
+                                ui.label("No backups available");
+                            }
+                            for backup in backups {
+                                let label = backup
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().to_string())
+                                    .unwrap_or_default();
+                                if ui.button(label).clicked() {
+                                    self.restore_workspaces_from_backup(
+                                        &backup.to_string_lossy(),
+                                        &workspace_path,
+                                    );
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    });
+                    ui.menu_button("Window Bindings", |ui| {
                         if ui.button("Save Window Bindings...").clicked() {
                             let default_path = self
                                 .last_bindings_file
                                 .clone()
-                                .unwrap_or_else(|| "window_handles.json".to_string());
+                                .unwrap_or_else(|| self.data_path("window_handles.json"));
 
-                            if let Some(chosen) = rfd::FileDialog::new()
-                                .set_file_name(&default_path)
+                            if let Some(chosen) = self.file_dialog_for(&default_path)
                                 .save_file()
                                 .map(|p| p.to_string_lossy().to_string())
                             {
@@ -403,20 +842,124 @@ impl App {
                             }
                             ui.close_menu();
                         }
+                        if ui.button("Load Window Bindings...").clicked() {
+                            let default_path = self
+                                .last_bindings_file
+                                .clone()
+                                .unwrap_or_else(|| self.data_path("window_handles.json"));
+
+                            if let Some(chosen) = self.file_dialog_for(&default_path)
+                                .pick_file()
+                                .map(|p| p.to_string_lossy().to_string())
+                            {
+                                match self.load_and_apply_window_bindings(&chosen) {
+                                    Ok(stats) => {
+                                        log_binding_stats(&chosen, stats);
+                                        if stats.restored > 0 || stats.invalidated > 0 {
+                                            self.unsaved_changes = true;
+                                        }
+                                        self.last_bindings_file = Some(chosen.clone());
+                                        save_settings(&self.current_settings());
+                                        show_message_box(
+                                            &format_binding_stats(stats),
+                                            "Load Window Bindings",
+                                        );
+                                    }
+                                    Err(err) => show_error_box(
+                                        &format!("Failed to load window bindings: {}", err),
+                                        "Load Window Bindings",
+                                    ),
+                                }
+                            }
+                            ui.close_menu();
+                        }
                     });
                     if ui.button("Open Log Folder").clicked() {
                         self.open_log_folder();
                         ui.close_menu();
                     }
                     if ui.button("Recapture All").clicked() {
-                        self.start_recapture_all();
+                        self.start_recapture_all(false);
+                        ui.close_menu();
+                    }
+                    if ui
+                        .button("Recapture Invalid Only")
+                        .on_hover_text("Skip windows whose handle still refers to a live window")
+                        .clicked()
+                    {
+                        self.start_recapture_all(true);
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.auto_rebind.is_none(),
+                            egui::Button::new("Rebind Launching Windows"),
+                        )
+                        .on_hover_text(format!(
+                            "Keep looking for missing windows for {} s while their \
+                             applications start, binding each one whose program and \
+                             title match",
+                            AUTO_REBIND_TIMEOUT.as_secs()
+                        ))
+                        .clicked()
+                    {
+                        self.start_auto_rebind();
                         ui.close_menu();
                     }
                     if ui.button("Settings").clicked() {
                         self.show_settings = true;
                         ui.close_menu();
                     }
+                    if ui
+                        .add(egui::Button::new("Hotkeys").shortcut_text("F1"))
+                        .clicked()
+                    {
+                        self.show_hotkey_help = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("About/Status").clicked() {
+                        self.show_status = true;
+                        ui.close_menu();
+                    }
+                });
+
+                ui.menu_button("Edit", |ui| {
+                    if ui
+                        .add_enabled(
+                            self.undo_history.can_undo(),
+                            egui::Button::new("Undo").shortcut_text("Ctrl+Z"),
+                        )
+                        .clicked()
+                    {
+                        self.undo();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.undo_history.can_redo(),
+                            egui::Button::new("Redo").shortcut_text("Ctrl+Y"),
+                        )
+                        .clicked()
+                    {
+                        self.redo();
+                        ui.close_menu();
+                    }
                 });
+
+                let toggle_key = self.hotkeys_toggle_key.lock().unwrap().clone();
+                let hotkeys_changed = {
+                    let mut enabled = self.hotkeys_enabled.lock().unwrap();
+                    let response = ui.checkbox(&mut enabled, "Hotkeys active");
+                    if toggle_key.trim().is_empty() {
+                        response
+                    } else {
+                        response.on_hover_text(format!("Press {} to flip", toggle_key))
+                    }
+                    .changed()
+                };
+                if hotkeys_changed {
+                    save_settings(&self.current_settings());
+                }
             });
         });
     }
@@ -470,12 +1013,23 @@ impl App {
                     disabled: false,
                     valid: false,
                     rotate: false,
+                    rotate_reverse: false,
+                    rotate_wrap: true,
+                    minimize_others: false,
                     rotation_offset: 0,
+                    offscreen: false,
+                    invalid_windows: 0,
+                    expanded: true,
+                    activation_delay_ms: 0,
+                    strict_toggle: false,
+                    steal_focus: true,
+                    require_focus_member: false,
                 });
             }
             if ui.button("Send All Home").clicked() {
                 self.send_all_home();
             }
+            self.render_recent_workspaces(ui);
             let label = if self.all_expanded {
                 "Collapse All"
             } else {
@@ -485,8 +1039,189 @@ impl App {
                 self.all_expanded = !self.all_expanded;
                 self.expand_all_signal = Some(self.all_expanded);
             }
+
+            let all_disabled = {
+                let workspaces = self.workspaces.lock().unwrap();
+                !workspaces.is_empty() && workspaces.iter().all(|ws| ws.disabled)
+            };
+            let (label, hover) = if all_disabled {
+                (
+                    "Enable All",
+                    "Re-enable every workspace and register its hotkey",
+                )
+            } else {
+                (
+                    "Disable All Hotkeys",
+                    "Disable every workspace so none of their hotkeys fire",
+                )
+            };
+            if ui.button(label).on_hover_text(hover).clicked() {
+                self.set_all_workspaces_disabled(!all_disabled);
+            }
+
+            let elapsed = self.last_deleted_at.map(|at| at.elapsed());
+            if elapsed.is_some_and(|elapsed| elapsed >= UNDO_DELETE_TIMEOUT) {
+                self.last_deleted = None;
+                self.last_deleted_at = None;
+            }
+            if let (Some((_, workspace)), Some(elapsed)) = (&self.last_deleted, elapsed) {
+                ui.ctx()
+                    .request_repaint_after(UNDO_DELETE_TIMEOUT - elapsed);
+                if ui
+                    .button(format!("Undo delete '{}'", workspace.name))
+                    .clicked()
+                {
+                    self.undo_delete_workspace();
+                }
+            }
+        });
+    }
+    /// Renders the "Recent" dropdown listing recently toggled workspaces;
+    /// choosing one toggles it again.
+    fn render_recent_workspaces(&mut self, ui: &mut egui::Ui) {
+        let recent: Vec<String> = {
+            let workspaces = self.workspaces.lock().unwrap();
+            self.recent
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|name| workspaces.iter().any(|ws| &ws.name == *name))
+                .cloned()
+                .collect()
+        };
+        let mut chosen = None;
+        ui.add_enabled_ui(!recent.is_empty(), |ui| {
+            egui::ComboBox::from_id_salt("recent_workspaces")
+                .selected_text("Recent")
+                .show_ui(ui, |ui| {
+                    for name in &recent {
+                        if ui.selectable_label(false, name).clicked() {
+                            chosen = Some(name.clone());
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Toggle a recently used workspace again");
+        });
+
+        if let Some(name) = chosen {
+            let animation = *self.move_animation.lock().unwrap();
+            let verify_moves = *self.verify_moves.lock().unwrap();
+            let mut workspaces = self.workspaces.lock().unwrap();
+            if let Some(ws) = workspaces.iter_mut().find(|ws| ws.name == name) {
+                info!("Toggling recent workspace '{}'.", ws.name);
+                toggle_workspace_windows(ws, animation, verify_moves);
+                push_recent(&mut self.recent.lock().unwrap(), &name);
+            }
+        }
+    }
+
+    /// Renders the search field that finds windows by title across all
+    /// workspaces; clicking a result jumps to the workspace holding it.
+    fn render_window_search(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Find window:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.window_search)
+                    .hint_text("Title or alias")
+                    .desired_width(240.0),
+            );
+            if !self.window_search.is_empty() && ui.small_button("Clear").clicked() {
+                self.window_search.clear();
+            }
+        });
+
+        if self.window_search.trim().is_empty() {
+            return;
+        }
+
+        let results: Vec<(usize, String)> = {
+            let workspaces = self.workspaces.lock().unwrap();
+            find_windows(&workspaces, &self.window_search)
+                .into_iter()
+                .map(|(ws, win)| {
+                    let workspace = &workspaces[ws];
+                    (
+                        ws,
+                        format!(
+                            "{}  ({})",
+                            workspace.windows[win].display_name(),
+                            workspace.name
+                        ),
+                    )
+                })
+                .collect()
+        };
+        if results.is_empty() {
+            ui.weak("No matching windows.");
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .id_salt("window_search_results")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                for (ws, label) in results {
+                    if ui
+                        .link(label)
+                        .on_hover_text("Show the workspace holding this window")
+                        .clicked()
+                    {
+                        self.scroll_to_workspace = Some(ws);
+                    }
+                }
+            });
+    }
+
+    /// Renders the status bar showing the last triggered hotkey and workspace totals.
+    fn render_status_bar(&self, ui: &mut egui::Ui) {
+        let hotkey_text = match self.last_hotkey_info.lock().unwrap().as_ref() {
+            Some((sequence, at)) => {
+                format!(
+                    "Last hotkey: {} ({} ago)",
+                    sequence,
+                    format_elapsed(at.elapsed())
+                )
+            }
+            None => "Last hotkey: none".to_string(),
+        };
+
+        let (total, with_invalid) = {
+            let workspaces = self.workspaces.lock().unwrap();
+            let with_invalid = workspaces
+                .iter()
+                .filter(|ws| ws.windows.iter().any(|w| !w.valid))
+                .count();
+            (workspaces.len(), with_invalid)
+        };
+
+        ui.horizontal(|ui| {
+            if !*self.hotkeys_enabled.lock().unwrap() {
+                ui.colored_label(egui::Color32::ORANGE, "Hotkeys suspended");
+                ui.separator();
+            }
+            if let Some(state) = self.auto_rebind {
+                let left = state.deadline.saturating_duration_since(Instant::now());
+                ui.colored_label(
+                    egui::Color32::LIGHT_BLUE,
+                    format!("Waiting for launching windows ({} s left)", left.as_secs()),
+                );
+                ui.separator();
+            }
+            ui.label(hotkey_text);
+            ui.separator();
+            ui.label(format!(
+                "{} workspace{}, {} with invalid windows, unsaved changes: {}",
+                total,
+                if total == 1 { "" } else { "s" },
+                with_invalid,
+                if self.unsaved_changes { "yes" } else { "no" }
+            ));
         });
+
+        // Keep the "ago" text current without user interaction.
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
     }
+
     /// Renders the list of workspaces in the application's GUI.
     ///
     /// This function displays each workspace as a collapsible header, allowing users to view and edit details.
@@ -526,6 +1261,14 @@ impl App {
 
         let mut any_changed = false;
         let mut requested_hotkey: Option<usize> = None;
+        let mut requested_import: Option<usize> = None;
+        let mut requested_recapture: Option<(usize, usize)> = None;
+        let mut requested_add_windows: Option<usize> = None;
+        let mut requested_countdown: Option<usize> = None;
+        let mut requested_gather: Option<usize> = None;
+        let mut duplicate_index: Option<usize> = None;
+        let mut export_index: Option<usize> = None;
+        let mut import_requested = false;
         egui::ScrollArea::both()
             .auto_shrink([false; 2])
             .show(ui, |ui| {
@@ -536,13 +1279,20 @@ impl App {
                     workspace.validate_workspace();
                     let header_text = workspace.get_header_text();
                     let header_id = egui::Id::new(format!("workspace_{}_header", i));
+                    let jump_here = self.scroll_to_workspace == Some(i);
+                    if jump_here {
+                        workspace.expanded = true;
+                    }
 
                     let mut state =
                         egui::collapsing_header::CollapsingState::load_with_default_open(
                             ui.ctx(),
                             header_id,
-                            true,
+                            workspace.expanded,
                         );
+                    // The header id is index based, so the workspace's own flag wins
+                    // over any state egui remembered for this slot.
+                    state.set_open(workspace.expanded);
                     if let Some(expand) = self.expand_all_signal {
                         state.set_open(expand);
                     }
@@ -555,16 +1305,43 @@ impl App {
                                     self.rename_dialog = Some((i, workspace.name.clone()));
                                     ui.close_menu();
                                 }
+                                if ui.button("Duplicate").clicked() {
+                                    duplicate_index = Some(i);
+                                    ui.close_menu();
+                                }
+                                if ui.button("Export Workspace...").clicked() {
+                                    export_index = Some(i);
+                                    ui.close_menu();
+                                }
+                                if ui.button("Import Workspace...").clicked() {
+                                    import_requested = true;
+                                    ui.close_menu();
+                                }
                             });
                         })
                         .body(|ui| {
-                            let (changed, open_dialog) = workspace.render_details(ui, self);
-                            if changed {
+                            let details = workspace.render_details(ui, self);
+                            if details.changed {
                                 any_changed = true;
                             }
-                            if open_dialog {
+                            if details.open_hotkey_dialog {
                                 requested_hotkey = Some(i);
                             }
+                            if details.open_import_dialog {
+                                requested_import = Some(i);
+                            }
+                            if details.open_add_windows_dialog {
+                                requested_add_windows = Some(i);
+                            }
+                            if let Some(j) = details.recapture_window {
+                                requested_recapture = Some((i, j));
+                            }
+                            if details.capture_with_countdown {
+                                requested_countdown = Some(i);
+                            }
+                            if details.gather_here {
+                                requested_gather = Some(i);
+                            }
 
                             let mut context = WorkspaceControlContext {
                                 workspace_to_delete,
@@ -579,21 +1356,90 @@ impl App {
                             }
                         });
 
+                    if jump_here {
+                        header_inner.response.scroll_to_me(Some(egui::Align::TOP));
+                    }
+
                     // Attach right-click context menu to the header for renaming
                     header_inner.response.context_menu(|ui| {
                         if ui.button("Rename").clicked() {
                             self.rename_dialog = Some((i, workspace.name.clone()));
                             ui.close_menu();
                         }
+                        if ui.button("Duplicate").clicked() {
+                            duplicate_index = Some(i);
+                            ui.close_menu();
+                        }
+                        if ui.button("Export Workspace...").clicked() {
+                            export_index = Some(i);
+                            ui.close_menu();
+                        }
+                        if ui.button("Import Workspace...").clicked() {
+                            import_requested = true;
+                            ui.close_menu();
+                        }
                     });
+
+                    // Write the (possibly toggled) open state back so it is saved
+                    // with the workspaces. It is only view state, so it does not
+                    // count as an unsaved change.
+                    workspace.expanded =
+                        egui::collapsing_header::CollapsingState::load(ui.ctx(), header_id)
+                            .map_or(workspace.expanded, |state| state.is_open());
                 }
             });
         if any_changed {
             self.unsaved_changes = true;
+            self.undo_history.mark_changed();
         }
 
         // Reset expand_all_signal after use
         self.expand_all_signal = None;
+        self.scroll_to_workspace = None;
+
+        if let Some(i) = duplicate_index {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            if let Some(copy) = workspaces.get(i).map(Workspace::duplicated) {
+                info!(
+                    "Duplicated workspace '{}' as '{}'.",
+                    workspaces[i].name, copy.name
+                );
+                workspaces.insert(i + 1, copy);
+                self.unsaved_changes = true;
+                self.undo_history.mark_changed();
+            }
+        }
+
+        if let Some(i) = export_index {
+            let workspace = self.workspaces.lock().unwrap().get(i).cloned();
+            if let Some(workspace) = workspace {
+                if let Some(chosen) = self
+                    .file_dialog()
+                    .set_file_name(format!("{}.json", workspace.name))
+                    .add_filter("Workspace", &["json"])
+                    .save_file()
+                    .map(|p| p.to_string_lossy().to_string())
+                {
+                    if let Err(e) = export_workspace(&workspace, &chosen) {
+                        error!("Failed to export workspace '{}': {}", workspace.name, e);
+                        show_error_box(
+                            &format!("Failed to export workspace to '{}':\n{}", chosen, e),
+                            "Export Workspace",
+                        );
+                    }
+                }
+            }
+        }
+        if import_requested {
+            if let Some(chosen) = self
+                .file_dialog()
+                .add_filter("Workspace", &["json"])
+                .pick_file()
+                .map(|p| p.to_string_lossy().to_string())
+            {
+                self.import_workspace_from_file(&chosen);
+            }
+        }
 
         // Move workspace up/down if requested
         if let Some(i) = move_up_index {
@@ -601,6 +1447,7 @@ impl App {
             if i > 0 {
                 workspaces.swap(i, i - 1);
                 self.unsaved_changes = true;
+                self.undo_history.mark_changed();
             }
         }
         if let Some(i) = move_down_index {
@@ -608,12 +1455,36 @@ impl App {
             if i < workspaces.len() - 1 {
                 workspaces.swap(i, i + 1);
                 self.unsaved_changes = true;
+                self.undo_history.mark_changed();
             }
         }
 
         if let Some(idx) = requested_hotkey {
             self.hotkey_dialog = Some((idx, String::new()));
         }
+        if let Some((ws_idx, win_idx)) = requested_recapture {
+            self.start_recapture(vec![(ws_idx, win_idx)]);
+        }
+        if let Some(idx) = requested_countdown {
+            self.start_capture_countdown(CountdownCapture::AddWindow(idx));
+        }
+        if let Some(idx) = requested_gather {
+            self.gather_workspace_here(idx);
+        }
+        if let Some(idx) = requested_add_windows {
+            self.add_windows_dialog = Some(AddWindowsDialog {
+                target: idx,
+                candidates: list_capturable_windows(),
+                selected: Vec::new(),
+            });
+        }
+        if let Some(idx) = requested_import {
+            self.import_dialog = Some(ImportWindowsDialog {
+                target: idx,
+                source: None,
+                selected: Vec::new(),
+            });
+        }
 
         // Take the dialog state out to avoid borrow conflicts
         if let Some((index, mut name_buf)) = self.rename_dialog.take() {
@@ -627,10 +1498,17 @@ impl App {
                 .show(ui.ctx(), |ui| {
                     ui.label("Enter new workspace name:");
                     let text_response = ui.text_edit_singleline(&mut name_buf);
+                    // Focus the name field when the dialog opens so it can be typed into right away.
+                    if ui.memory(|mem| mem.focused().is_none()) {
+                        text_response.request_focus();
+                    }
 
                     if text_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
                         rename_confirmed = true;
                     }
+                    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        close_dialog = true;
+                    }
                     ui.horizontal(|ui| {
                         if ui.button("OK").clicked() {
                             rename_confirmed = true;
@@ -644,8 +1522,15 @@ impl App {
             if rename_confirmed {
                 let mut workspaces = self.workspaces.lock().unwrap();
                 if let Some(ws) = workspaces.get_mut(index) {
+                    // Keep the "Recent" entry for this workspace.
+                    for name in self.recent.lock().unwrap().iter_mut() {
+                        if *name == ws.name {
+                            name.clone_from(&name_buf);
+                        }
+                    }
                     ws.name = name_buf;
                     self.unsaved_changes = true;
+                    self.undo_history.mark_changed();
                 }
                 // Dialog stays closed
             } else if !close_dialog {
@@ -659,6 +1544,7 @@ impl App {
         if let Some((index, mut sequence)) = self.hotkey_dialog.take() {
             let mut close_dialog = false;
             let mut confirm = false;
+            let mut test = false;
 
             egui::Window::new("Set Hotkey")
                 .collapsible(false)
@@ -674,6 +1560,9 @@ impl App {
                             &sequence
                         }
                     ));
+                    if let Some(warning) = hotkey_layout_warning(&sequence) {
+                        ui.colored_label(egui::Color32::ORANGE, warning);
+                    }
 
                     ui.ctx().input(|i| {
                         for ev in &i.events {
@@ -717,21 +1606,444 @@ impl App {
                         if ui.button("Cancel").clicked() {
                             close_dialog = true;
                         }
+                        if ui
+                            .button("Test")
+                            .on_hover_text(
+                                "Toggle this workspace's windows now, without saving the hotkey",
+                            )
+                            .clicked()
+                        {
+                            test = true;
+                        }
                     });
                 });
 
-            if confirm {
-                let mut workspaces = self.workspaces.lock().unwrap();
-                if let Some(ws) = workspaces.get_mut(index) {
-                    match ws.set_hotkey(self, &sequence) {
-                        Ok(()) => self.unsaved_changes = true,
-                        Err(e) => show_error_box(&e, "Hotkey Error"),
+            if test {
+                // Toggle a copy so the window moves and any message box run
+                // without holding the lock the hotkey thread needs.
+                let copy = self.workspaces.lock().unwrap().get(index).cloned();
+                if let Some(mut ws) = copy {
+                    info!("Testing the toggle of workspace '{}'.", ws.name);
+                    let animation = *self.move_animation.lock().unwrap();
+                    let verify_moves = *self.verify_moves.lock().unwrap();
+                    toggle_workspace_windows(&mut ws, animation, verify_moves);
+                    let mut workspaces = self.workspaces.lock().unwrap();
+                    if let Some(original) = workspaces
+                        .get_mut(index)
+                        .filter(|original| original.name == ws.name)
+                    {
+                        original.rotation_offset = ws.rotation_offset;
+                    }
+                }
+            }
+
+            // Ask before taking the lock, so the modal box does not stall the
+            // hotkey thread.
+            if confirm
+                && !hotkey_has_modifier(&sequence)
+                && !show_confirmation_box(
+                    "Binding to a single key with no modifiers will trigger during \
+                     normal typing. Continue?",
+                    "Hotkey Without Modifiers",
+                )
+            {
+                info!("Kept the previous hotkey of workspace {}.", index);
+            } else if confirm {
+                let result = self
+                    .workspaces
+                    .lock()
+                    .unwrap()
+                    .get_mut(index)
+                    .map(|ws| ws.set_hotkey(self, &sequence));
+                match result {
+                    Some(Ok(true)) => {
+                        self.unsaved_changes = true;
+                        self.undo_history.mark_changed();
                     }
+                    Some(Err(e)) => show_error_box(&e, "Hotkey Error"),
+                    Some(Ok(false)) | None => {}
                 }
             } else if !close_dialog {
                 self.hotkey_dialog = Some((index, sequence));
             }
         }
+
+        if let Some(dialog) = self.import_dialog.take() {
+            self.render_import_dialog(ui.ctx(), dialog);
+        }
+
+        if let Some(dialog) = self.add_windows_dialog.take() {
+            self.render_add_windows_dialog(ui.ctx(), dialog);
+        }
+
+        if let Some(dialog) = self.rescale_dialog.take() {
+            self.render_rescale_dialog(ui.ctx(), dialog);
+        }
+
+        if let Some(comparison) = self.workspace_comparison.take() {
+            self.render_workspace_comparison(ui.ctx(), comparison);
+        }
+    }
+
+    /// Asks for an older and a newer workspace file and compares them.
+    fn prompt_compare_workspaces(&mut self) {
+        let pick = |title: &str| {
+            self.file_dialog()
+                .set_title(title)
+                .add_filter("Workspaces", WORKSPACE_FILE_EXTENSIONS)
+                .pick_file()
+                .map(|p| p.to_string_lossy().to_string())
+        };
+        let Some(older) = pick("Older workspace file") else {
+            return;
+        };
+        let Some(newer) = pick("Newer workspace file") else {
+            return;
+        };
+        let read = |path: &str| {
+            read_workspaces(path).map_err(|e| format!("Failed to read '{}': {}", path, e))
+        };
+        match read(&older).and_then(|a| read(&newer).map(|b| diff_workspaces(&a, &b))) {
+            Ok(diff) => {
+                self.workspace_comparison = Some(WorkspaceComparison { older, newer, diff });
+            }
+            Err(e) => {
+                warn!("{}", e);
+                show_error_box(&e, "Compare Workspaces");
+            }
+        }
+    }
+
+    /// Renders the differences found by "Compare Workspaces" as a tree.
+    fn render_workspace_comparison(
+        &mut self,
+        ctx: &egui::Context,
+        comparison: WorkspaceComparison,
+    ) {
+        let describe = |rect: Rect| format!("({}, {}) {}x{}", rect.0, rect.1, rect.2, rect.3);
+        let diff = &comparison.diff;
+        let mut open = true;
+        egui::Window::new("Compare Workspaces")
+            .open(&mut open)
+            .default_size([480.0, 360.0])
+            .show(ctx, |ui| {
+                ui.label(format!("Older: {}", comparison.older));
+                ui.label(format!("Newer: {}", comparison.newer));
+                ui.separator();
+                if diff.is_empty() {
+                    ui.label("The files contain the same workspaces.");
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for name in &diff.added {
+                        ui.colored_label(egui::Color32::GREEN, format!("+ {}", name));
+                    }
+                    for name in &diff.removed {
+                        ui.colored_label(egui::Color32::RED, format!("- {}", name));
+                    }
+                    for changes in &diff.changed {
+                        egui::CollapsingHeader::new(format!("~ {}", changes.name))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                if let Some((before, after)) = &changes.hotkey {
+                                    ui.label(format!(
+                                        "Hotkey: {} -> {}",
+                                        before.as_deref().unwrap_or("none"),
+                                        after.as_deref().unwrap_or("none")
+                                    ));
+                                }
+                                for title in &changes.added_windows {
+                                    ui.colored_label(egui::Color32::GREEN, format!("+ {}", title));
+                                }
+                                for title in &changes.removed_windows {
+                                    ui.colored_label(egui::Color32::RED, format!("- {}", title));
+                                }
+                                for window in &changes.moved_windows {
+                                    ui.label(format!("~ {}", window.title));
+                                    ui.indent(&window.title, |ui| {
+                                        if let Some((before, after)) = window.home {
+                                            ui.label(format!(
+                                                "Home: {} -> {}",
+                                                describe(before),
+                                                describe(after)
+                                            ));
+                                        }
+                                        if let Some((before, after)) = window.target {
+                                            ui.label(format!(
+                                                "Target: {} -> {}",
+                                                describe(before),
+                                                describe(after)
+                                            ));
+                                        }
+                                    });
+                                }
+                            });
+                    }
+                });
+            });
+        if open {
+            self.workspace_comparison = Some(comparison);
+        }
+    }
+
+    /// Renders the "Rescale Layout" dialog, which scales the positions of every
+    /// window in every workspace from one screen size to another.
+    fn render_rescale_dialog(&mut self, ctx: &egui::Context, mut dialog: RescaleDialog) {
+        let mut close_dialog = false;
+        let mut confirm = false;
+
+        // Dry run on a copy so the dialog can say what would change.
+        let current = self.workspaces.lock().unwrap().clone();
+        let (_, affected) = rescale_workspaces(current, dialog.from, dialog.to);
+
+        egui::Window::new("Rescale Layout")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let size_row = |ui: &mut egui::Ui, label: &str, size: &mut (i32, i32)| {
+                    ui.horizontal(|ui| {
+                        ui.label(label);
+                        ui.add(egui::DragValue::new(&mut size.0).range(1..=16384));
+                        ui.label("x");
+                        ui.add(egui::DragValue::new(&mut size.1).range(1..=16384));
+                        if ui
+                            .small_button("Primary monitor")
+                            .on_hover_text("Use the resolution of the primary monitor")
+                            .clicked()
+                        {
+                            *size = primary_screen_size();
+                        }
+                    });
+                };
+                size_row(ui, "Captured on:", &mut dialog.from);
+                size_row(ui, "Fit to:", &mut dialog.to);
+
+                ui.label(format!(
+                    "Scale: {:.3} x {:.3}",
+                    dialog.to.0 as f64 / dialog.from.0 as f64,
+                    dialog.to.1 as f64 / dialog.from.1 as f64
+                ));
+                ui.label(format!(
+                    "{} window{} would be moved.",
+                    affected,
+                    if affected == 1 { "" } else { "s" }
+                ));
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(affected > 0, egui::Button::new("Rescale"))
+                        .clicked()
+                    {
+                        confirm = true;
+                    }
+                    if ui.button("Cancel").clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape))
+                    {
+                        close_dialog = true;
+                    }
+                });
+            });
+
+        if confirm {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let (rescaled, affected) =
+                rescale_workspaces(std::mem::take(&mut *workspaces), dialog.from, dialog.to);
+            *workspaces = rescaled;
+            info!(
+                "Rescaled {} window(s) from {}x{} to {}x{}.",
+                affected, dialog.from.0, dialog.from.1, dialog.to.0, dialog.to.1
+            );
+            self.unsaved_changes = true;
+            self.undo_history.mark_changed();
+        } else if !close_dialog {
+            self.rescale_dialog = Some(dialog);
+        }
+    }
+
+    /// Renders the "Add Windows" picker, which adds the checked windows to
+    /// `dialog.target` at their current positions.
+    fn render_add_windows_dialog(&mut self, ctx: &egui::Context, mut dialog: AddWindowsDialog) {
+        let mut close_dialog = false;
+        let mut confirm = false;
+
+        egui::Window::new("Add Windows")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if dialog.candidates.is_empty() {
+                    ui.label("No visible windows were found.");
+                } else {
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            for (index, (_, title)) in dialog.candidates.iter().enumerate() {
+                                let mut checked = dialog.selected.contains(&index);
+                                if ui.checkbox(&mut checked, title).changed() {
+                                    if checked {
+                                        dialog.selected.push(index);
+                                    } else {
+                                        dialog.selected.retain(|&i| i != index);
+                                    }
+                                }
+                            }
+                        });
+                }
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!dialog.selected.is_empty(), egui::Button::new("Add"))
+                        .clicked()
+                    {
+                        confirm = true;
+                    }
+                    if ui.button("Refresh").clicked() {
+                        dialog.candidates = list_capturable_windows();
+                        dialog.selected.clear();
+                    }
+                    if ui.button("Cancel").clicked()
+                        || ui.input(|i| i.key_pressed(egui::Key::Escape))
+                    {
+                        close_dialog = true;
+                    }
+                });
+            });
+
+        if confirm {
+            dialog.selected.sort_unstable();
+            let mut workspaces = self.workspaces.lock().unwrap();
+            if let Some(target) = workspaces.get_mut(dialog.target) {
+                for &index in &dialog.selected {
+                    let (hwnd, title) = dialog.candidates[index].clone();
+                    let hwnd = HWND(hwnd as *mut c_void);
+                    if unsafe { IsWindow(hwnd).as_bool() } {
+                        target.windows.push(Window::capture(hwnd, title));
+                    } else {
+                        warn!("Window '{}' closed before it could be added.", title);
+                    }
+                }
+                info!(
+                    "Added {} window(s) to workspace '{}'.",
+                    dialog.selected.len(),
+                    target.name
+                );
+                self.unsaved_changes = true;
+                self.undo_history.mark_changed();
+            }
+        } else if !close_dialog {
+            self.add_windows_dialog = Some(dialog);
+        }
+    }
+
+    /// Renders the "Import Windows" dialog, which copies windows from another
+    /// workspace into `dialog.target`.
+    fn render_import_dialog(&mut self, ctx: &egui::Context, mut dialog: ImportWindowsDialog) {
+        let mut close_dialog = false;
+        let mut confirm = false;
+
+        let (workspace_names, source_windows) = {
+            let workspaces = self.workspaces.lock().unwrap();
+            let names: Vec<(usize, String)> = workspaces
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != dialog.target)
+                .map(|(i, ws)| (i, ws.name.clone()))
+                .collect();
+            let windows: Vec<String> = dialog
+                .source
+                .and_then(|source| workspaces.get(source))
+                .map(|ws| {
+                    ws.windows
+                        .iter()
+                        .map(|w| {
+                            format!(
+                                "{} (home {:?}, target {:?})",
+                                w.display_label(),
+                                w.home,
+                                w.target
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            (names, windows)
+        };
+
+        egui::Window::new("Import Windows")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if workspace_names.is_empty() {
+                    ui.label("There are no other workspaces to import from.");
+                } else {
+                    let selected_text = dialog
+                        .source
+                        .and_then(|source| workspace_names.iter().find(|(i, _)| *i == source))
+                        .map(|(_, name)| name.clone())
+                        .unwrap_or_else(|| "Select workspace".to_string());
+                    ui.horizontal(|ui| {
+                        ui.label("From workspace:");
+                        egui::ComboBox::from_id_salt("import_windows_source")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                for (index, name) in &workspace_names {
+                                    if ui
+                                        .selectable_value(&mut dialog.source, Some(*index), name)
+                                        .changed()
+                                    {
+                                        dialog.selected.clear();
+                                    }
+                                }
+                            });
+                    });
+
+                    for (index, label) in source_windows.iter().enumerate() {
+                        let mut checked = dialog.selected.contains(&index);
+                        if ui.checkbox(&mut checked, label).changed() {
+                            if checked {
+                                dialog.selected.push(index);
+                            } else {
+                                dialog.selected.retain(|&i| i != index);
+                            }
+                        }
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!dialog.selected.is_empty(), egui::Button::new("Import"))
+                        .clicked()
+                    {
+                        confirm = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+
+        if confirm {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let source = dialog
+                .source
+                .and_then(|source| workspaces.get(source).cloned());
+            if let (Some(source), Some(target)) = (source, workspaces.get_mut(dialog.target)) {
+                dialog.selected.sort_unstable();
+                target.import_windows_from(&source, &dialog.selected);
+                info!(
+                    "Imported {} window(s) from '{}' into '{}'.",
+                    dialog.selected.len(),
+                    source.name,
+                    target.name
+                );
+                self.unsaved_changes = true;
+                self.undo_history.mark_changed();
+            }
+        } else if !close_dialog {
+            self.import_dialog = Some(dialog);
+        }
     }
 
     /// Renders the controls for managing individual workspaces.
@@ -789,7 +2101,7 @@ impl App {
 
             if ui.button("Delete Workspace").clicked() {
                 let confirmation_message = format!(
-                    "Are you sure you want to delete workspace '{}'? This action cannot be undone.",
+                    "Are you sure you want to delete workspace '{}'?",
                     &workspace.name
                 );
                 if show_confirmation_box(&confirmation_message, "Confirm Deletion") {
@@ -845,26 +2157,18 @@ impl App {
         let default_path = self
             .last_workspace_file
             .clone()
-            .unwrap_or_else(|| "workspaces.json".to_string());
+            .unwrap_or_else(|| self.data_path("workspaces.json"));
         self.save_workspaces_to_file(&default_path);
     }
 
     /// Save workspaces to the specified path and persist the choice.
     pub fn save_workspaces_to_file(&mut self, path: &str) {
         let workspaces = self.workspaces.lock().unwrap();
-        save_workspaces(&workspaces, path);
+        save_workspaces(&workspaces, path, self.max_backups, self.pretty_json);
         self.last_workspace_file = Some(path.to_string());
         self.unsaved_changes = false;
         info!("Workspaces saved successfully.");
-        save_settings(&Settings {
-            save_on_exit: self.save_on_exit,
-            auto_save: self.auto_save,
-            log_level: self.log_level.clone(),
-            last_layout_file: self.last_layout_file.clone(),
-            last_workspace_file: self.last_workspace_file.clone(),
-            last_bindings_file: self.last_bindings_file.clone(),
-            developer_debugging: self.developer_debugging,
-        });
+        save_settings(&self.current_settings());
     }
 
     fn save_window_bindings_to_file(&mut self, path: &str) -> Result<usize, WindowBindingError> {
@@ -874,15 +2178,7 @@ impl App {
 
         if result.is_ok() {
             self.last_bindings_file = Some(path.to_string());
-            save_settings(&Settings {
-                save_on_exit: self.save_on_exit,
-                auto_save: self.auto_save,
-                log_level: self.log_level.clone(),
-                last_layout_file: self.last_layout_file.clone(),
-                last_workspace_file: self.last_workspace_file.clone(),
-                last_bindings_file: self.last_bindings_file.clone(),
-                developer_debugging: self.developer_debugging,
-            });
+            save_settings(&self.current_settings());
         }
 
         result
@@ -899,7 +2195,7 @@ impl App {
 
         let stats = {
             let mut workspaces = self.workspaces.lock().unwrap();
-            apply_window_bindings(&mut workspaces, &bindings)
+            apply_window_bindings(&mut workspaces, &bindings, self.binding_match_threshold)
         };
 
         Ok(stats)
@@ -967,6 +2263,7 @@ impl App {
         let mut workspaces = self.workspaces.lock().unwrap();
         workspaces.push(workspace);
         self.unsaved_changes = true;
+        self.undo_history.mark_changed();
     }
 
     /// Deletes a workspace from the list by its index.
@@ -977,6 +2274,8 @@ impl App {
     /// # Behavior
     /// - Locks the `workspaces` mutex to modify the list.
     /// - Removes the workspace at the specified index from the list.
+    /// - Keeps the removed workspace in `last_deleted` so it can be restored with
+    ///   "Undo delete" until the timeout expires or another workspace is deleted.
     ///
     /// # Parameters
     /// - `index: usize`: The zero-based index of the workspace to delete.
@@ -991,11 +2290,8 @@ impl App {
     /// - Any changes made to the workspace list are not persisted to disk until `save_workspaces` is called.
     ///
     /// # Notes
-    /// - If the `index` is out of bounds, the function will panic as it directly calls `Vec::remove`.
+    /// - If the `index` is out of bounds, nothing is removed.
     /// - If the workspace has a registered hotkey, it will be unregistered before removal.
-    ///
-    /// # Error Conditions
-    /// - Panics if the `index` is greater than or equal to the length of the `workspaces` list.
     fn delete_workspace(&mut self, index: usize) {
         let mut workspaces = self.workspaces.lock().unwrap();
         if let Some(workspace) = workspaces.get_mut(index) {
@@ -1003,7 +2299,81 @@ impl App {
                 hotkey.unregister(self);
             }
         }
-        workspaces.remove(index);
+        if index < workspaces.len() {
+            let workspace = workspaces.remove(index);
+            self.last_deleted = Some((index, workspace));
+            self.last_deleted_at = Some(Instant::now());
+        }
+        self.unsaved_changes = true;
+        self.undo_history.mark_changed();
+    }
+
+    /// Restores the most recently deleted workspace at its previous position
+    /// and re-registers its hotkey.
+    fn undo_delete_workspace(&mut self) {
+        self.last_deleted_at = None;
+        let Some((index, mut workspace)) = self.last_deleted.take() else {
+            return;
+        };
+
+        let mut workspaces = self.workspaces.lock().unwrap();
+        let index = index.min(workspaces.len());
+        self.register_workspace_hotkey(index, &mut workspace);
+        info!("Restored deleted workspace '{}'.", workspace.name);
+        workspaces.insert(index, workspace);
+        self.unsaved_changes = true;
+        self.undo_history.mark_changed();
+    }
+
+    /// Records the current workspaces as an undo step, after an edit changed them.
+    pub fn push_undo_snapshot(&mut self) {
+        let workspaces = self.workspaces.lock().unwrap();
+        self.undo_history.record(&workspaces);
+    }
+
+    /// Reverts the workspaces to the state before the last edit.
+    pub fn undo(&mut self) {
+        if let Some(snapshot) = self.undo_history.undo() {
+            info!("Undid the last workspace edit.");
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    /// Re-applies the last edit reverted with [`App::undo`].
+    pub fn redo(&mut self) {
+        if let Some(snapshot) = self.undo_history.redo() {
+            info!("Redid a workspace edit.");
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    /// Replaces the workspaces with an undo/redo `snapshot`, re-registering
+    /// hotkeys if the snapshot binds them differently.
+    fn restore_snapshot(&mut self, mut snapshot: Vec<Workspace>) {
+        let bindings = |workspaces: &[Workspace]| -> Vec<Option<String>> {
+            workspaces
+                .iter()
+                .map(|ws| {
+                    ws.hotkey
+                        .as_ref()
+                        .filter(|_| !ws.disabled)
+                        .map(|hotkey| hotkey.key_sequence.clone())
+                })
+                .collect()
+        };
+
+        let mut workspaces = self.workspaces.lock().unwrap();
+        if bindings(&workspaces) != bindings(&snapshot) {
+            for ws in workspaces.iter() {
+                if let Some(ref hotkey) = ws.hotkey {
+                    hotkey.unregister(self);
+                }
+            }
+            for (i, ws) in snapshot.iter_mut().enumerate() {
+                self.register_workspace_hotkey(i, ws);
+            }
+        }
+        *workspaces = snapshot;
         self.unsaved_changes = true;
     }
 
@@ -1020,40 +2390,41 @@ impl App {
             .show(ctx, |ui| {
                 let response = ui.checkbox(&mut self.save_on_exit, "Save on exit");
                 if response.changed() {
-                    save_settings(&Settings {
-                        save_on_exit: self.save_on_exit,
-                        auto_save: self.auto_save,
-                        log_level: self.log_level.clone(),
-                        last_layout_file: None,
-                        last_workspace_file: self.last_workspace_file.clone(),
-                        last_bindings_file: self.last_bindings_file.clone(),
-                        developer_debugging: self.developer_debugging,
-                    });
+                    save_settings(&self.current_settings());
                 }
                 let auto_response = ui.checkbox(&mut self.auto_save, "Auto-save");
                 if auto_response.changed() {
-                    save_settings(&Settings {
-                        save_on_exit: self.save_on_exit,
-                        auto_save: self.auto_save,
-                        log_level: self.log_level.clone(),
-                        last_layout_file: self.last_layout_file.clone(),
-                        last_workspace_file: self.last_workspace_file.clone(),
-                        last_bindings_file: self.last_bindings_file.clone(),
-                        developer_debugging: self.developer_debugging,
-                    });
+                    save_settings(&self.current_settings());
+                }
+                if ui
+                    .checkbox(&mut self.start_with_windows, "Start with Windows")
+                    .changed()
+                {
+                    if let Err(e) = set_start_with_windows(self.start_with_windows) {
+                        error!("{}", e);
+                        show_error_box(&e, "Start with Windows");
+                        self.start_with_windows = registered_command().is_some();
+                    }
+                    save_settings(&self.current_settings());
                 }
                 let dev_response =
                     ui.checkbox(&mut self.developer_debugging, "Developer Debugging");
                 if dev_response.changed() {
-                    save_settings(&Settings {
-                        save_on_exit: self.save_on_exit,
-                        auto_save: self.auto_save,
-                        log_level: self.log_level.clone(),
-                        last_layout_file: self.last_layout_file.clone(),
-                        last_workspace_file: self.last_workspace_file.clone(),
-                        last_bindings_file: self.last_bindings_file.clone(),
-                        developer_debugging: self.developer_debugging,
+                    save_settings(&self.current_settings());
+                }
+                let mut theme_changed = false;
+                egui::ComboBox::from_label("Theme")
+                    .selected_text(self.theme.label())
+                    .show_ui(ui, |ui| {
+                        for theme in Theme::ALL {
+                            theme_changed |= ui
+                                .selectable_value(&mut self.theme, theme, theme.label())
+                                .clicked();
+                        }
                     });
+                if theme_changed {
+                    apply_theme(ctx, self.theme);
+                    save_settings(&self.current_settings());
                 }
                 let mut changed = false;
                 egui::ComboBox::from_label("Log Level")
@@ -1068,16 +2439,191 @@ impl App {
                             }
                         }
                     });
+                let log_size_response = ui
+                    .horizontal(|ui| {
+                        ui.label("Roll log over at:");
+                        let size = ui.add(
+                            egui::DragValue::new(&mut self.log_max_size_mb)
+                                .range(1..=1024)
+                                .suffix(" MB"),
+                        );
+                        ui.label("keeping");
+                        let keep = ui.add(
+                            egui::DragValue::new(&mut self.log_keep_files)
+                                .range(0..=20)
+                                .suffix(" old files"),
+                        );
+                        size | keep
+                    })
+                    .inner
+                    .on_hover_text("Takes effect the next time Multi Manager starts");
+                changed |= log_size_response.changed();
                 if changed {
-                    save_settings(&Settings {
-                        save_on_exit: self.save_on_exit,
-                        auto_save: self.auto_save,
-                        log_level: self.log_level.clone(),
-                        last_layout_file: self.last_layout_file.clone(),
-                        last_workspace_file: self.last_workspace_file.clone(),
-                        last_bindings_file: self.last_bindings_file.clone(),
-                        developer_debugging: self.developer_debugging,
-                    });
+                    save_settings(&self.current_settings());
+                }
+                let threshold_response = ui
+                    .add(
+                        egui::Slider::new(&mut self.binding_match_threshold, 0.0..=1.0)
+                            .text("Binding title match threshold"),
+                    )
+                    .on_hover_text(
+                        "Minimum title similarity for restoring a saved window binding \
+                         when the exact title no longer matches",
+                    );
+                if threshold_response.changed() {
+                    save_settings(&self.current_settings());
+                }
+                let backups_response = ui
+                    .horizontal(|ui| {
+                        ui.label("Workspace backups to keep:");
+                        ui.add(egui::DragValue::new(&mut self.max_backups).range(0..=100))
+                    })
+                    .inner
+                    .on_hover_text("Set to 0 to disable backups");
+                if backups_response.changed() {
+                    save_settings(&self.current_settings());
+                }
+                if ui
+                    .checkbox(&mut self.pretty_json, "Indent saved JSON files")
+                    .on_hover_text("Turn off to write compact single-line JSON")
+                    .changed()
+                {
+                    save_settings(&self.current_settings());
+                }
+                let animation_changed = {
+                    let mut animation = self.move_animation.lock().unwrap();
+                    let toggled = ui
+                        .checkbox(&mut animation.enabled, "Animate window moves")
+                        .changed();
+                    let duration_changed = ui
+                        .add_enabled(
+                            animation.enabled,
+                            egui::Slider::new(&mut animation.duration_ms, 50..=1000)
+                                .text("Animation duration (ms)"),
+                        )
+                        .changed();
+                    toggled || duration_changed
+                };
+                if animation_changed {
+                    save_settings(&self.current_settings());
+                }
+                let verify_changed = {
+                    let mut verify = self.verify_moves.lock().unwrap();
+                    ui.checkbox(&mut verify, "Verify window moves")
+                        .on_hover_text(
+                            "Re-check each window after toggling and move it again \
+                             if it did not stay put",
+                        )
+                        .changed()
+                };
+                if verify_changed {
+                    save_settings(&self.current_settings());
+                }
+                let mut compensate = frame_bounds_compensation();
+                if ui
+                    .checkbox(&mut compensate, "Ignore invisible window borders")
+                    .on_hover_text(
+                        "Capture and place windows by their visible edges. Turn off to \
+                         use the full window rectangle as older versions did",
+                    )
+                    .changed()
+                {
+                    set_frame_bounds_compensation(compensate);
+                    save_settings(&self.current_settings());
+                }
+                if ui
+                    .checkbox(&mut self.minimize_to_tray, "Minimize to tray")
+                    .changed()
+                {
+                    save_settings(&self.current_settings());
+                }
+                if ui
+                    .checkbox(&mut self.start_minimized, "Start minimized to tray")
+                    .changed()
+                {
+                    save_settings(&self.current_settings());
+                }
+                let toggle_key_changed = {
+                    let mut toggle_key = self.hotkeys_toggle_key.lock().unwrap();
+                    let response = ui
+                        .horizontal(|ui| {
+                            ui.label("Suspend/resume hotkeys with:");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut *toggle_key)
+                                    .hint_text("none")
+                                    .desired_width(140.0),
+                            )
+                        })
+                        .inner;
+                    if !toggle_key.trim().is_empty() {
+                        if let Err(e) = normalize_hotkey(&toggle_key) {
+                            ui.colored_label(egui::Color32::RED, e);
+                        }
+                    }
+                    response.changed()
+                };
+                if toggle_key_changed {
+                    save_settings(&self.current_settings());
+                }
+                let poll_changed = {
+                    let mut poll_ms = self.hotkey_poll_ms.lock().unwrap();
+                    ui.add(
+                        egui::Slider::new(&mut *poll_ms, HOTKEY_POLL_RANGE_MS)
+                            .logarithmic(true)
+                            .text("Hotkey polling interval (ms)"),
+                    )
+                    .on_hover_text("Lower reacts to hotkeys faster, higher uses less CPU")
+                    .changed()
+                };
+                if poll_changed {
+                    save_settings(&self.current_settings());
+                }
+                if ui
+                    .add(
+                        egui::Slider::new(&mut self.capture_countdown_secs, 1..=30)
+                            .text("Capture countdown (s)"),
+                    )
+                    .on_hover_text("Delay before a countdown capture grabs the foreground window")
+                    .changed()
+                {
+                    save_settings(&self.current_settings());
+                }
+                if ui
+                    .add(
+                        egui::Slider::new(&mut self.desktop_switch_delay_ms, 0..=2000)
+                            .text("Desktop switch delay (ms)"),
+                    )
+                    .on_hover_text(
+                        "Wait after Restore All Desktops switches desktop before moving \
+                         windows; raise it if windows land on the wrong desktop",
+                    )
+                    .changed()
+                {
+                    save_settings(&self.current_settings());
+                }
+                if ui
+                    .checkbox(
+                        &mut self.origin_current_desktop_only,
+                        "Move All to Origin: current desktop only",
+                    )
+                    .changed()
+                {
+                    save_settings(&self.current_settings());
+                }
+                ui.label("Processes to leave alone in Move All to Origin (one per line):");
+                let mut excluded = self.origin_exclude_processes.join("\n");
+                if ui
+                    .add(
+                        egui::TextEdit::multiline(&mut excluded)
+                            .desired_rows(3)
+                            .hint_text("e.g. notepad.exe"),
+                    )
+                    .changed()
+                {
+                    // Keep blank lines while editing; they are dropped when saved.
+                    self.origin_exclude_processes =
+                        excluded.split('\n').map(str::to_string).collect();
+                    save_settings(&self.current_settings());
                 }
                 let mut path = self.last_layout_file.clone().unwrap_or_default();
                 ui.horizontal(|ui| {
@@ -1088,15 +2634,7 @@ impl App {
                         } else {
                             self.last_layout_file = Some(path.clone());
                         }
-                        save_settings(&Settings {
-                            save_on_exit: self.save_on_exit,
-                            auto_save: self.auto_save,
-                            log_level: self.log_level.clone(),
-                            last_layout_file: self.last_layout_file.clone(),
-                            last_workspace_file: self.last_workspace_file.clone(),
-                            last_bindings_file: self.last_bindings_file.clone(),
-                            developer_debugging: self.developer_debugging,
-                        });
+                        save_settings(&self.current_settings());
                     }
                 });
                 let mut bindings_path = self.last_bindings_file.clone().unwrap_or_default();
@@ -1108,25 +2646,185 @@ impl App {
                         } else {
                             self.last_bindings_file = Some(bindings_path.clone());
                         }
-                        save_settings(&Settings {
-                            save_on_exit: self.save_on_exit,
-                            auto_save: self.auto_save,
-                            log_level: self.log_level.clone(),
-                            last_layout_file: self.last_layout_file.clone(),
-                            last_workspace_file: self.last_workspace_file.clone(),
-                            last_bindings_file: self.last_bindings_file.clone(),
-                            developer_debugging: self.developer_debugging,
-                        });
+                        save_settings(&self.current_settings());
                     }
                 });
-                if ui.button("Close").clicked() {
+                let mut data_dir = self.data_dir.clone().unwrap_or_default();
+                ui.horizontal(|ui| {
+                    ui.label("Data folder:");
+                    let mut changed = ui
+                        .add(
+                            egui::TextEdit::singleline(&mut data_dir)
+                                .hint_text(app_dir().to_string_lossy()),
+                        )
+                        .on_hover_text(
+                            "Folder file dialogs open in and default files and logs are kept in",
+                        )
+                        .changed();
+                    if ui.button("Browse...").clicked() {
+                        if let Some(dir) = self.file_dialog().pick_folder() {
+                            data_dir = dir.to_string_lossy().to_string();
+                            changed = true;
+                        }
+                    }
+                    if changed {
+                        // Trimmed when saved, so spaces can be typed mid-path.
+                        self.data_dir = Some(data_dir).filter(|d| !d.trim().is_empty());
+                        save_settings(&self.current_settings());
+                    }
+                });
+                if ui.button("Close").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
                     self.show_settings = false;
                 }
             });
     }
 
+    /// Displays a read-only summary of workspaces, windows and hotkeys when
+    /// `self.show_status` is `true`, to help diagnose hotkeys that stopped working.
+    fn render_status_window(&mut self, ctx: &egui::Context) {
+        let (workspace_count, window_count, valid_count) = {
+            let workspaces = self.workspaces.lock().unwrap();
+            let windows: Vec<&Window> = workspaces.iter().flat_map(|ws| &ws.windows).collect();
+            let valid = windows
+                .iter()
+                .filter(|w| unsafe { IsWindow(HWND(w.id as *mut c_void)).as_bool() })
+                .count();
+            (workspaces.len(), windows.len(), valid)
+        };
+        let hotkey_count = self.registered_hotkeys.lock().unwrap().len();
+        let hotkeys_enabled = *self.hotkeys_enabled.lock().unwrap();
+
+        let center = ctx.available_rect().center();
+        let mut open = true;
+        egui::Window::new("About/Status")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .default_pos(center)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} {}",
+                    self.app_title_name,
+                    env!("CARGO_PKG_VERSION")
+                ));
+                ui.separator();
+                egui::Grid::new("status_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Workspaces:");
+                        ui.label(workspace_count.to_string());
+                        ui.end_row();
+                        ui.label("Windows:");
+                        ui.label(window_count.to_string());
+                        ui.end_row();
+                        ui.label("Valid windows:");
+                        ui.label(format!("{} of {}", valid_count, window_count));
+                        ui.end_row();
+                        ui.label("Registered hotkeys:");
+                        ui.label(hotkey_count.to_string());
+                        ui.end_row();
+                        ui.label("Hotkeys active:");
+                        ui.label(if hotkeys_enabled { "yes" } else { "no" });
+                        ui.end_row();
+                        ui.label("Log level:");
+                        ui.label(log::max_level().to_string().to_lowercase());
+                        ui.end_row();
+                    });
+                if ui.button("Close").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    self.show_status = false;
+                }
+            });
+        if !open {
+            self.show_status = false;
+        }
+    }
+
+    /// Renders the hotkey overview shown with F1: every workspace hotkey with
+    /// whether it is currently registered, followed by the application-wide keys.
+    fn render_hotkey_help(&mut self, ctx: &egui::Context) {
+        let workspace_keys: Vec<(String, String, &'static str)> = {
+            let workspaces = self.workspaces.lock().unwrap();
+            let registered = self.registered_hotkeys.lock().unwrap();
+            workspaces
+                .iter()
+                .filter_map(|ws| {
+                    let hotkey = ws.hotkey.as_ref()?;
+                    let status = if ws.disabled {
+                        "disabled"
+                    } else if registered.contains_key(&hotkey.key_sequence) {
+                        "active"
+                    } else {
+                        "not registered"
+                    };
+                    Some((hotkey.key_sequence.clone(), ws.name.clone(), status))
+                })
+                .collect()
+        };
+        let toggle_key = self.hotkeys_toggle_key.lock().unwrap().trim().to_string();
+        let hotkeys_enabled = *self.hotkeys_enabled.lock().unwrap();
+
+        let mut open = true;
+        egui::Window::new("Hotkeys")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if !hotkeys_enabled {
+                    ui.colored_label(egui::Color32::ORANGE, "Workspace hotkeys are suspended.");
+                }
+                ui.strong("Workspaces");
+                if workspace_keys.is_empty() {
+                    ui.label("No workspace has a hotkey.");
+                } else {
+                    egui::Grid::new("hotkey_help_workspaces")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (sequence, name, status) in &workspace_keys {
+                                ui.monospace(sequence);
+                                ui.label(name);
+                                ui.weak(*status);
+                                ui.end_row();
+                            }
+                        });
+                }
+                ui.separator();
+                ui.strong("Application");
+                egui::Grid::new("hotkey_help_global")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        if !toggle_key.is_empty() {
+                            ui.monospace(&toggle_key);
+                            ui.label("Suspend or resume workspace hotkeys");
+                            ui.end_row();
+                        }
+                        for (keys, action) in [
+                            ("Ctrl+S", "Save workspaces"),
+                            ("Ctrl+O", "Load workspaces"),
+                            ("Ctrl+Z", "Undo"),
+                            ("Ctrl+Y", "Redo"),
+                            ("F1", "Show or hide this list"),
+                        ] {
+                            ui.monospace(keys);
+                            ui.label(action);
+                            ui.end_row();
+                        }
+                    });
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    self.show_hotkey_help = false;
+                }
+            });
+        if !open {
+            self.show_hotkey_help = false;
+        }
+    }
+
     /// Sends every window in all workspaces back to its configured home position.
-    fn send_all_home(&self) {
+    pub fn send_all_home(&self) {
         let (actionable_workspaces, actionable_count) = {
             let workspaces = self.workspaces.lock().unwrap();
             let mut actionable_count = 0usize;
@@ -1165,11 +2863,10 @@ impl App {
 
     /// Open the folder containing `multi_manager.log` using Windows Explorer.
     fn open_log_folder(&self) {
-        use std::path::PathBuf;
         use std::process::Command;
 
-        let log_path = std::fs::canonicalize("multi_manager.log")
-            .unwrap_or_else(|_| PathBuf::from("multi_manager.log"));
+        let log_path = log_path(self.data_dir.as_deref());
+        let log_path = std::fs::canonicalize(&log_path).unwrap_or(log_path);
 
         if let Err(e) = Command::new("explorer").arg(&log_path).spawn() {
             show_error_box(&format!("Failed to open log folder: {}", e), "Error");
@@ -1177,20 +2874,395 @@ impl App {
     }
 
     /// Begin recapturing all windows across every workspace.
-    fn start_recapture_all(&mut self) {
-        self.recapture_queue.clear();
-        let workspaces = self.workspaces.lock().unwrap();
-        for (wi, ws) in workspaces.iter().enumerate() {
-            for (wj, _w) in ws.windows.iter().enumerate() {
-                self.recapture_queue.push((wi, wj));
-            }
+    ///
+    /// With `invalid_only`, windows whose HWND still passes `IsWindow` are not
+    /// queued, which skips everything that survived a restart of the owning app.
+    fn start_recapture_all(&mut self, invalid_only: bool) {
+        let queue: Vec<(usize, usize)> = {
+            let workspaces = self.workspaces.lock().unwrap();
+            workspaces
+                .iter()
+                .enumerate()
+                .flat_map(|(wi, ws)| {
+                    ws.windows
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, w)| {
+                            !invalid_only
+                                || !unsafe { IsWindow(HWND(w.id as *mut c_void)).as_bool() }
+                        })
+                        .map(move |(wj, _)| (wi, wj))
+                })
+                .collect()
+        };
+        if invalid_only && queue.is_empty() {
+            show_message_box(
+                "Every captured window still has a valid handle.",
+                "Recapture Invalid Only",
+            );
         }
+        self.start_recapture(queue);
+    }
+
+    /// Begin the recapture prompt for the given `(workspace, window)` indices.
+    fn start_recapture(&mut self, queue: Vec<(usize, usize)>) {
+        self.recapture_queue = queue;
         self.recapture_active = !self.recapture_queue.is_empty();
         // Clear any pending key state to avoid accidental captures
         let _ = poll_recapture_keys();
     }
 
-    /// Handle the recapture-all workflow, displaying a floating panel and
+    /// Starts a countdown after which the foreground window is captured for `purpose`.
+    fn start_capture_countdown(&mut self, purpose: CountdownCapture) {
+        let delay = Duration::from_secs(self.capture_countdown_secs.into());
+        self.capture_countdown = Some((purpose, Instant::now() + delay));
+        info!(
+            "Capturing the foreground window in {}s.",
+            self.capture_countdown_secs
+        );
+    }
+
+    /// Returns the time left on a countdown started for `purpose`, or `None` if
+    /// no such countdown is running. An expired countdown reports zero.
+    fn countdown_remaining(&self, purpose: CountdownCapture) -> Option<Duration> {
+        self.capture_countdown
+            .filter(|(running, _)| *running == purpose)
+            .map(|(_, deadline)| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Returns the foreground window for a countdown capture, refusing this
+    /// application's own window, which is in front if the user never switched away.
+    fn countdown_foreground_window(&self) -> Option<(HWND, String)> {
+        let (hwnd, title) = get_active_window()?;
+        if self.main_window == Some(hwnd.0 as isize) {
+            warn!("Countdown capture ignored: Multi Manager itself was in the foreground.");
+            show_message_box(
+                "Multi Manager was still the foreground window when the countdown ended.\n\
+                 Switch to the window to capture before the countdown runs out.",
+                "Capture",
+            );
+            return None;
+        }
+        Some((hwnd, title))
+    }
+
+    /// Shows the countdown for adding a window and captures the foreground
+    /// window into the workspace once it expires.
+    fn process_add_window_countdown(&mut self, ctx: &egui::Context) {
+        let Some((CountdownCapture::AddWindow(ws_idx), _)) = self.capture_countdown else {
+            return;
+        };
+        let remaining = self
+            .countdown_remaining(CountdownCapture::AddWindow(ws_idx))
+            .unwrap_or_default();
+
+        if remaining.is_zero() {
+            self.capture_countdown = None;
+            if let Some((hwnd, title)) = self.countdown_foreground_window() {
+                let mut workspaces = self.workspaces.lock().unwrap();
+                if let Some(workspace) = workspaces.get_mut(ws_idx) {
+                    let window = Window::capture(hwnd, title);
+                    info!(
+                        "Captured window '{}' into workspace '{}' after countdown.",
+                        window.title, workspace.name
+                    );
+                    workspace.windows.push(window);
+                    self.unsaved_changes = true;
+                    self.undo_history.mark_changed();
+                }
+            }
+            return;
+        }
+
+        let mut cancel = false;
+        egui::Window::new("Capture")
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 20.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Capturing the foreground window in {}s...",
+                    remaining.as_secs_f32().ceil() as u64
+                ));
+                ui.label("Switch to the window you want to add.");
+                cancel = ui.button("Cancel").clicked();
+            });
+        if cancel {
+            info!("Countdown capture canceled.");
+            self.capture_countdown = None;
+        }
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+
+    /// Rebinds window `win_idx` of workspace `ws_idx` to the currently active window.
+    fn recapture_window_from_active(&mut self, ws_idx: usize, win_idx: usize) {
+        let Some((hwnd, title)) = get_active_window() else {
+            warn!("Recapture canceled or no active window detected.");
+            return;
+        };
+        self.rebind_window(ws_idx, win_idx, hwnd, title);
+    }
+
+    /// Starts scanning for missing windows every [`AUTO_REBIND_INTERVAL`] until
+    /// all are bound or [`AUTO_REBIND_TIMEOUT`] passes.
+    fn start_auto_rebind(&mut self) {
+        let now = Instant::now();
+        info!(
+            "Waiting up to {} s for missing windows to appear.",
+            AUTO_REBIND_TIMEOUT.as_secs()
+        );
+        self.auto_rebind = Some(AutoRebind {
+            deadline: now + AUTO_REBIND_TIMEOUT,
+            next_scan: now,
+            rebound: 0,
+        });
+    }
+
+    /// Runs a due scan of "Rebind Launching Windows", binding every missing
+    /// window that now has a live match, and reports the result once nothing
+    /// is missing or the deadline passes.
+    fn process_auto_rebind(&mut self, ctx: &egui::Context) {
+        let Some(mut state) = self.auto_rebind else {
+            return;
+        };
+        let now = Instant::now();
+        if now < state.next_scan {
+            ctx.request_repaint_after(state.next_scan - now);
+            return;
+        }
+
+        let (matches, live, missing_count) = {
+            let workspaces = self.workspaces.lock().unwrap();
+            let mut missing = Vec::new();
+            let mut bound = std::collections::HashSet::new();
+            for (i, ws) in workspaces.iter().enumerate() {
+                for (j, window) in ws.windows.iter().enumerate() {
+                    if !window.enabled {
+                        continue;
+                    }
+                    if window.matches_live(HWND(window.id as *mut c_void)) {
+                        bound.insert(window.id as isize);
+                    } else {
+                        missing.push((i, j));
+                    }
+                }
+            }
+            let live: Vec<LiveWindow> = list_capturable_windows()
+                .into_iter()
+                .filter(|(hwnd, _)| !bound.contains(hwnd) && self.main_window != Some(*hwnd))
+                .map(|(hwnd, title)| LiveWindow {
+                    hwnd,
+                    title,
+                    fingerprint: window_fingerprint(HWND(hwnd as *mut c_void)),
+                })
+                .collect();
+            let matches =
+                match_missing_windows(&workspaces, &missing, &live, self.binding_match_threshold);
+            (matches, live, missing.len())
+        };
+
+        for &((ws_idx, win_idx), i) in &matches {
+            let candidate = &live[i];
+            self.rebind_window(
+                ws_idx,
+                win_idx,
+                HWND(candidate.hwnd as *mut c_void),
+                candidate.title.clone(),
+            );
+        }
+        if !matches.is_empty() {
+            state.rebound += matches.len();
+            self.undo_history.mark_changed();
+        }
+
+        let still_missing = missing_count - matches.len();
+        if still_missing == 0 || now >= state.deadline {
+            self.auto_rebind = None;
+            info!(
+                "Rebind Launching Windows finished: {} bound, {} still missing.",
+                state.rebound, still_missing
+            );
+            show_message_box(
+                &format!(
+                    "Bound {} window{}; {} still missing.",
+                    state.rebound,
+                    if state.rebound == 1 { "" } else { "s" },
+                    still_missing
+                ),
+                "Rebind Launching Windows",
+            );
+            return;
+        }
+        state.next_scan = now + AUTO_REBIND_INTERVAL;
+        self.auto_rebind = Some(state);
+        ctx.request_repaint_after(AUTO_REBIND_INTERVAL);
+    }
+
+    /// Points window `win_idx` of workspace `ws_idx` at `hwnd`.
+    fn rebind_window(&mut self, ws_idx: usize, win_idx: usize, hwnd: HWND, title: String) {
+        let mut workspaces = self.workspaces.lock().unwrap();
+        if let Some(win) = workspaces
+            .get_mut(ws_idx)
+            .and_then(|ws| ws.windows.get_mut(win_idx))
+        {
+            win.id = hwnd.0 as usize;
+            win.title = title;
+            win.fingerprint = window_fingerprint(hwnd);
+            win.valid = true;
+            win.sync_alias_from_title_if_missing();
+            info!(
+                "Recaptured window '{}', new HWND: {:?}",
+                win.display_label(),
+                hwnd
+            );
+            self.unsaved_changes = true;
+        }
+    }
+
+    /// Show the "Save All Desktops" dialog, where the include and exclude
+    /// filters are edited before the layout file is chosen.
+    fn render_capture_filter(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut save = false;
+        let mut changed = false;
+        egui::Window::new("Save All Desktops")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("Enter title substrings or executable names, one per line.");
+                ui.label("Only record windows matching (leave empty for all windows):");
+                let mut include = self.desktop_capture_include.join("\n");
+                if ui
+                    .add(
+                        egui::TextEdit::multiline(&mut include)
+                            .desired_rows(3)
+                            .hint_text("e.g. Code.exe"),
+                    )
+                    .changed()
+                {
+                    // Keep blank lines while editing; they are dropped when saved.
+                    self.desktop_capture_include =
+                        include.split('\n').map(str::to_string).collect();
+                    changed = true;
+                }
+                ui.label("Skip windows matching:");
+                let mut exclude = self.desktop_capture_exclude.join("\n");
+                if ui
+                    .add(
+                        egui::TextEdit::multiline(&mut exclude)
+                            .desired_rows(3)
+                            .hint_text("e.g. Private Browsing"),
+                    )
+                    .changed()
+                {
+                    self.desktop_capture_exclude =
+                        exclude.split('\n').map(str::to_string).collect();
+                    changed = true;
+                }
+                ui.horizontal(|ui| {
+                    save = ui.button("Save...").clicked();
+                    if ui.button("Cancel").clicked() {
+                        self.show_capture_filter = false;
+                    }
+                });
+            });
+        if changed {
+            save_settings(&self.current_settings());
+        }
+        if !open {
+            self.show_capture_filter = false;
+        }
+        if !save {
+            return;
+        }
+
+        self.show_capture_filter = false;
+        let default_path = self
+            .last_layout_file
+            .clone()
+            .unwrap_or_else(|| self.data_path("desktop_layout.json"));
+        let Some(chosen) = self
+            .file_dialog_for(&default_path)
+            .save_file()
+            .map(|p| p.to_string_lossy().to_string())
+        else {
+            return;
+        };
+        self.last_layout_file = Some(chosen.clone());
+        let settings = self.current_settings();
+        save_settings(&settings);
+        let filter = CaptureFilter::from_settings(&settings);
+        self.start_desktop_job("Save All Desktops", move |progress| {
+            capture_all_desktops(&chosen, &filter, progress)
+                .map(|count| format!("Saved {} window(s) to {}", count, chosen))
+        });
+    }
+
+    /// Run a desktop capture or restore on a background thread, showing its
+    /// progress until it finishes.
+    fn start_desktop_job<F>(&mut self, title: &'static str, job: F)
+    where
+        F: FnOnce(&DesktopProgress) -> Result<String, String> + Send + 'static,
+    {
+        let progress = Arc::new(DesktopProgress::default());
+        let worker_progress = Arc::clone(&progress);
+        let promise = Promise::spawn_thread(title, move || job(&worker_progress));
+        self.desktop_job = Some(DesktopJob {
+            title,
+            progress,
+            promise: Arc::new(Mutex::new(promise)),
+        });
+    }
+
+    /// Show the progress of the running desktop job, cancel it on Esc and
+    /// report its outcome once the worker finishes.
+    fn process_desktop_job(&mut self, ctx: &egui::Context) {
+        let Some(job) = self.desktop_job.clone() else {
+            return;
+        };
+
+        let outcome = job.promise.lock().unwrap().ready().cloned();
+        if let Some(outcome) = outcome {
+            self.desktop_job = None;
+            match outcome {
+                Ok(message) => show_message_box(&message, job.title),
+                Err(e) => show_error_box(&e, job.title),
+            }
+            return;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            job.progress.cancel();
+        }
+        let (processed, total) = job.progress.counts();
+        egui::Window::new(job.title)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 20.0])
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if total == 0 {
+                    ui.label("Collecting windows...");
+                } else {
+                    ui.label(format!("Processed {} of {} windows", processed, total));
+                    ui.add(egui::ProgressBar::new(processed as f32 / total as f32));
+                }
+                if job.progress.is_cancelled() {
+                    ui.label("Cancelling...");
+                } else {
+                    ui.horizontal(|ui| {
+                        if ui.button("Cancel").clicked() {
+                            job.progress.cancel();
+                        }
+                        ui.label("or press Esc");
+                    });
+                }
+            });
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+
+    /// Handle the recapture workflow, displaying a floating panel and
     /// updating window handles when the user confirms.
     fn process_recapture_all(&mut self, ctx: &egui::Context) {
         if !self.recapture_active {
@@ -1198,6 +3270,20 @@ impl App {
         }
 
         if let Some(&(ws_idx, win_idx)) = self.recapture_queue.first() {
+            let countdown = self.countdown_remaining(CountdownCapture::Recapture);
+            if countdown.is_some_and(|remaining| remaining.is_zero()) {
+                self.capture_countdown = None;
+                if let Some((hwnd, title)) = self.countdown_foreground_window() {
+                    self.rebind_window(ws_idx, win_idx, hwnd, title);
+                    self.recapture_queue.remove(0);
+                }
+                if self.recapture_queue.is_empty() {
+                    self.recapture_active = false;
+                }
+                ctx.request_repaint();
+                return;
+            }
+
             let (ws_name, win_label) = {
                 let workspaces = self.workspaces.lock().unwrap();
                 let ws_name = workspaces
@@ -1212,7 +3298,10 @@ impl App {
                 (ws_name, win_label)
             };
 
-            egui::Window::new("Recapture All")
+            let remaining = self.recapture_queue.len();
+            let countdown_secs = self.capture_countdown_secs;
+            let mut start_countdown = false;
+            egui::Window::new("Recapture")
                 .anchor(egui::Align2::CENTER_TOP, [0.0, 20.0])
                 .collapsible(false)
                 .resizable(false)
@@ -1221,31 +3310,41 @@ impl App {
                         "Recapturing workspace '{}' window '{}'",
                         ws_name, win_label
                     ));
-                    ui.label("Focus the desired window and press Enter to capture, 'S' to skip, or Esc to cancel.");
+                    if remaining > 1 {
+                        ui.label(format!("{} windows remaining", remaining));
+                    }
+                    if let Some(left) = countdown {
+                        ui.label(format!(
+                            "Capturing the foreground window in {}s... Switch to the desired window, or press 'S' to skip or Esc to cancel.",
+                            left.as_secs_f32().ceil() as u64
+                        ));
+                    } else {
+                        ui.label("Focus the desired window and press Enter to capture, 'S' to skip, or Esc to cancel.");
+                        start_countdown = ui
+                            .button(format!("Capture ({}s)", countdown_secs))
+                            .on_hover_text("Capture the foreground window when the countdown ends")
+                            .clicked();
+                    }
                 });
+            if start_countdown {
+                self.start_capture_countdown(CountdownCapture::Recapture);
+            }
 
             if let Some(action) = poll_recapture_keys() {
                 match action {
                     RecaptureAction::Confirm => {
-                        if let Some((hwnd, title)) = get_active_window() {
-                            let mut workspaces = self.workspaces.lock().unwrap();
-                            if let Some(ws) = workspaces.get_mut(ws_idx) {
-                                if let Some(win) = ws.windows.get_mut(win_idx) {
-                                    win.id = hwnd.0 as usize;
-                                    win.title = title;
-                                    win.valid = true;
-                                    self.unsaved_changes = true;
-                                }
-                            }
-                        }
+                        self.recapture_window_from_active(ws_idx, win_idx);
                         self.recapture_queue.remove(0);
+                        self.capture_countdown = None;
                     }
                     RecaptureAction::Skip => {
                         self.recapture_queue.remove(0);
+                        self.capture_countdown = None;
                     }
                     RecaptureAction::Cancel => {
                         self.recapture_queue.clear();
                         self.recapture_active = false;
+                        self.capture_countdown = None;
                     }
                 }
             }
@@ -1271,54 +3370,346 @@ impl App {
         if !*initial_validation_done {
             let mut workspaces = self.workspaces.lock().unwrap();
             for (i, workspace) in workspaces.iter_mut().enumerate() {
-                if workspace.disabled {
+                self.register_workspace_hotkey(i, workspace);
+            }
+            *initial_validation_done = true;
+        }
+    }
+
+    /// Sets `disabled` on every workspace, unregistering the hotkeys of newly
+    /// disabled workspaces and registering those of newly enabled ones.
+    fn set_all_workspaces_disabled(&mut self, disabled: bool) {
+        {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            for (i, workspace) in workspaces.iter_mut().enumerate() {
+                if workspace.disabled == disabled {
                     continue;
                 }
-                if let Some(ref mut hotkey) = workspace.hotkey {
-                    if !hotkey.register(self, i as i32) {
-                        warn!(
-                            "Failed to register hotkey '{}' for workspace '{}'",
-                            hotkey, workspace.name
-                        );
+                workspace.disabled = disabled;
+                if disabled {
+                    if let Some(ref hotkey) = workspace.hotkey {
+                        hotkey.unregister(self);
                     }
+                } else {
+                    self.register_workspace_hotkey(i, workspace);
                 }
             }
-            *initial_validation_done = true;
         }
+        info!(
+            "{} all workspaces.",
+            if disabled { "Disabled" } else { "Enabled" }
+        );
+        self.unsaved_changes = true;
+        self.undo_history.mark_changed();
+    }
+
+    /// Moves the windows of workspace `index` onto the current virtual desktop
+    /// and reports the outcome.
+    ///
+    /// Works on a copy, so neither the desktop moves nor the message box hold
+    /// the workspaces lock the hotkey thread needs.
+    fn gather_workspace_here(&self, index: usize) {
+        let Some(workspace) = self.workspaces.lock().unwrap().get(index).cloned() else {
+            return;
+        };
+        match gather_workspace_windows(&workspace) {
+            Ok(summary) => {
+                let mut message = format!(
+                    "Moved {} window{} to this desktop; {} already here.",
+                    summary.moved,
+                    if summary.moved == 1 { "" } else { "s" },
+                    summary.already_here
+                );
+                if summary.failed > 0 {
+                    message.push_str(&format!(
+                        "\n{} could not be moved; see the log for details.",
+                        summary.failed
+                    ));
+                }
+                show_message_box(&message, "Gather Here");
+            }
+            Err(e) => show_error_box(&e, "Gather Here"),
+        }
+    }
+
+    /// Registers the hotkey of an enabled `workspace` using `index` as its id.
+    fn register_workspace_hotkey(&self, index: usize, workspace: &mut Workspace) {
+        if workspace.disabled {
+            return;
+        }
+        if let Some(ref mut hotkey) = workspace.hotkey {
+            if !hotkey.register_or_share(self, index as i32, workspace.require_focus_member) {
+                warn!(
+                    "Failed to register hotkey '{}' for workspace '{}'",
+                    hotkey, workspace.name
+                );
+            }
+        }
+    }
+
+    /// Appends the single workspace exported to `path`, dropping its hotkey if
+    /// another workspace already uses it.
+    pub fn import_workspace_from_file(&mut self, path: &str) {
+        let mut workspace = match import_workspace(path) {
+            Ok(workspace) => workspace,
+            Err(err) => {
+                error!("Failed to import workspace from '{}': {}", path, err);
+                show_error_box(
+                    &format!("Failed to import workspace from '{}':\n{}", path, err),
+                    "Import Workspace",
+                );
+                return;
+            }
+        };
+
+        let mut workspaces = self.workspaces.lock().unwrap();
+        if let Some(hotkey) = workspace.hotkey.take_if(|hotkey| {
+            !workspace.require_focus_member && hotkey_in_use(&workspaces, &hotkey.key_sequence)
+        }) {
+            warn!(
+                "Cleared hotkey '{}' of imported workspace '{}'; it is already in use.",
+                hotkey, workspace.name
+            );
+            show_message_box(
+                &format!(
+                    "The hotkey '{}' of '{}' is already in use and was cleared.",
+                    hotkey, workspace.name
+                ),
+                "Import Workspace",
+            );
+        }
+        self.register_workspace_hotkey(workspaces.len(), &mut workspace);
+        info!("Imported workspace '{}' from '{}'.", workspace.name, path);
+        workspaces.push(workspace);
+        self.unsaved_changes = true;
+        self.undo_history.mark_changed();
+    }
+
+    /// Appends the workspaces stored in `path` to the current ones.
+    ///
+    /// For each workspace whose name is already taken the user chooses between
+    /// importing it under a numbered name and skipping it. Imported hotkeys that
+    /// are already in use are dropped. A summary is shown afterwards.
+    pub fn merge_workspaces_from_file(&mut self, path: &str) {
+        // `load_workspaces` would register hotkeys under the file's own indices,
+        // so parse with `read_workspaces` and register once appended.
+        let incoming = match read_workspaces(path) {
+            Ok(incoming) => incoming,
+            Err(err) => {
+                error!("Failed to merge workspaces from '{}': {}", path, err);
+                show_error_box(
+                    &format!("Failed to merge workspaces from '{}':\n{}", path, err),
+                    "Merge Workspaces",
+                );
+                return;
+            }
+        };
+
+        // Decide on duplicates before taking the lock, as the prompts are modal.
+        let mut known = self.workspaces.lock().unwrap().clone();
+        let existing_count = known.len();
+        let mut skipped = 0;
+        let mut dropped_hotkeys = 0;
+        for mut workspace in incoming {
+            if known
+                .iter()
+                .any(|ws| ws.name.eq_ignore_ascii_case(&workspace.name))
+            {
+                let renamed = unique_workspace_name(&known, &workspace.name);
+                if !show_confirmation_box(
+                    &format!(
+                        "A workspace named '{}' already exists.\n\
+                         Import it as '{}'? Choose No to skip it.",
+                        workspace.name, renamed
+                    ),
+                    "Merge Workspaces",
+                ) {
+                    info!("Skipped duplicate workspace '{}'.", workspace.name);
+                    skipped += 1;
+                    continue;
+                }
+                workspace.name = renamed;
+            }
+            if let Some(hotkey) = workspace.hotkey.take_if(|hotkey| {
+                !workspace.require_focus_member && hotkey_in_use(&known, &hotkey.key_sequence)
+            }) {
+                warn!(
+                    "Dropped hotkey '{}' of merged workspace '{}'; it is already in use.",
+                    hotkey, workspace.name
+                );
+                dropped_hotkeys += 1;
+            }
+            workspace.rotation_offset = 0;
+            known.push(workspace);
+        }
+
+        let added = known.len() - existing_count;
+        {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            for mut workspace in known.drain(existing_count..) {
+                self.register_workspace_hotkey(workspaces.len(), &mut workspace);
+                workspaces.push(workspace);
+            }
+        }
+        if added > 0 {
+            self.unsaved_changes = true;
+            self.undo_history.mark_changed();
+        }
+        info!(
+            "Merged {} workspace(s) from '{}', skipped {}.",
+            added, path, skipped
+        );
+
+        let mut summary = format!(
+            "Added {} workspace{} from '{}'.\nSkipped {} duplicate{}.",
+            added,
+            if added == 1 { "" } else { "s" },
+            path,
+            skipped,
+            if skipped == 1 { "" } else { "s" }
+        );
+        if dropped_hotkeys > 0 {
+            summary.push_str(&format!(
+                "\nDropped {} hotkey{} already in use.",
+                dropped_hotkeys,
+                if dropped_hotkeys == 1 { "" } else { "s" }
+            ));
+        }
+        show_message_box(&summary, "Merge Workspaces");
     }
 
     /// Load workspaces from the specified file, replacing current ones.
-    pub fn load_workspaces_from_file(&mut self, path: &str) {
+    ///
+    /// If there are unsaved changes the user is asked to confirm before they are
+    /// discarded. A summary of the loaded workspaces is shown afterwards.
+    /// Returns `false` if the user canceled the load.
+    /// Asks the hotkey thread to stop and waits for it, so it cannot toggle a
+    /// workspace while the application is shutting down.
+    fn stop_hotkey_thread(&self) {
+        self.hotkey_thread_stop.store(true, Ordering::Relaxed);
+        let promise = self.hotkey_promise.lock().unwrap().take();
+        if let Some(promise) = promise {
+            promise.block_until_ready();
+        }
+    }
+
+    /// Offers to save unsaved changes when the window is closing and they would
+    /// otherwise be lost, cancelling the close if the user asks to.
+    fn confirm_close(&mut self, ctx: &egui::Context) {
+        if !self.unsaved_changes || self.save_on_exit {
+            return;
+        }
+        match show_save_changes_box(
+            "You have unsaved changes to your workspaces. Save them before exiting?",
+            "Unsaved Changes",
+        ) {
+            SaveChoice::Save => self.save_workspaces(),
+            SaveChoice::Discard => info!("Exiting without saving workspace changes."),
+            SaveChoice::Cancel => {
+                info!("Exit canceled by user.");
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            }
+        }
+    }
+
+    /// Asks for a workspace file and loads it, confirming first if there are
+    /// unsaved changes.
+    fn prompt_load_workspaces(&mut self) {
+        let default_path = self
+            .last_workspace_file
+            .clone()
+            .unwrap_or_else(|| self.data_path("workspaces.json"));
+        if let Some(chosen) = self
+            .file_dialog_for(&default_path)
+            .add_filter("Workspaces", WORKSPACE_FILE_EXTENSIONS)
+            .pick_file()
+            .map(|p| p.to_string_lossy().to_string())
         {
+            self.load_workspaces_from_file(&chosen);
+        }
+    }
+
+    pub fn load_workspaces_from_file(&mut self, path: &str) -> bool {
+        if self.unsaved_changes
+            && !show_confirmation_box(
+                "You have unsaved changes. Discard them and load workspaces from file?",
+                "Discard Unsaved Changes",
+            )
+        {
+            info!("Loading workspaces from '{}' canceled by user.", path);
+            return false;
+        }
+
+        let mut loaded = match read_workspaces(path) {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                error!("Failed to load workspaces from '{}': {}", path, err);
+                show_error_box(
+                    &format!("Failed to load workspaces from '{}':\n{}", path, err),
+                    "Load Workspaces",
+                );
+                return false;
+            }
+        };
+
+        self.undo_history.reset(&loaded);
+        {
+            // Swap under a single lock so the hotkey thread never sees the old
+            // list with its hotkeys already unregistered, or a half-registered one.
             let mut workspaces = self.workspaces.lock().unwrap();
             for ws in workspaces.iter_mut() {
                 if let Some(ref hotkey) = ws.hotkey {
                     hotkey.unregister(self);
                 }
             }
+            register_workspace_hotkeys(&mut loaded, self);
+            *workspaces = loaded;
         }
-
-        {
-            let mut workspaces = self.workspaces.lock().unwrap();
-            *workspaces = load_workspaces(path, self);
-        }
+        self.last_deleted = None;
+        self.last_deleted_at = None;
 
         self.last_workspace_file = Some(path.to_string());
         self.unsaved_changes = false;
-        save_settings(&Settings {
-            save_on_exit: self.save_on_exit,
-            auto_save: self.auto_save,
-            log_level: self.log_level.clone(),
-            last_layout_file: self.last_layout_file.clone(),
-            last_workspace_file: self.last_workspace_file.clone(),
-            last_bindings_file: self.last_bindings_file.clone(),
-            developer_debugging: self.developer_debugging,
-        });
+        save_settings(&self.current_settings());
 
         let bindings_path = self
             .last_bindings_file
             .clone()
-            .unwrap_or_else(|| "window_handles.json".to_string());
+            .unwrap_or_else(|| self.data_path("window_handles.json"));
         self.attempt_restore_bindings(&bindings_path);
+
+        let (workspace_count, hotkey_count, registered_count) = {
+            let workspaces = self.workspaces.lock().unwrap();
+            let hotkeys = workspaces.iter().filter_map(|ws| ws.hotkey.as_ref());
+            (
+                workspaces.len(),
+                hotkeys.clone().count(),
+                hotkeys.filter(|hotkey| hotkey.id.is_some()).count(),
+            )
+        };
+        show_message_box(
+            &format!(
+                "Loaded {} workspace{} from '{}'.\nRegistered {} of {} hotkey{}.",
+                workspace_count,
+                if workspace_count == 1 { "" } else { "s" },
+                path,
+                registered_count,
+                hotkey_count,
+                if hotkey_count == 1 { "" } else { "s" }
+            ),
+            "Load Workspaces",
+        );
+        true
+    }
+
+    /// Loads the workspaces stored in `backup` while keeping `workspace_path` as
+    /// the save location, so the next save overwrites it with the restored data.
+    fn restore_workspaces_from_backup(&mut self, backup: &str, workspace_path: &str) {
+        if self.load_workspaces_from_file(backup) {
+            info!("Restored workspaces from backup '{}'.", backup);
+            self.last_workspace_file = Some(workspace_path.to_string());
+            self.unsaved_changes = true;
+            save_settings(&self.current_settings());
+        }
     }
 }
@@ -6,6 +6,15 @@ use windows::Win32::UI::Input::KeyboardAndMouse::RegisterHotKey;
 use windows::Win32::UI::Input::KeyboardAndMouse::UnregisterHotKey;
 use windows::Win32::UI::Input::KeyboardAndMouse::HOT_KEY_MODIFIERS;
 
+/// A key combination registered with Windows and the number of workspaces
+/// using it. Only workspaces with `require_focus_member` set share a
+/// registration; see [`Hotkey::register_or_share`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HotkeyRegistration {
+    pub id: usize,
+    pub users: usize,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Hotkey {
     pub key_sequence: String,
@@ -112,7 +121,13 @@ impl Hotkey {
                 if RegisterHotKey(None, id, HOT_KEY_MODIFIERS(modifiers), vk).is_ok() {
                     self.id = Some(id);
                     let mut registered_hotkeys = app.registered_hotkeys.lock().unwrap();
-                    registered_hotkeys.insert(self.key_sequence.clone(), id as usize);
+                    registered_hotkeys.insert(
+                        self.key_sequence.clone(),
+                        HotkeyRegistration {
+                            id: id as usize,
+                            users: 1,
+                        },
+                    );
                     info!("Registered hotkey '{}' with ID {}.", self.key_sequence, id);
                     return true;
                 } else {
@@ -126,6 +141,29 @@ impl Hotkey {
         false
     }
 
+    /// Registers this `Hotkey` like [`Hotkey::register`], or, if `shared` is
+    /// `true` and another workspace already registered the same combination,
+    /// joins that registration instead of calling `RegisterHotKey` again.
+    ///
+    /// Workspaces with `require_focus_member` set pass `shared`, since they only
+    /// react while one of their own windows is focused and so can reuse a
+    /// hotkey of another workspace.
+    pub fn register_or_share(&mut self, app: &crate::gui::App, id: i32, shared: bool) -> bool {
+        if shared {
+            let mut registered_hotkeys = app.registered_hotkeys.lock().unwrap();
+            if let Some(registration) = registered_hotkeys.get_mut(&self.key_sequence) {
+                registration.users += 1;
+                self.id = Some(registration.id as i32);
+                info!(
+                    "Sharing hotkey '{}' (ID {}) between {} workspaces.",
+                    self.key_sequence, registration.id, registration.users
+                );
+                return true;
+            }
+        }
+        self.register(app, id)
+    }
+
     /// Unregisters this `Hotkey` from the **global** Windows hotkey system, if it was previously registered.
     ///
     /// # Behavior
@@ -133,6 +171,8 @@ impl Hotkey {
     ///   [`UnregisterHotKey`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-unregisterhotkey)
     ///   to remove the global hotkey binding.
     /// - On success, removes the corresponding entry from `app.registered_hotkeys`.
+    /// - If other workspaces still share the registration, only drops this one's
+    ///   use of it and leaves the hotkey registered.
     /// - Logs an info-level message if the unregistration succeeds or a warning if it fails.
     /// - Returns `true` if the unregistration call succeeds, otherwise `false`.
     ///
@@ -157,6 +197,20 @@ impl Hotkey {
     /// - Only valid on Windows, as it relies on the native global hotkey mechanism.
     pub fn unregister(&self, app: &crate::gui::App) -> bool {
         if let Some(id) = self.id {
+            {
+                let mut registered_hotkeys = app.registered_hotkeys.lock().unwrap();
+                if let Some(registration) = registered_hotkeys
+                    .get_mut(&self.key_sequence)
+                    .filter(|r| r.users > 1 && r.id == id as usize)
+                {
+                    registration.users -= 1;
+                    info!(
+                        "Released shared hotkey '{}'; {} workspace(s) still use it.",
+                        self.key_sequence, registration.users
+                    );
+                    return true;
+                }
+            }
             unsafe {
                 if UnregisterHotKey(None, id).is_ok() {
                     let mut registered_hotkeys = app.registered_hotkeys.lock().unwrap();
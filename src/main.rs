@@ -1,23 +1,36 @@
 #![windows_subsystem = "windows"]
 
+mod autostart;
+mod backup;
 mod desktop_window_info;
 mod gui;
 mod hotkey;
+mod monitor;
+mod overlay;
+mod paths;
 mod settings;
+mod tray;
+mod undo;
 mod utils;
 mod virtual_desktop;
 mod window_bindings;
+mod window_icon;
 mod window_manager;
 mod workspace;
-
-use crate::settings::load_settings;
-use crate::window_manager::{capture_all_desktops, move_all_to_origin, restore_all_desktops};
+mod workspace_diff;
+
+use crate::settings::{clamp_hotkey_poll_ms, load_reports, load_settings};
+use crate::utils::show_confirmation_box;
+use crate::window_manager::{
+    capture_all_desktops, move_all_to_origin, restore_all_desktops, set_frame_bounds_compensation,
+    CaptureFilter, DesktopProgress, MoveAnimation,
+};
 use clap::{ArgAction, Parser};
-use log::info;
-use std::collections::HashMap;
+use log::{info, warn};
+use std::collections::{HashMap, VecDeque};
 use std::env;
-use std::path::PathBuf;
 use std::process::Command;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
 #[cfg(windows)]
@@ -33,6 +46,48 @@ fn ensure_console() {
 #[cfg(not(windows))]
 fn ensure_console() {}
 
+/// Title of the main window, also used to find an already running instance.
+const APP_TITLE: &str = "Multi Manager";
+
+/// Claims the named mutex that marks a running GUI instance.
+///
+/// Returns `false` if another instance already holds it. The mutex handle is
+/// intentionally never closed so it stays owned until the process exits.
+#[cfg(windows)]
+fn acquire_single_instance() -> bool {
+    use windows::core::w;
+    use windows::Win32::Foundation::{GetLastError, ERROR_ALREADY_EXISTS};
+    use windows::Win32::System::Threading::CreateMutexW;
+    unsafe {
+        match CreateMutexW(None, true, w!("Local\\MultiManagerSingleInstance")) {
+            Ok(_) => GetLastError() != ERROR_ALREADY_EXISTS,
+            Err(e) => {
+                log::warn!("Failed to create single-instance mutex: {}", e);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn acquire_single_instance() -> bool {
+    true
+}
+
+/// Brings the main window of the already running instance to the foreground.
+#[cfg(windows)]
+fn focus_existing_instance() {
+    use windows::core::HSTRING;
+    use windows::Win32::UI::WindowsAndMessaging::FindWindowW;
+    match unsafe { FindWindowW(None, &HSTRING::from(APP_TITLE)) } {
+        Ok(hwnd) => tray::show_main_window(hwnd),
+        Err(_) => log::warn!("Another instance is running but its window was not found."),
+    }
+}
+
+#[cfg(not(windows))]
+fn focus_existing_instance() {}
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Multi Manager window tool", long_about = None)]
 struct CliArgs {
@@ -42,9 +97,17 @@ struct CliArgs {
     #[arg(long = "load-desktops", default_missing_value = "desktop_layout.json", num_args = 0..=1)]
     load_desktops: Option<String>,
 
+    /// With `--load-desktops`, log the planned moves without applying them.
+    #[arg(long = "dry-run", action = ArgAction::SetTrue, requires = "load_desktops")]
+    dry_run: bool,
+
     #[arg(long = "move-origin", action = ArgAction::SetTrue)]
     move_origin: bool,
 
+    /// Send one workspace's windows (or every workspace's, if no name is given) home.
+    #[arg(long = "send-home", value_name = "NAME|INDEX", num_args = 0..=1)]
+    send_home: Option<Option<String>>,
+
     #[arg(long = "save-workspaces", default_missing_value = "workspaces.json", num_args = 0..=1)]
     save_workspaces: Option<String>,
 
@@ -56,6 +119,24 @@ struct CliArgs {
 
     #[arg(long = "edit-settings", action = ArgAction::SetTrue)]
     edit_settings: bool,
+
+    /// Print a summary of every workspace in the file (defaults to `workspaces.json`
+    /// in the data folder), then exit.
+    #[arg(long = "list-workspaces", default_missing_value = "", num_args = 0..=1)]
+    list_workspaces: Option<String>,
+
+    /// Toggle the workspace with this name or index in `workspaces.json`, then exit.
+    #[arg(long = "toggle-workspace", value_name = "NAME|INDEX")]
+    toggle_workspace: Option<String>,
+
+    /// Refresh the home rectangles of one workspace's live windows in
+    /// `workspaces.json` from their current positions, then exit.
+    #[arg(long = "recapture-workspace", value_name = "NAME|INDEX")]
+    recapture_workspace: Option<String>,
+
+    /// With `--recapture-workspace`, refresh target rectangles instead of home ones.
+    #[arg(long = "recapture-target", action = ArgAction::SetTrue, requires = "recapture_workspace")]
+    recapture_target: bool,
 }
 
 /// The main entry point for the Multi Manager application.
@@ -97,20 +178,55 @@ fn main() {
     // Ensure logging is initialized
     ensure_logging_initialized();
 
+    // Work in physical pixels on every monitor so captured rectangles are
+    // applied at the right size on mixed-DPI setups.
+    monitor::enable_dpi_awareness();
+    set_frame_bounds_compensation(load_settings().frame_bounds_compensation);
+
     // Backtrace for Debug
     env::set_var("RUST_BACKTRACE", "1");
 
     info!("Starting Multi Manager application...");
 
     if let Some(file) = args.save_desktops {
-        capture_all_desktops(&file);
-        println!("Saved desktops to {}", file);
+        let filter = CaptureFilter::from_settings(&load_settings());
+        match capture_all_desktops(&file, &filter, &DesktopProgress::default()) {
+            Ok(count) => println!("Saved {} window(s) to {}", count, file),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
         return;
     }
 
     if let Some(file) = args.load_desktops {
-        restore_all_desktops(&file);
-        println!("Restored desktops from {}", file);
+        let delay = std::time::Duration::from_millis(load_settings().desktop_switch_delay_ms);
+        match restore_all_desktops(&file, args.dry_run, delay, &DesktopProgress::default()) {
+            Ok(summary) => {
+                if args.dry_run {
+                    println!(
+                        "Would restore {} window(s) from {}; {} not found",
+                        summary.stats.matched, file, summary.stats.skipped_invalid
+                    );
+                } else {
+                    println!("{} from {}", summary.stats, file);
+                }
+                for (info, hwnd) in &summary.matched {
+                    println!(
+                        "  {} (HWND {:#x}) -> desktop {} at {:?}",
+                        info.title, hwnd, info.desktop_index, info.rect
+                    );
+                }
+                for info in &summary.missing {
+                    println!("  missing: {}", info.title);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
         return;
     }
 
@@ -125,7 +241,42 @@ fn main() {
     }
 
     if args.move_origin {
-        move_all_to_origin();
+        move_all_to_origin(&load_settings());
+        return;
+    }
+
+    if let Some(file) = args.list_workspaces {
+        let file = Some(file)
+            .filter(|file| !file.is_empty())
+            .unwrap_or_else(default_workspaces_file);
+        if let Err(e) = cli_list_workspaces(&file) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(selector) = args.toggle_workspace {
+        if let Err(e) = cli_toggle_workspace(&selector) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(selector) = args.recapture_workspace {
+        if let Err(e) = cli_recapture_workspace(&selector, args.recapture_target) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(selector) = args.send_home {
+        if let Err(e) = cli_send_home(selector.as_deref()) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
         return;
     }
 
@@ -139,33 +290,88 @@ fn main() {
         return;
     }
 
+    // Command line actions above may run alongside the GUI, but a second GUI
+    // would register the same hotkeys and auto-save over the first one.
+    if !acquire_single_instance() {
+        info!("Multi Manager is already running; focusing the existing window.");
+        focus_existing_instance();
+        return;
+    }
+
     let settings = load_settings();
 
     // Initialize the application states
     let app = gui::App {
-        app_title_name: "Multi Manager".to_string(),
+        app_title_name: APP_TITLE.to_string(),
         workspaces: Arc::new(Mutex::new(Vec::new())),
         last_hotkey_info: Arc::new(Mutex::new(None)), // Initialize to None
-        hotkey_promise: Arc::new(Mutex::new(None)),   // Initialize the promise
+        recent: Arc::new(Mutex::new(VecDeque::new())),
+        hotkey_promise: Arc::new(Mutex::new(None)), // Initialize the promise
+        hotkey_thread_stop: Arc::new(AtomicBool::new(false)),
         initial_validation_done: Arc::new(Mutex::new(false)), // Initialize flag to false
         registered_hotkeys: Arc::new(Mutex::new(HashMap::new())), // Initialize the map
         rename_dialog: None,
         hotkey_dialog: None,
+        import_dialog: None,
+        add_windows_dialog: None,
+        rescale_dialog: None,
+        workspace_comparison: None,
         all_expanded: true,
         expand_all_signal: None,
+        window_search: String::new(),
+        scroll_to_workspace: None,
         show_settings: false,
+        show_status: false,
+        show_hotkey_help: false,
         auto_save: settings.auto_save,
         unsaved_changes: false,
         save_on_exit: settings.save_on_exit,
         log_level: settings.log_level.clone(),
+        log_max_size_mb: settings.log_max_size_mb,
+        log_keep_files: settings.log_keep_files,
         last_layout_file: settings.last_layout_file.clone(),
         last_workspace_file: settings.last_workspace_file.clone(),
         last_bindings_file: settings.last_bindings_file.clone(),
+        data_dir: settings.data_dir.clone(),
         developer_debugging: settings.developer_debugging,
+        binding_match_threshold: settings.binding_match_threshold,
+        max_backups: settings.max_backups,
+        pretty_json: settings.pretty_json,
+        move_animation: Arc::new(Mutex::new(MoveAnimation {
+            enabled: settings.animate_moves,
+            duration_ms: settings.animation_ms,
+        })),
+        verify_moves: Arc::new(Mutex::new(settings.verify_moves)),
+        hotkeys_enabled: Arc::new(Mutex::new(settings.hotkeys_enabled)),
+        hotkeys_toggle_key: Arc::new(Mutex::new(settings.hotkeys_toggle_key.clone())),
+        hotkey_poll_ms: Arc::new(Mutex::new(clamp_hotkey_poll_ms(settings.hotkey_poll_ms))),
+        minimize_to_tray: settings.minimize_to_tray,
+        start_minimized: settings.start_minimized,
+        start_with_windows: settings.start_with_windows,
+        main_window: None,
+        theme: settings.theme,
+        origin_exclude_processes: settings.origin_exclude_processes.clone(),
+        origin_current_desktop_only: settings.origin_current_desktop_only,
         recapture_queue: Vec::new(),
         recapture_active: false,
+        capture_countdown_secs: settings.capture_countdown_secs,
+        desktop_switch_delay_ms: settings.desktop_switch_delay_ms,
+        capture_countdown: None,
+        auto_rebind: None,
+        desktop_job: None,
+        show_capture_filter: false,
+        desktop_capture_include: settings.desktop_capture_include.clone(),
+        desktop_capture_exclude: settings.desktop_capture_exclude.clone(),
+        last_deleted: None,
+        last_deleted_at: None,
+        undo_history: Default::default(),
     };
 
+    if let Err(e) = autostart::reconcile_start_with_windows(settings.start_with_windows) {
+        log::warn!("{}", e);
+        utils::show_error_box(&e, "Start with Windows");
+    }
+
     // Launch GUI and set the taskbar icon after creating the window
     gui::run_gui(app);
 }
@@ -174,8 +380,8 @@ fn main() {
 fn open_log_folder() {
     use crate::utils::show_error_box;
 
-    let log_path = std::fs::canonicalize("multi_manager.log")
-        .unwrap_or_else(|_| PathBuf::from("multi_manager.log"));
+    let log_path = paths::log_path(load_settings().data_dir.as_deref());
+    let log_path = std::fs::canonicalize(&log_path).unwrap_or(log_path);
 
     if let Err(e) = Command::new("explorer").arg(&log_path).spawn() {
         show_error_box(&format!("Failed to open log folder: {}", e), "Error");
@@ -184,23 +390,34 @@ fn open_log_folder() {
 
 /// Launch a text editor to modify `settings.json`.
 fn edit_settings() {
+    let path = paths::settings_path();
     #[cfg(windows)]
     {
-        let _ = Command::new("notepad").arg("settings.json").spawn();
+        let _ = Command::new("notepad").arg(&path).spawn();
     }
     #[cfg(target_os = "macos")]
     {
-        let _ = Command::new("open").arg("settings.json").spawn();
+        let _ = Command::new("open").arg(&path).spawn();
     }
     #[cfg(all(not(windows), not(target_os = "macos")))]
     {
-        let _ = Command::new("xdg-open").arg("settings.json").spawn();
+        let _ = Command::new("xdg-open").arg(&path).spawn();
     }
 }
 
 fn cli_save_workspaces(path: &str) {
+    use crate::workspace::{read_workspaces, serialize_workspaces, WorkspaceFormat};
     use std::fs;
-    match fs::read_to_string("workspaces.json") {
+    let source = default_workspaces_file();
+    let workspaces = match read_workspaces(&source) {
+        Ok(workspaces) => workspaces,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", source, e);
+            return;
+        }
+    };
+    let pretty = load_settings().pretty_json;
+    match serialize_workspaces(&workspaces, WorkspaceFormat::from_path(path), pretty) {
         Ok(content) => {
             if let Err(e) = fs::write(path, content) {
                 eprintln!("Failed to save workspaces: {}", e);
@@ -208,12 +425,12 @@ fn cli_save_workspaces(path: &str) {
                 println!("Saved workspaces to {}", path);
             }
         }
-        Err(e) => eprintln!("Failed to read workspaces.json: {}", e),
+        Err(e) => eprintln!("Failed to serialize workspaces: {}", e),
     }
 }
 
 fn cli_load_workspaces(path: &str) {
-    use crate::workspace::Workspace;
+    use crate::workspace::{parse_workspaces_as, serialize_workspaces, WorkspaceFormat};
     use std::fs;
 
     let content = match fs::read_to_string(path) {
@@ -224,18 +441,235 @@ fn cli_load_workspaces(path: &str) {
         }
     };
 
-    if serde_json::from_str::<Vec<Workspace>>(&content).is_err() {
-        eprintln!("Invalid workspace JSON: {}", path);
+    let content = match parse_workspaces_as(&content, WorkspaceFormat::from_path(path))
+        .map_err(|e| e.to_string())
+        .and_then(|workspaces| {
+            serialize_workspaces(
+                &workspaces,
+                WorkspaceFormat::Json,
+                load_settings().pretty_json,
+            )
+        }) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Invalid workspace file '{}': {}", path, e);
+            return;
+        }
+    };
+
+    let destination = default_workspaces_file();
+    let existing = fs::read_to_string(&destination).ok();
+    if existing.is_some_and(|existing| existing != content)
+        && !show_confirmation_box(
+            &format!(
+                "This will replace {} and discard its workspaces. Continue?",
+                destination
+            ),
+            "Discard Current Workspaces",
+        )
+    {
+        println!("Load canceled; {} was not changed.", destination);
         return;
     }
 
-    if let Err(e) = fs::write("workspaces.json", &content) {
-        eprintln!("Failed to write workspaces.json: {}", e);
+    if let Err(e) = fs::write(&destination, &content) {
+        eprintln!("Failed to write {}: {}", destination, e);
     } else {
         println!("Loaded workspaces from {}", path);
     }
 }
 
+/// Path of the `workspaces.json` the CLI commands read, in the data folder
+/// (see [`paths::resolve_data_path`]).
+fn default_workspaces_file() -> String {
+    paths::resolve_legacy_data_path(load_settings().data_dir.as_deref(), "workspaces.json")
+}
+
+/// Toggles the workspace in `workspaces.json` matching `selector` by name or index.
+fn cli_toggle_workspace(selector: &str) -> Result<(), String> {
+    use crate::window_manager::toggle_workspace_windows;
+    use crate::workspace::{find_workspace, read_workspaces};
+
+    let path = default_workspaces_file();
+    let mut workspaces =
+        read_workspaces(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let index = find_workspace(&workspaces, selector)
+        .ok_or_else(|| format!("No workspace named or numbered '{}'.", selector))?;
+    let workspace = &mut workspaces[index];
+    let settings = load_settings();
+    toggle_workspace_windows(
+        workspace,
+        MoveAnimation {
+            enabled: settings.animate_moves,
+            duration_ms: settings.animation_ms,
+        },
+        settings.verify_moves,
+    );
+    println!("Toggled workspace '{}'", workspace.name);
+    Ok(())
+}
+
+/// Refreshes the home (or, with `target`, the target) rectangle of every live
+/// window in the workspace matching `selector` and writes `workspaces.json` back.
+///
+/// Windows whose HWND no longer exists, or now belongs to a different window,
+/// keep their stored rectangles.
+fn cli_recapture_workspace(selector: &str, target: bool) -> Result<(), String> {
+    use crate::workspace::{find_workspace, read_workspaces, save_workspaces};
+    use std::ffi::c_void;
+    use windows::Win32::Foundation::HWND;
+
+    let path = default_workspaces_file();
+    let mut workspaces =
+        read_workspaces(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let index = find_workspace(&workspaces, selector)
+        .ok_or_else(|| format!("No workspace named or numbered '{}'.", selector))?;
+
+    let workspace = &mut workspaces[index];
+    let mut recaptured = 0usize;
+    for window in workspace.windows.iter_mut() {
+        let live = window.matches_live(HWND(window.id as *mut c_void));
+        let captured = live
+            && if target {
+                window.capture_target()
+            } else {
+                window.capture_home()
+            };
+        if captured {
+            recaptured += 1;
+        } else {
+            eprintln!(
+                "Warning: '{}' is not a live window or its handle now belongs to another window; left unchanged.",
+                window.display_label()
+            );
+        }
+    }
+    println!(
+        "Recaptured {} {} position(s) of {} window(s) in '{}'.",
+        recaptured,
+        if target { "target" } else { "home" },
+        workspace.windows.len(),
+        workspace.name
+    );
+
+    let settings = load_settings();
+    save_workspaces(
+        &workspaces,
+        &path,
+        settings.max_backups,
+        settings.pretty_json,
+    );
+    Ok(())
+}
+
+/// Sends the windows of the workspace matching `selector`, or of every workspace
+/// when `selector` is `None`, back to their home positions.
+///
+/// Windows whose HWND no longer exists are dropped first, mirroring
+/// `App::send_all_home`, so the printed count only covers windows that moved.
+fn cli_send_home(selector: Option<&str>) -> Result<(), String> {
+    use crate::window_manager::{send_all_windows_home, send_workspace_windows_home};
+    use crate::workspace::{find_workspace, read_workspaces};
+    use std::ffi::c_void;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::IsWindow;
+
+    let path = default_workspaces_file();
+    let mut workspaces =
+        read_workspaces(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if let Some(selector) = selector {
+        let index = find_workspace(&workspaces, selector)
+            .ok_or_else(|| format!("No workspace named or numbered '{}'.", selector))?;
+        workspaces = vec![workspaces.swap_remove(index)];
+    }
+
+    let mut moved = 0usize;
+    for workspace in workspaces.iter_mut() {
+        workspace.windows.retain(|window| {
+            let hwnd = HWND(window.id as *mut c_void);
+            window.enabled && unsafe { IsWindow(hwnd).as_bool() }
+        });
+        moved += workspace.windows.len();
+    }
+
+    match selector {
+        Some(_) => send_workspace_windows_home(&workspaces[0]),
+        None => send_all_windows_home(&workspaces),
+    }
+    println!("Moved {} window(s) home.", moved);
+    Ok(())
+}
+
+/// Prints a table of the workspaces stored in `path`.
+fn cli_list_workspaces(path: &str) -> Result<(), String> {
+    use crate::workspace::read_workspaces;
+
+    let mut workspaces =
+        read_workspaces(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+    for workspace in workspaces.iter_mut() {
+        workspace.validate_workspace();
+    }
+    print!("{}", format_workspace_table(&workspaces));
+    Ok(())
+}
+
+/// Formats one row per workspace with its index, name, hotkey, window count and status.
+fn format_workspace_table(workspaces: &[workspace::Workspace]) -> String {
+    let header = ["#", "Name", "Hotkey", "Windows", "Status"];
+    let rows: Vec<[String; 5]> = workspaces
+        .iter()
+        .enumerate()
+        .map(|(i, ws)| {
+            let status = if ws.disabled {
+                "disabled"
+            } else if ws.valid {
+                "valid"
+            } else {
+                "invalid"
+            };
+            [
+                i.to_string(),
+                ws.name.clone(),
+                ws.hotkey
+                    .as_ref()
+                    .map(|h| h.key_sequence.clone())
+                    .unwrap_or_else(|| "-".to_string()),
+                ws.windows.len().to_string(),
+                status.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: [&str; 5]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        format!("{}\n", line.trim_end())
+    };
+
+    let mut table = format_row(header);
+    table.push_str(&format_row(
+        widths
+            .map(|width| "-".repeat(width))
+            .each_ref()
+            .map(String::as_str),
+    ));
+    for row in &rows {
+        table.push_str(&format_row(row.each_ref().map(String::as_str)));
+    }
+    table
+}
+
 /// Ensures that a valid `log4rs.yaml` logging configuration file exists and initializes the logger.
 ///
 /// # Behavior
@@ -264,7 +698,12 @@ fn cli_load_workspaces(path: &str) {
 /// ```
 fn ensure_logging_initialized() {
     use log::LevelFilter;
-    use log4rs::append::file::FileAppender;
+    use log4rs::append::rolling_file::policy::compound::roll::delete::DeleteRoller;
+    use log4rs::append::rolling_file::policy::compound::roll::fixed_window::FixedWindowRoller;
+    use log4rs::append::rolling_file::policy::compound::roll::Roll;
+    use log4rs::append::rolling_file::policy::compound::trigger::size::SizeTrigger;
+    use log4rs::append::rolling_file::policy::compound::CompoundPolicy;
+    use log4rs::append::rolling_file::RollingFileAppender;
     use log4rs::config::{Appender, Config, Root};
     use log4rs::encode::pattern::PatternEncoder;
 
@@ -278,11 +717,58 @@ fn ensure_logging_initialized() {
         _ => LevelFilter::Info,
     };
 
-    let logfile = FileAppender::builder()
-        .encoder(Box::new(PatternEncoder::new("{d} - {l} - {m}{n}")))
-        .append(false)
-        .build("multi_manager.log")
-        .expect("failed to create log file");
+    // Keep appending across launches and roll over by size, so recent history
+    // survives a restart without the log growing without bound.
+    let build_appender = |log_path: &std::path::Path| {
+        let archive_pattern = log_path.with_file_name("multi_manager.{}.log");
+        let roller: Box<dyn Roll> = if settings.log_keep_files == 0 {
+            Box::new(DeleteRoller::new())
+        } else {
+            Box::new(
+                FixedWindowRoller::builder()
+                    .build(&archive_pattern.to_string_lossy(), settings.log_keep_files)
+                    .map_err(|e| e.to_string())?,
+            )
+        };
+        let trigger = SizeTrigger::new(settings.log_max_size_mb.max(1) * 1024 * 1024);
+        RollingFileAppender::builder()
+            .encoder(Box::new(PatternEncoder::new("{d} - {l} - {m}{n}")))
+            .build(
+                log_path,
+                Box::new(CompoundPolicy::new(Box::new(trigger), roller)),
+            )
+            .map_err(|e| e.to_string())
+    };
+
+    // A data folder that is missing or read-only must not keep the app from
+    // starting, or the setting could not be fixed from the GUI.
+    let log_path = paths::log_path(settings.data_dir.as_deref());
+    let mut fallback_warning = None;
+    let logfile = match build_appender(&log_path) {
+        Ok(logfile) => logfile,
+        Err(e) => {
+            let fallback = paths::log_path(None);
+            let warning = format!(
+                "Failed to create log file '{}': {}; logging to '{}' instead.",
+                log_path.display(),
+                e,
+                fallback.display()
+            );
+            eprintln!("{}", warning);
+            fallback_warning = Some(warning);
+            match build_appender(&fallback) {
+                Ok(logfile) => logfile,
+                Err(e) => {
+                    eprintln!(
+                        "Failed to create log file '{}': {}; logging is disabled.",
+                        fallback.display(),
+                        e
+                    );
+                    return;
+                }
+            }
+        }
+    };
 
     let config = Config::builder()
         .appender(Appender::builder().build("file", Box::new(logfile)))
@@ -292,4 +778,113 @@ fn ensure_logging_initialized() {
     if let Err(e) = log4rs::init_config(config) {
         eprintln!("Failed to initialize logging: {}", e);
     }
+    if let Some(warning) = fallback_warning {
+        warn!("{}", warning);
+    }
+    // Settings were loaded above, before logging existed.
+    for report in load_reports() {
+        warn!("{}", report);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> CliArgs {
+        CliArgs::try_parse_from(std::iter::once("multi_manager").chain(args.iter().copied()))
+            .expect("arguments should parse")
+    }
+
+    #[test]
+    fn toggle_workspace_takes_name_or_index() {
+        assert_eq!(
+            parse(&["--toggle-workspace", "Coding"])
+                .toggle_workspace
+                .as_deref(),
+            Some("Coding")
+        );
+        assert_eq!(
+            parse(&["--toggle-workspace", "2"])
+                .toggle_workspace
+                .as_deref(),
+            Some("2")
+        );
+        assert!(parse(&[]).toggle_workspace.is_none());
+    }
+
+    #[test]
+    fn list_workspaces_without_a_file_uses_the_default() {
+        // An empty value stands for `default_workspaces_file()`.
+        assert_eq!(
+            parse(&["--list-workspaces"]).list_workspaces.as_deref(),
+            Some("")
+        );
+        assert_eq!(
+            parse(&["--list-workspaces", "other.yaml"])
+                .list_workspaces
+                .as_deref(),
+            Some("other.yaml")
+        );
+        assert!(parse(&[]).list_workspaces.is_none());
+    }
+
+    #[test]
+    fn workspace_table_aligns_columns() {
+        let workspaces = vec![
+            workspace::Workspace {
+                name: "Coding".to_string(),
+                hotkey: Some(hotkey::Hotkey::new("Ctrl+Alt+C").unwrap()),
+                valid: true,
+                ..Default::default()
+            },
+            workspace::Workspace {
+                name: "Off".to_string(),
+                disabled: true,
+                ..Default::default()
+            },
+        ];
+        let table = format_workspace_table(&workspaces);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "#  Name    Hotkey      Windows  Status");
+        assert_eq!(lines[2], "0  Coding  Ctrl+Alt+C  0        valid");
+        assert_eq!(lines[3], "1  Off     -           0        disabled");
+    }
+
+    #[test]
+    fn send_home_name_is_optional() {
+        assert_eq!(parse(&["--send-home"]).send_home, Some(None));
+        assert_eq!(
+            parse(&["--send-home", "Coding"]).send_home,
+            Some(Some("Coding".to_string()))
+        );
+        assert_eq!(parse(&[]).send_home, None);
+    }
+
+    #[test]
+    fn dry_run_requires_load_desktops() {
+        let args = parse(&["--load-desktops", "--dry-run"]);
+        assert_eq!(args.load_desktops.as_deref(), Some("desktop_layout.json"));
+        assert!(args.dry_run);
+        assert!(CliArgs::try_parse_from(["multi_manager", "--dry-run"]).is_err());
+    }
+
+    #[test]
+    fn recapture_workspace_targets_home_unless_asked() {
+        let args = parse(&["--recapture-workspace", "Coding"]);
+        assert_eq!(args.recapture_workspace.as_deref(), Some("Coding"));
+        assert!(!args.recapture_target);
+
+        let args = parse(&["--recapture-workspace", "1", "--recapture-target"]);
+        assert_eq!(args.recapture_workspace.as_deref(), Some("1"));
+        assert!(args.recapture_target);
+
+        assert!(CliArgs::try_parse_from(["multi_manager", "--recapture-workspace"]).is_err());
+        assert!(CliArgs::try_parse_from(["multi_manager", "--recapture-target"]).is_err());
+    }
+
+    #[test]
+    fn toggle_workspace_requires_a_value() {
+        assert!(CliArgs::try_parse_from(["multi_manager", "--toggle-workspace"]).is_err());
+    }
 }
@@ -0,0 +1,530 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM, RECT};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, MonitorFromRect, MonitorFromWindow, HDC, HMONITOR,
+    MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
+};
+use windows::Win32::UI::HiDpi::{
+    GetDpiForMonitor, GetDpiForWindow, SetProcessDpiAwarenessContext,
+    DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, MDT_EFFECTIVE_DPI,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, MONITORINFOF_PRIMARY, SM_CXSCREEN, SM_CXVIRTUALSCREEN, SM_CYSCREEN,
+    SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+};
+
+/// The DPI Windows uses for 100% scaling.
+pub const DEFAULT_DPI: u32 = 96;
+
+/// A rectangle stored relative to the work area of a specific monitor.
+///
+/// `device` is the GDI device name reported by `GetMonitorInfoW` (e.g. `\\.\DISPLAY1`)
+/// and `rect` is `(x, y, width, height)` with `x`/`y` measured from the top-left
+/// corner of that monitor's work area.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MonitorAnchor {
+    pub device: String,
+    pub rect: (i32, i32, i32, i32),
+}
+
+/// Information about a connected monitor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorDetails {
+    /// GDI device name of the monitor.
+    pub device: String,
+    /// Work area of the monitor as `(x, y, width, height)` in virtual screen coordinates.
+    pub work_area: (i32, i32, i32, i32),
+    /// `true` if this is the primary monitor.
+    pub primary: bool,
+    /// Effective DPI of the monitor (96 at 100% scaling).
+    pub dpi: u32,
+}
+
+/// Converts an absolute `rect` into coordinates relative to `work_area`.
+pub fn to_relative(
+    work_area: (i32, i32, i32, i32),
+    rect: (i32, i32, i32, i32),
+) -> (i32, i32, i32, i32) {
+    (rect.0 - work_area.0, rect.1 - work_area.1, rect.2, rect.3)
+}
+
+/// Converts a `rect` relative to `work_area` back into absolute coordinates.
+pub fn to_absolute(
+    work_area: (i32, i32, i32, i32),
+    rect: (i32, i32, i32, i32),
+) -> (i32, i32, i32, i32) {
+    (rect.0 + work_area.0, rect.1 + work_area.1, rect.2, rect.3)
+}
+
+/// Scales `rect` from `from_dpi` to `to_dpi`, rounding to the nearest pixel.
+///
+/// Returns `rect` unchanged if the DPIs match or either is zero.
+pub fn scale_rect_for_dpi(
+    rect: (i32, i32, i32, i32),
+    from_dpi: u32,
+    to_dpi: u32,
+) -> (i32, i32, i32, i32) {
+    if from_dpi == to_dpi || from_dpi == 0 || to_dpi == 0 {
+        return rect;
+    }
+    let scale = |value: i32| -> i32 {
+        let scaled = value as i64 * to_dpi as i64;
+        let from = from_dpi as i64;
+        let rounded = if scaled >= 0 {
+            (scaled + from / 2) / from
+        } else {
+            (scaled - from / 2) / from
+        };
+        rounded as i32
+    };
+    (scale(rect.0), scale(rect.1), scale(rect.2), scale(rect.3))
+}
+
+/// Scales `rect` by the ratio between a `to` and a `from` screen size
+/// (`(width, height)`), rounding to the nearest pixel.
+///
+/// Returns `rect` unchanged if either size is not positive.
+pub fn scale_rect_between(
+    rect: (i32, i32, i32, i32),
+    from: (i32, i32),
+    to: (i32, i32),
+) -> (i32, i32, i32, i32) {
+    if from.0 <= 0 || from.1 <= 0 || to.0 <= 0 || to.1 <= 0 {
+        return rect;
+    }
+    let sx = to.0 as f64 / from.0 as f64;
+    let sy = to.1 as f64 / from.1 as f64;
+    let scale = |value: i32, factor: f64| (value as f64 * factor).round() as i32;
+    (
+        scale(rect.0, sx),
+        scale(rect.1, sy),
+        scale(rect.2, sx),
+        scale(rect.3, sy),
+    )
+}
+
+/// Returns the center of the primary monitor's work area in screen coordinates.
+///
+/// The primary monitor always starts at `(0, 0)`, but a taskbar on its left or
+/// top edge moves the work area, and monitors left of or above it have
+/// negative coordinates, so the center is read from the monitor itself.
+pub fn primary_work_area_center() -> (i32, i32) {
+    let (x, y, w, h) = list_monitors()
+        .into_iter()
+        .find(|monitor| monitor.primary)
+        .map(|monitor| monitor.work_area)
+        .unwrap_or_else(|| {
+            let (w, h) = primary_screen_size();
+            (0, 0, w, h)
+        });
+    (x + w / 2, y + h / 2)
+}
+
+/// Returns how far the work area of the monitor holding `rect` is inset from
+/// that monitor's top-left corner, e.g. by a taskbar on its left or top edge.
+///
+/// `GetWindowPlacement` and `SetWindowPlacement` express the restore rectangle
+/// of normal top-level windows in workspace coordinates, which are screen
+/// coordinates shifted by this offset.
+pub fn workspace_offset_for_rect(rect: (i32, i32, i32, i32)) -> (i32, i32) {
+    let rect = RECT {
+        left: rect.0,
+        top: rect.1,
+        right: rect.0 + rect.2,
+        bottom: rect.1 + rect.3,
+    };
+    let mut info = MONITORINFO {
+        cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+        ..Default::default()
+    };
+    let ok = unsafe {
+        let monitor = MonitorFromRect(&rect, MONITOR_DEFAULTTONEAREST);
+        GetMonitorInfoW(monitor, &mut info)
+    };
+    if !ok.as_bool() {
+        return (0, 0);
+    }
+    (
+        info.rcWork.left - info.rcMonitor.left,
+        info.rcWork.top - info.rcMonitor.top,
+    )
+}
+
+/// Converts `rect` from workspace coordinates (see [`workspace_offset_for_rect`])
+/// into screen coordinates.
+pub fn workspace_to_screen(rect: (i32, i32, i32, i32), offset: (i32, i32)) -> (i32, i32, i32, i32) {
+    (rect.0 + offset.0, rect.1 + offset.1, rect.2, rect.3)
+}
+
+/// Converts `rect` from screen coordinates into workspace coordinates.
+pub fn screen_to_workspace(rect: (i32, i32, i32, i32), offset: (i32, i32)) -> (i32, i32, i32, i32) {
+    (rect.0 - offset.0, rect.1 - offset.1, rect.2, rect.3)
+}
+
+/// Returns the resolution of the primary monitor as `(width, height)`.
+pub fn primary_screen_size() -> (i32, i32) {
+    unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) }
+}
+
+/// Returns `true` if the two `(x, y, width, height)` rectangles overlap.
+pub fn rects_intersect(a: (i32, i32, i32, i32), b: (i32, i32, i32, i32)) -> bool {
+    a.0 < b.0 + b.2 && b.0 < a.0 + a.2 && a.1 < b.1 + b.3 && b.1 < a.1 + a.3
+}
+
+/// Returns the bounding rectangle of all connected monitors as `(x, y, width, height)`.
+pub fn virtual_screen_rect() -> (i32, i32, i32, i32) {
+    unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    }
+}
+
+/// Makes the process per-monitor DPI aware so window rectangles are reported
+/// and applied in physical pixels on every monitor.
+pub fn enable_dpi_awareness() {
+    unsafe {
+        if let Err(e) = SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) {
+            warn!("Failed to enable per-monitor DPI awareness: {}", e);
+        }
+    }
+}
+
+/// Returns the DPI of the monitor `hwnd` is displayed on.
+pub fn dpi_for_window(hwnd: HWND) -> u32 {
+    match unsafe { GetDpiForWindow(hwnd) } {
+        0 => DEFAULT_DPI,
+        dpi => dpi,
+    }
+}
+
+fn dpi_for_monitor(monitor: HMONITOR) -> u32 {
+    let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+    match unsafe { GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) } {
+        Ok(()) if dpi_x != 0 => dpi_x,
+        _ => DEFAULT_DPI,
+    }
+}
+
+fn details_for_handle(monitor: HMONITOR) -> Option<MonitorDetails> {
+    if monitor.is_invalid() {
+        return None;
+    }
+    let mut info = MONITORINFOEXW::default();
+    info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+    let ok = unsafe { GetMonitorInfoW(monitor, &mut info as *mut _ as *mut MONITORINFO) };
+    if !ok.as_bool() {
+        return None;
+    }
+    let len = info
+        .szDevice
+        .iter()
+        .position(|&c| c == 0)
+        .unwrap_or(info.szDevice.len());
+    let work = info.monitorInfo.rcWork;
+    Some(MonitorDetails {
+        device: String::from_utf16_lossy(&info.szDevice[..len]),
+        work_area: (
+            work.left,
+            work.top,
+            work.right - work.left,
+            work.bottom - work.top,
+        ),
+        primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+        dpi: dpi_for_monitor(monitor),
+    })
+}
+
+unsafe extern "system" fn enum_monitor_proc(
+    monitor: HMONITOR,
+    _hdc: HDC,
+    _rect: *mut RECT,
+    lparam: LPARAM,
+) -> BOOL {
+    let list = &mut *(lparam.0 as *mut Vec<MonitorDetails>);
+    if let Some(details) = details_for_handle(monitor) {
+        list.push(details);
+    }
+    BOOL(1)
+}
+
+/// Enumerates all monitors currently attached to the desktop.
+pub fn list_monitors() -> Vec<MonitorDetails> {
+    let mut monitors: Vec<MonitorDetails> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(enum_monitor_proc),
+            LPARAM(&mut monitors as *mut _ as isize),
+        );
+    }
+    monitors
+}
+
+/// Returns the monitor that contains the largest part of `hwnd`.
+pub fn monitor_for_window(hwnd: HWND) -> Option<MonitorDetails> {
+    details_for_handle(unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) })
+}
+
+/// Returns the monitor that contains the largest part of `rect`.
+pub fn monitor_for_rect(rect: (i32, i32, i32, i32)) -> Option<MonitorDetails> {
+    let rect = RECT {
+        left: rect.0,
+        top: rect.1,
+        right: rect.0 + rect.2,
+        bottom: rect.1 + rect.3,
+    };
+    details_for_handle(unsafe { MonitorFromRect(&rect, MONITOR_DEFAULTTONEAREST) })
+}
+
+/// Describes the monitor named `device` as e.g. `Monitor 2 (DISPLAY2)`, numbering
+/// monitors in the order of `monitors`.
+pub fn describe_monitor(monitors: &[MonitorDetails], device: &str) -> String {
+    let name = device.trim_start_matches(r"\\.\");
+    match monitors.iter().position(|monitor| monitor.device == device) {
+        Some(index) if monitors[index].primary => {
+            format!("Monitor {} ({}, primary)", index + 1, name)
+        }
+        Some(index) => format!("Monitor {} ({})", index + 1, name),
+        None => name.to_string(),
+    }
+}
+
+/// Builds a [`MonitorAnchor`] for `rect` using the monitor that currently hosts `hwnd`.
+pub fn anchor_for_window(hwnd: HWND, rect: (i32, i32, i32, i32)) -> Option<MonitorAnchor> {
+    monitor_for_window(hwnd).map(|monitor| MonitorAnchor {
+        rect: to_relative(monitor.work_area, rect),
+        device: monitor.device,
+    })
+}
+
+/// Builds a [`MonitorAnchor`] for `rect` using the monitor the rectangle lies on.
+pub fn anchor_for_rect(rect: (i32, i32, i32, i32)) -> Option<MonitorAnchor> {
+    monitor_for_rect(rect).map(|monitor| MonitorAnchor {
+        rect: to_relative(monitor.work_area, rect),
+        device: monitor.device,
+    })
+}
+
+/// Converts `fractions` of `work_area` (`x`, `y`, `width`, `height`, each 0.0–1.0)
+/// into an absolute rectangle, rounding to the nearest pixel.
+pub fn fraction_to_rect(
+    work_area: (i32, i32, i32, i32),
+    fractions: (f32, f32, f32, f32),
+) -> (i32, i32, i32, i32) {
+    let scale = |fraction: f32, length: i32| (fraction as f64 * length as f64).round() as i32;
+    (
+        work_area.0 + scale(fractions.0, work_area.2),
+        work_area.1 + scale(fractions.1, work_area.3),
+        scale(fractions.2, work_area.2),
+        scale(fractions.3, work_area.3),
+    )
+}
+
+/// Expresses `rect` as fractions of `work_area`; the inverse of [`fraction_to_rect`].
+pub fn rect_to_fraction(
+    work_area: (i32, i32, i32, i32),
+    rect: (i32, i32, i32, i32),
+) -> (f32, f32, f32, f32) {
+    let fraction = |value: i32, length: i32| {
+        if length == 0 {
+            0.0
+        } else {
+            value as f32 / length as f32
+        }
+    };
+    (
+        fraction(rect.0 - work_area.0, work_area.2),
+        fraction(rect.1 - work_area.1, work_area.3),
+        fraction(rect.2, work_area.2),
+        fraction(rect.3, work_area.3),
+    )
+}
+
+/// Returns the work area of the monitor named `device`, falling back to the
+/// primary monitor when it is `None` or no longer connected.
+pub fn work_area_for_device(device: Option<&str>) -> Option<(i32, i32, i32, i32)> {
+    let monitors = list_monitors();
+    device
+        .and_then(|device| monitors.iter().find(|m| m.device == device))
+        .or_else(|| monitors.iter().find(|m| m.primary))
+        .map(|monitor| monitor.work_area)
+}
+
+/// Translates a stored anchor back into absolute screen coordinates.
+///
+/// If the anchored monitor is no longer connected the rectangle is placed
+/// relative to the primary monitor and a warning is logged. When `captured_dpi`
+/// is known and differs from the destination monitor's DPI, the rectangle is
+/// scaled so the window keeps the same logical size. Without an anchor, or when
+/// no monitors can be enumerated, `absolute` is used as-is (only its size is
+/// rescaled).
+pub fn resolve_rect(
+    anchor: Option<&MonitorAnchor>,
+    absolute: (i32, i32, i32, i32),
+    captured_dpi: Option<u32>,
+) -> (i32, i32, i32, i32) {
+    let rescale = |rect: (i32, i32, i32, i32), monitor: &MonitorDetails| match captured_dpi {
+        Some(dpi) => scale_rect_for_dpi(rect, dpi, monitor.dpi),
+        None => rect,
+    };
+
+    let Some(anchor) = anchor else {
+        return match (captured_dpi, monitor_for_rect(absolute)) {
+            (Some(_), Some(monitor)) => {
+                let scaled = rescale(absolute, &monitor);
+                (absolute.0, absolute.1, scaled.2, scaled.3)
+            }
+            _ => absolute,
+        };
+    };
+
+    let monitors = list_monitors();
+    if let Some(monitor) = monitors.iter().find(|m| m.device == anchor.device) {
+        return to_absolute(monitor.work_area, rescale(anchor.rect, monitor));
+    }
+
+    match monitors.iter().find(|m| m.primary) {
+        Some(primary) => {
+            warn!(
+                "Monitor '{}' is no longer available; placing window on primary monitor '{}'.",
+                anchor.device, primary.device
+            );
+            to_absolute(primary.work_area, rescale(anchor.rect, primary))
+        }
+        None => absolute,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monitors_are_described_by_position() {
+        let monitor = |device: &str, primary| MonitorDetails {
+            device: device.to_string(),
+            work_area: (0, 0, 1920, 1040),
+            primary,
+            dpi: 96,
+        };
+        let monitors = vec![
+            monitor(r"\\.\DISPLAY1", true),
+            monitor(r"\\.\DISPLAY2", false),
+        ];
+        assert_eq!(
+            describe_monitor(&monitors, r"\\.\DISPLAY1"),
+            "Monitor 1 (DISPLAY1, primary)"
+        );
+        assert_eq!(
+            describe_monitor(&monitors, r"\\.\DISPLAY2"),
+            "Monitor 2 (DISPLAY2)"
+        );
+        assert_eq!(describe_monitor(&monitors, r"\\.\DISPLAY3"), "DISPLAY3");
+    }
+
+    #[test]
+    fn relative_round_trip() {
+        let work_area = (-1920, 40, 1920, 1040);
+        let rect = (-1800, 100, 800, 600);
+        let relative = to_relative(work_area, rect);
+        assert_eq!(relative, (120, 60, 800, 600));
+        assert_eq!(to_absolute(work_area, relative), rect);
+    }
+
+    #[test]
+    fn relative_rect_moves_with_monitor() {
+        let relative = (120, 60, 800, 600);
+        assert_eq!(
+            to_absolute((0, 0, 1920, 1080), relative),
+            (120, 60, 800, 600)
+        );
+        assert_eq!(
+            to_absolute((2560, 0, 1920, 1080), relative),
+            (2680, 60, 800, 600)
+        );
+    }
+
+    #[test]
+    fn fractions_follow_work_area() {
+        let left_half = (0.0, 0.0, 0.5, 1.0);
+        assert_eq!(
+            fraction_to_rect((0, 0, 1920, 1040), left_half),
+            (0, 0, 960, 1040)
+        );
+        assert_eq!(
+            fraction_to_rect((-2560, 0, 2560, 1400), left_half),
+            (-2560, 0, 1280, 1400)
+        );
+        let rect = (960, 520, 960, 520);
+        let fractions = rect_to_fraction((0, 0, 1920, 1040), rect);
+        assert_eq!(fractions, (0.5, 0.5, 0.5, 0.5));
+        assert_eq!(fraction_to_rect((0, 0, 1920, 1040), fractions), rect);
+    }
+
+    #[test]
+    fn negative_coordinates_round_trip_through_workspace_coordinates() {
+        // A window on a monitor left of the primary one, whose taskbar is on the left.
+        let rect = (-1910, -40, 800, 600);
+        let offset = (48, 0);
+        let workspace = screen_to_workspace(rect, offset);
+        assert_eq!(workspace, (-1958, -40, 800, 600));
+        assert_eq!(workspace_to_screen(workspace, offset), rect);
+        assert_eq!(
+            to_absolute(
+                (-1920, 0, 1920, 1040),
+                to_relative((-1920, 0, 1920, 1040), rect)
+            ),
+            rect
+        );
+    }
+
+    #[test]
+    fn rects_intersect_detects_offscreen() {
+        let screen = (-1920, 0, 3840, 1080);
+        assert!(rects_intersect(screen, (100, 100, 800, 600)));
+        assert!(rects_intersect(screen, (-2000, -50, 200, 200)));
+        assert!(!rects_intersect(screen, (1920, 0, 800, 600)));
+        assert!(!rects_intersect(screen, (0, 1080, 800, 600)));
+        assert!(!rects_intersect(screen, (-3000, 100, 500, 500)));
+    }
+
+    #[test]
+    fn scale_rect_between_dpis() {
+        let rect = (100, 50, 1200, 900);
+        // 150% -> 100%
+        assert_eq!(scale_rect_for_dpi(rect, 144, 96), (67, 33, 800, 600));
+        // 100% -> 150%
+        assert_eq!(
+            scale_rect_for_dpi((67, 33, 800, 600), 96, 144),
+            (101, 50, 1200, 900)
+        );
+        assert_eq!(scale_rect_for_dpi(rect, 96, 96), rect);
+        assert_eq!(scale_rect_for_dpi(rect, 0, 96), rect);
+        assert_eq!(
+            scale_rect_for_dpi((-30, -15, 10, 10), 96, 192),
+            (-60, -30, 20, 20)
+        );
+    }
+
+    #[test]
+    fn rects_scale_between_screen_sizes() {
+        assert_eq!(
+            scale_rect_between((1920, 0, 960, 1080), (3840, 2160), (1920, 1080)),
+            (960, 0, 480, 540)
+        );
+        assert_eq!(
+            scale_rect_between((-100, 50, 300, 200), (1000, 1000), (1500, 500)),
+            (-150, 25, 450, 100)
+        );
+        assert_eq!(
+            scale_rect_between((1, 2, 3, 4), (0, 1080), (1920, 1080)),
+            (1, 2, 3, 4)
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use crate::monitor::virtual_screen_rect;
+use crate::workspace::Workspace;
+use log::warn;
+use std::cell::RefCell;
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint, CreatePen, CreateSolidBrush, DeleteObject, EndPaint, FillRect, GetStockObject,
+    Rectangle, SelectObject, SetBkMode, SetTextColor, TextOutW, HDC, NULL_BRUSH, PAINTSTRUCT,
+    PS_SOLID, TRANSPARENT,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+/// Color used for home rectangles (`0x00BBGGRR`).
+const HOME_COLOR: COLORREF = COLORREF(0x00FF_9040);
+/// Color used for target rectangles (`0x00BBGGRR`).
+const TARGET_COLOR: COLORREF = COLORREF(0x0040_D040);
+/// Opacity of the whole overlay, 0–255.
+const OVERLAY_ALPHA: u8 = 200;
+
+/// Which of a window's stored positions a [`PreviewRect`] shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreviewKind {
+    Home,
+    Target,
+}
+
+/// A labeled rectangle drawn by the layout preview overlay.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreviewRect {
+    pub label: String,
+    pub rect: (i32, i32, i32, i32),
+    pub kind: PreviewKind,
+}
+
+thread_local! {
+    /// Title line and rectangles painted by the overlay owned by this thread.
+    static PREVIEW: RefCell<(String, Vec<PreviewRect>)> = const { RefCell::new((String::new(), Vec::new())) };
+}
+
+/// Collects the home and target rectangles of every enabled window in `workspace`.
+///
+/// Rectangles are resolved the same way [`crate::window_manager::toggle_workspace_windows`]
+/// resolves them, so the preview matches where windows will actually land.
+pub fn preview_rects(workspace: &Workspace) -> Vec<PreviewRect> {
+    let enabled = workspace.windows.iter().filter(|window| window.enabled);
+    let homes = enabled.clone().map(|window| PreviewRect {
+        label: format!("Home: {}", window.display_name()),
+        rect: window.home_rect(),
+        kind: PreviewKind::Home,
+    });
+    let targets = enabled.map(|window| PreviewRect {
+        label: format!("Target: {}", window.display_name()),
+        rect: window.target_rect(),
+        kind: PreviewKind::Target,
+    });
+    homes.chain(targets).collect()
+}
+
+/// Shows a translucent, always-on-top overlay over every monitor with the home
+/// and target rectangles of `workspace`'s windows.
+///
+/// The overlay runs on its own thread and closes on any key press or mouse click.
+pub fn show_layout_preview(workspace: &Workspace) {
+    let title = format!(
+        "Layout preview for '{}' - press any key or click to dismiss",
+        workspace.name
+    );
+    let rects = preview_rects(workspace);
+    std::thread::spawn(move || {
+        PREVIEW.with(|preview| *preview.borrow_mut() = (title, rects));
+        if let Err(e) = run_overlay() {
+            warn!("Failed to show layout preview: {}", e);
+        }
+    });
+}
+
+/// Creates the overlay window and pumps its messages until it is dismissed.
+fn run_overlay() -> windows::core::Result<()> {
+    let class_name = w!("MultiManagerLayoutPreview");
+    let (x, y, w, h) = virtual_screen_rect();
+    unsafe {
+        let instance = GetModuleHandleW(None)?;
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(overlay_proc),
+            hInstance: instance.into(),
+            hCursor: LoadCursorW(None, IDC_ARROW)?,
+            lpszClassName: class_name,
+            ..Default::default()
+        };
+        // Registration fails harmlessly if an earlier preview already registered the class.
+        RegisterClassW(&class);
+
+        let hwnd = CreateWindowExW(
+            WS_EX_LAYERED | WS_EX_TOPMOST | WS_EX_TOOLWINDOW,
+            class_name,
+            PCWSTR::null(),
+            WS_POPUP,
+            x,
+            y,
+            w,
+            h,
+            None,
+            None,
+            instance,
+            None,
+        )?;
+        SetLayeredWindowAttributes(hwnd, COLORREF(0), OVERLAY_ALPHA, LWA_ALPHA)?;
+        let _ = ShowWindow(hwnd, SW_SHOW);
+        let _ = SetForegroundWindow(hwnd);
+
+        let mut message = MSG::default();
+        while GetMessageW(&mut message, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&message);
+            DispatchMessageW(&message);
+        }
+    }
+    Ok(())
+}
+
+unsafe extern "system" fn overlay_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_PAINT => {
+            let mut paint = PAINTSTRUCT::default();
+            let hdc = BeginPaint(hwnd, &mut paint);
+            paint_overlay(hdc);
+            let _ = EndPaint(hwnd, &paint);
+            LRESULT(0)
+        }
+        WM_KEYDOWN | WM_SYSKEYDOWN | WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN => {
+            let _ = DestroyWindow(hwnd);
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+/// Paints the dimmed background, the title line and each labeled rectangle.
+///
+/// The window's client origin sits at the virtual screen's top-left corner, so
+/// stored screen coordinates are shifted by that offset before drawing.
+unsafe fn paint_overlay(hdc: HDC) {
+    let (origin_x, origin_y, width, height) = virtual_screen_rect();
+
+    let background = CreateSolidBrush(COLORREF(0x0020_2020));
+    FillRect(
+        hdc,
+        &RECT {
+            left: 0,
+            top: 0,
+            right: width,
+            bottom: height,
+        },
+        background,
+    );
+    let _ = DeleteObject(background);
+
+    SetBkMode(hdc, TRANSPARENT);
+    let previous_brush = SelectObject(hdc, GetStockObject(NULL_BRUSH));
+
+    PREVIEW.with(|preview| {
+        let (title, rects) = &*preview.borrow();
+
+        SetTextColor(hdc, COLORREF(0x00FF_FFFF));
+        draw_text(hdc, 16, 16, title);
+
+        for preview_rect in rects {
+            let color = match preview_rect.kind {
+                PreviewKind::Home => HOME_COLOR,
+                PreviewKind::Target => TARGET_COLOR,
+            };
+            let (x, y, w, h) = preview_rect.rect;
+            let (left, top) = (x - origin_x, y - origin_y);
+
+            let pen = CreatePen(PS_SOLID, 3, color);
+            let previous_pen = SelectObject(hdc, pen);
+            let _ = Rectangle(hdc, left, top, left + w, top + h);
+            SelectObject(hdc, previous_pen);
+            let _ = DeleteObject(pen);
+
+            SetTextColor(hdc, color);
+            draw_text(hdc, left + 8, top + 8, &preview_rect.label);
+        }
+    });
+
+    SelectObject(hdc, previous_brush);
+}
+
+unsafe fn draw_text(hdc: HDC, x: i32, y: i32, text: &str) {
+    let wide: Vec<u16> = text.encode_utf16().collect();
+    let _ = TextOutW(hdc, x, y, &wide);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::Window;
+
+    #[test]
+    fn preview_includes_home_and_target_of_enabled_windows() {
+        let workspace = Workspace {
+            name: "Layout".to_string(),
+            windows: vec![
+                Window {
+                    title: "Editor".to_string(),
+                    home: (0, 0, 800, 600),
+                    target: (800, 0, 800, 600),
+                    enabled: true,
+                    ..Default::default()
+                },
+                Window {
+                    title: "Hidden".to_string(),
+                    enabled: false,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let rects = preview_rects(&workspace);
+        assert_eq!(
+            rects,
+            vec![
+                PreviewRect {
+                    label: "Home: Editor".to_string(),
+                    rect: (0, 0, 800, 600),
+                    kind: PreviewKind::Home,
+                },
+                PreviewRect {
+                    label: "Target: Editor".to_string(),
+                    rect: (800, 0, 800, 600),
+                    kind: PreviewKind::Target,
+                },
+            ]
+        );
+    }
+}
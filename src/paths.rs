@@ -0,0 +1,194 @@
+//! Locations of the files Multi Manager keeps between runs.
+//!
+//! Files live in a per-user folder (`%APPDATA%\MultiManager`) so the
+//! application finds them however it is launched. `settings.json` always
+//! lives there; workspaces, layouts, bindings and logs follow the `data_dir`
+//! setting when it is set. Default files left in the working directory by
+//! older versions are copied over on first use.
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use std::path::{Path, PathBuf};
+
+/// Name of the per-user folder created under `%APPDATA%`.
+pub const APP_DIR_NAME: &str = "MultiManager";
+
+static APP_DIR: Lazy<PathBuf> = Lazy::new(|| {
+    let Some(root) = roaming_app_data() else {
+        return PathBuf::new();
+    };
+    let dir = root.join(APP_DIR_NAME);
+    match std::fs::create_dir_all(&dir) {
+        Ok(()) => dir,
+        Err(e) => {
+            // Logging is not set up yet when this first runs.
+            eprintln!(
+                "Failed to create '{}': {}; using the working directory.",
+                dir.display(),
+                e
+            );
+            PathBuf::new()
+        }
+    }
+});
+
+/// Returns the per-user application folder, creating it on first use.
+///
+/// Returns an empty path, meaning the working directory, if the folder could
+/// not be resolved or created.
+pub fn app_dir() -> &'static Path {
+    &APP_DIR
+}
+
+/// Returns the roaming application data folder of the current user.
+#[cfg(all(target_os = "windows", not(test)))]
+fn roaming_app_data() -> Option<PathBuf> {
+    use windows::Win32::System::Com::CoTaskMemFree;
+    use windows::Win32::UI::Shell::{
+        FOLDERID_RoamingAppData, SHGetKnownFolderPath, KNOWN_FOLDER_FLAG,
+    };
+
+    unsafe {
+        let path =
+            SHGetKnownFolderPath(&FOLDERID_RoamingAppData, KNOWN_FOLDER_FLAG(0), None).ok()?;
+        let resolved = path.to_string().ok().map(PathBuf::from);
+        CoTaskMemFree(Some(path.0 as *const _));
+        resolved
+    }
+}
+
+#[cfg(all(not(target_os = "windows"), not(test)))]
+fn roaming_app_data() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(PathBuf::from)
+}
+
+/// Keeps test runs out of the user's real settings.
+#[cfg(test)]
+fn roaming_app_data() -> Option<PathBuf> {
+    Some(std::env::temp_dir().join("multi_manager_tests"))
+}
+
+/// Path of `settings.json`.
+pub fn settings_path() -> PathBuf {
+    app_dir().join("settings.json")
+}
+
+/// Folder holding workspaces, layouts, bindings and logs: `data_dir` when it
+/// is set and not blank, the application folder otherwise.
+pub fn data_dir(data_dir: Option<&str>) -> PathBuf {
+    match data_dir.map(str::trim).filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => app_dir().to_path_buf(),
+    }
+}
+
+/// Resolves the default file `name` against [`data_dir`]. Absolute names are
+/// returned unchanged.
+pub fn resolve_data_path(data_dir_setting: Option<&str>, name: &str) -> String {
+    if Path::new(name).is_absolute() {
+        return name.to_string();
+    }
+    data_dir(data_dir_setting)
+        .join(name)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Resolves the default file `name` like [`resolve_data_path`] and, if that
+/// file does not exist yet, copies over a `name` left in the working directory
+/// by older versions, the way [`crate::settings::load_settings`] falls back to
+/// an old `settings.json`.
+pub fn resolve_legacy_data_path(data_dir_setting: Option<&str>, name: &str) -> String {
+    let path = resolve_data_path(data_dir_setting, name);
+    adopt_legacy_file(Path::new(name), Path::new(&path));
+    path
+}
+
+/// Copies `legacy` to `path` if `path` is missing and `legacy` is a different,
+/// existing file. Returns `true` if a copy was made.
+fn adopt_legacy_file(legacy: &Path, path: &Path) -> bool {
+    if path.exists() || !legacy.is_file() || legacy == path {
+        return false;
+    }
+    match std::fs::copy(legacy, path) {
+        Ok(_) => {
+            info!(
+                "Copied '{}' from the working directory to '{}'.",
+                legacy.display(),
+                path.display()
+            );
+            true
+        }
+        Err(e) => {
+            warn!(
+                "Failed to copy '{}' to '{}': {}",
+                legacy.display(),
+                path.display(),
+                e
+            );
+            false
+        }
+    }
+}
+
+/// Path of `multi_manager.log`.
+pub fn log_path(data_dir_setting: Option<&str>) -> PathBuf {
+    data_dir(data_dir_setting).join("multi_manager.log")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_files_resolve_against_data_dir() {
+        let in_app_dir = app_dir().join("workspaces.json");
+        assert_eq!(
+            resolve_data_path(None, "workspaces.json"),
+            in_app_dir.to_string_lossy()
+        );
+        assert_eq!(
+            resolve_data_path(Some("  "), "workspaces.json"),
+            in_app_dir.to_string_lossy()
+        );
+        assert_eq!(
+            resolve_data_path(Some("configs"), "workspaces.json"),
+            Path::new("configs")
+                .join("workspaces.json")
+                .to_string_lossy()
+        );
+        let absolute = std::env::temp_dir().join("layout.json");
+        let absolute = absolute.to_string_lossy();
+        assert_eq!(resolve_data_path(Some("configs"), &absolute), absolute);
+    }
+
+    #[test]
+    fn legacy_files_are_copied_only_when_missing() {
+        let dir = std::env::temp_dir().join("multi_manager_legacy_files");
+        std::fs::create_dir_all(&dir).unwrap();
+        let legacy = dir.join("legacy_workspaces.json");
+        let path = dir.join("workspaces.json");
+        std::fs::write(&legacy, "[1]").unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(adopt_legacy_file(&legacy, &path));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[1]");
+
+        std::fs::write(&legacy, "[2]").unwrap();
+        assert!(!adopt_legacy_file(&legacy, &path));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[1]");
+        assert!(!adopt_legacy_file(&path, &path));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn settings_stay_in_app_dir_while_logs_follow_data_dir() {
+        assert_eq!(settings_path(), app_dir().join("settings.json"));
+        assert_eq!(
+            log_path(Some("configs")),
+            Path::new("configs").join("multi_manager.log")
+        );
+        assert_eq!(log_path(None), app_dir().join("multi_manager.log"));
+    }
+}
@@ -1,6 +1,35 @@
+use crate::backup::DEFAULT_MAX_BACKUPS;
+use crate::paths::settings_path;
+use crate::window_bindings::DEFAULT_FUZZY_THRESHOLD;
+use crate::window_manager::DEFAULT_ANIMATION_MS;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Size in megabytes at which the log file rolls over unless configured otherwise.
+pub const DEFAULT_LOG_MAX_SIZE_MB: u64 = 10;
+
+/// Number of archived log files kept unless configured otherwise.
+pub const DEFAULT_LOG_KEEP_FILES: u32 = 3;
+
+/// Hotkey that suspends or resumes all workspace hotkeys unless configured otherwise.
+pub const DEFAULT_HOTKEYS_TOGGLE_KEY: &str = "Ctrl+Alt+Shift+F12";
+
+/// Delay between checks for pressed workspace hotkeys unless configured otherwise.
+pub const DEFAULT_HOTKEY_POLL_MS: u64 = 100;
+
+/// Range the hotkey polling interval is clamped to, in milliseconds.
+pub const HOTKEY_POLL_RANGE_MS: std::ops::RangeInclusive<u64> = 10..=1000;
+
+/// Seconds a countdown capture waits before grabbing the foreground window
+/// unless configured otherwise.
+pub const DEFAULT_CAPTURE_COUNTDOWN_SECS: u32 = 3;
+
+/// Pause after switching virtual desktops before moving windows, in
+/// milliseconds, unless configured otherwise.
+pub const DEFAULT_DESKTOP_SWITCH_DELAY_MS: u64 = 250;
 
 /// Persistent configuration options loaded from and saved to `settings.json`.
 ///
@@ -15,6 +44,12 @@ pub struct Settings {
     pub auto_save: bool,
     /// The log level used when initializing the logger (e.g. `"info"`).
     pub log_level: String,
+    /// Size in megabytes at which `multi_manager.log` is rolled over.
+    #[serde(default = "default_log_max_size_mb")]
+    pub log_max_size_mb: u64,
+    /// Number of rolled-over log files kept; `0` discards the log on rollover.
+    #[serde(default = "default_log_keep_files")]
+    pub log_keep_files: u32,
     /// Optional path to the last desktop layout file used.
     #[serde(default)]
     pub last_layout_file: Option<String>,
@@ -24,9 +59,163 @@ pub struct Settings {
     /// Optional path to the last saved window bindings file used.
     #[serde(default)]
     pub last_bindings_file: Option<String>,
+    /// Folder file dialogs open in and where default files and logs are kept;
+    /// `None` uses the per-user application folder (see [`crate::paths`]).
+    #[serde(default)]
+    pub data_dir: Option<String>,
     /// If `true`, additional developer debugging information is shown.
     #[serde(default)]
     pub developer_debugging: bool,
+    /// Minimum title similarity (0.0–1.0) used when fuzzy matching saved window bindings.
+    #[serde(default = "default_binding_match_threshold")]
+    pub binding_match_threshold: f64,
+    /// Number of timestamped workspace backups to keep; `0` disables backups.
+    #[serde(default = "default_max_backups")]
+    pub max_backups: usize,
+    /// If `true`, workspace JSON files are indented; otherwise they are written on one line.
+    #[serde(default = "default_pretty_json")]
+    pub pretty_json: bool,
+    /// If `true`, toggled windows glide to their destination instead of jumping.
+    #[serde(default)]
+    pub animate_moves: bool,
+    /// Duration of an animated move in milliseconds.
+    #[serde(default = "default_animation_ms")]
+    pub animation_ms: u32,
+    /// Color theme of the GUI.
+    #[serde(default)]
+    pub theme: Theme,
+    /// Executable names (e.g. `"notepad.exe"`) whose windows "Move All to Origin" leaves alone.
+    #[serde(default)]
+    pub origin_exclude_processes: Vec<String>,
+    /// If `true`, "Move All to Origin" only moves windows on the current virtual desktop.
+    #[serde(default)]
+    pub origin_current_desktop_only: bool,
+    /// If `true`, toggled windows are re-checked after moving and moved again if they drifted.
+    #[serde(default)]
+    pub verify_moves: bool,
+    /// If `true`, positions describe a window's visible frame, excluding the
+    /// invisible borders `GetWindowRect` includes.
+    #[serde(default = "default_frame_bounds_compensation")]
+    pub frame_bounds_compensation: bool,
+    /// Master switch for workspace hotkeys; `false` suspends all of them without
+    /// touching each workspace's `disabled` flag.
+    #[serde(default = "default_hotkeys_enabled")]
+    pub hotkeys_enabled: bool,
+    /// Global hotkey that flips `hotkeys_enabled`; empty for none.
+    #[serde(default = "default_hotkeys_toggle_key")]
+    pub hotkeys_toggle_key: String,
+    /// Milliseconds the hotkey thread sleeps between checks; lower reacts faster,
+    /// higher uses less CPU.
+    #[serde(default = "default_hotkey_poll_ms")]
+    pub hotkey_poll_ms: u64,
+    /// Seconds a countdown capture waits before grabbing the foreground window.
+    #[serde(default = "default_capture_countdown_secs")]
+    pub capture_countdown_secs: u32,
+    /// Milliseconds to wait after "Restore All Desktops" switches to a desktop
+    /// before moving its windows, so the switch has finished.
+    #[serde(default = "default_desktop_switch_delay_ms")]
+    pub desktop_switch_delay_ms: u64,
+    /// Title substrings or executable names "Save All Desktops" records; empty
+    /// records every window.
+    #[serde(default)]
+    pub desktop_capture_include: Vec<String>,
+    /// Title substrings or executable names "Save All Desktops" skips.
+    #[serde(default)]
+    pub desktop_capture_exclude: Vec<String>,
+    /// If `true`, minimizing the main window hides it to the tray icon.
+    #[serde(default)]
+    pub minimize_to_tray: bool,
+    /// If `true`, the main window starts hidden with only the tray icon showing.
+    #[serde(default)]
+    pub start_minimized: bool,
+    /// If `true`, a Run registry entry launches the application at login.
+    #[serde(default)]
+    pub start_with_windows: bool,
+}
+
+/// Color theme choices offered in the settings window.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    /// Follow the operating system's dark-mode setting.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::System => "System",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+        }
+    }
+
+    /// Returns `true` if this theme should render dark, asking the OS for `System`.
+    pub fn is_dark(&self) -> bool {
+        match self {
+            Theme::System => matches!(dark_light::detect(), dark_light::Mode::Dark),
+            Theme::Light => false,
+            Theme::Dark => true,
+        }
+    }
+}
+
+fn default_binding_match_threshold() -> f64 {
+    DEFAULT_FUZZY_THRESHOLD
+}
+
+fn default_max_backups() -> usize {
+    DEFAULT_MAX_BACKUPS
+}
+
+fn default_pretty_json() -> bool {
+    true
+}
+
+fn default_animation_ms() -> u32 {
+    DEFAULT_ANIMATION_MS
+}
+
+fn default_log_max_size_mb() -> u64 {
+    DEFAULT_LOG_MAX_SIZE_MB
+}
+
+fn default_log_keep_files() -> u32 {
+    DEFAULT_LOG_KEEP_FILES
+}
+
+fn default_frame_bounds_compensation() -> bool {
+    true
+}
+
+fn default_hotkeys_enabled() -> bool {
+    true
+}
+
+fn default_hotkeys_toggle_key() -> String {
+    DEFAULT_HOTKEYS_TOGGLE_KEY.to_string()
+}
+
+fn default_hotkey_poll_ms() -> u64 {
+    DEFAULT_HOTKEY_POLL_MS
+}
+
+fn default_capture_countdown_secs() -> u32 {
+    DEFAULT_CAPTURE_COUNTDOWN_SECS
+}
+
+fn default_desktop_switch_delay_ms() -> u64 {
+    DEFAULT_DESKTOP_SWITCH_DELAY_MS
+}
+
+/// Limits `ms` to [`HOTKEY_POLL_RANGE_MS`], so a hand-edited settings file can
+/// neither spin the hotkey thread nor make hotkeys feel unresponsive.
+pub fn clamp_hotkey_poll_ms(ms: u64) -> u64 {
+    ms.clamp(*HOTKEY_POLL_RANGE_MS.start(), *HOTKEY_POLL_RANGE_MS.end())
 }
 
 impl Default for Settings {
@@ -36,35 +225,170 @@ impl Default for Settings {
             save_on_exit: false,
             auto_save: false,
             log_level: "info".to_string(),
+            log_max_size_mb: DEFAULT_LOG_MAX_SIZE_MB,
+            log_keep_files: DEFAULT_LOG_KEEP_FILES,
             last_layout_file: None,
             last_workspace_file: None,
             last_bindings_file: None,
+            data_dir: None,
             developer_debugging: false,
+            binding_match_threshold: DEFAULT_FUZZY_THRESHOLD,
+            max_backups: DEFAULT_MAX_BACKUPS,
+            pretty_json: true,
+            animate_moves: false,
+            animation_ms: DEFAULT_ANIMATION_MS,
+            theme: Theme::System,
+            origin_exclude_processes: Vec::new(),
+            origin_current_desktop_only: false,
+            verify_moves: false,
+            frame_bounds_compensation: true,
+            hotkeys_enabled: true,
+            hotkeys_toggle_key: DEFAULT_HOTKEYS_TOGGLE_KEY.to_string(),
+            hotkey_poll_ms: DEFAULT_HOTKEY_POLL_MS,
+            capture_countdown_secs: DEFAULT_CAPTURE_COUNTDOWN_SECS,
+            desktop_switch_delay_ms: DEFAULT_DESKTOP_SWITCH_DELAY_MS,
+            desktop_capture_include: Vec::new(),
+            desktop_capture_exclude: Vec::new(),
+            minimize_to_tray: false,
+            start_minimized: false,
+            start_with_windows: false,
         }
     }
 }
 
-/// Load persisted settings from `settings.json` if it exists.
+/// Load persisted settings from `settings.json` (see [`settings_path`]) if it
+/// exists, falling back to a `settings.json` in the working directory left by
+/// older versions.
 ///
-/// If the file cannot be read or parsed, default settings are returned.
+/// A file that no longer matches the current schema is migrated with
+/// [`migrate_settings`]: every field that still parses is kept, the original is
+/// copied to `settings.json.bak` and the migrated settings are saved. If the
+/// file cannot be read or is not a JSON object, default settings are returned.
 pub fn load_settings() -> Settings {
+    let path = settings_path();
+    let path = if path.exists() {
+        path
+    } else {
+        PathBuf::from("settings.json")
+    };
     let mut content = String::new();
-    if let Ok(mut file) = File::open("settings.json") {
-        if file.read_to_string(&mut content).is_ok() {
-            if let Ok(settings) = serde_json::from_str::<Settings>(&content) {
-                return settings;
+    match File::open(&path).and_then(|mut file| file.read_to_string(&mut content)) {
+        Ok(_) => {}
+        Err(_) => return Settings::default(),
+    }
+    let error = match serde_json::from_str::<Settings>(&content) {
+        Ok(settings) => return settings,
+        Err(e) => e,
+    };
+
+    let Some(migration) = migrate_settings(&content) else {
+        report_load_problem(format!(
+            "Ignoring unreadable settings in '{}': {}",
+            path.display(),
+            error
+        ));
+        return Settings::default();
+    };
+    report_load_problem(format!(
+        "Migrated settings from an older format ({}); kept: {}; reset to defaults: {}",
+        error,
+        migration.kept.join(", "),
+        migration.reset.join(", ")
+    ));
+    let backup = path.with_extension("json.bak");
+    if let Err(e) = std::fs::write(&backup, &content) {
+        report_load_problem(format!(
+            "Failed to back up old settings to '{}': {}",
+            backup.display(),
+            e
+        ));
+    }
+    save_settings(&migration.settings);
+    migration.settings
+}
+
+/// Messages from [`load_settings`] waiting for logging to be set up.
+static LOAD_REPORTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Reports a problem found while loading settings, once however often the
+/// settings are loaded.
+///
+/// Logging is not initialized yet on the first load, so the message also goes
+/// to stderr and is kept for [`load_reports`].
+fn report_load_problem(message: String) {
+    let mut reports = LOAD_REPORTS.lock().unwrap();
+    if !reports.contains(&message) {
+        log::warn!("{}", message);
+        eprintln!("{}", message);
+        reports.push(message);
+    }
+}
+
+/// Returns the problems reported while loading settings, to be logged once
+/// logging is set up.
+pub fn load_reports() -> Vec<String> {
+    LOAD_REPORTS.lock().unwrap().clone()
+}
+
+/// Result of [`migrate_settings`].
+#[derive(Debug)]
+pub struct SettingsMigration {
+    pub settings: Settings,
+    /// Fields carried over from the old file.
+    pub kept: Vec<String>,
+    /// Fields that were missing or no longer parse and now hold their default.
+    pub reset: Vec<String>,
+}
+
+/// Builds [`Settings`] from a `settings.json` written for an older schema.
+///
+/// Starting from the defaults, each field of the old JSON object is copied
+/// over if the result still deserializes, so a renamed type or a field that
+/// used to be optional only resets that one field. Unknown fields are ignored.
+/// Returns `None` if `content` is not a JSON object.
+pub fn migrate_settings(content: &str) -> Option<SettingsMigration> {
+    let serde_json::Value::Object(old) = serde_json::from_str(content).ok()? else {
+        return None;
+    };
+    let serde_json::Value::Object(mut current) = serde_json::to_value(Settings::default()).ok()?
+    else {
+        return None;
+    };
+
+    let mut kept = Vec::new();
+    let mut reset = Vec::new();
+    let fields: Vec<String> = current.keys().cloned().collect();
+    for field in fields {
+        let Some(value) = old.get(&field) else {
+            reset.push(field);
+            continue;
+        };
+        let previous = current.insert(field.clone(), value.clone());
+        let candidate = serde_json::Value::Object(current.clone());
+        if serde_json::from_value::<Settings>(candidate).is_ok() {
+            kept.push(field);
+        } else {
+            if let Some(previous) = previous {
+                current.insert(field.clone(), previous);
             }
+            reset.push(field);
         }
     }
-    Settings::default()
+
+    let settings = serde_json::from_value(serde_json::Value::Object(current)).ok()?;
+    Some(SettingsMigration {
+        settings,
+        kept,
+        reset,
+    })
 }
 
-/// Save the provided `settings` struct to `settings.json` in a human
-/// readable format.
+/// Save the provided `settings` struct to `settings.json` (see
+/// [`settings_path`]) in a human readable format.
 pub fn save_settings(settings: &Settings) {
     if let Ok(json) = serde_json::to_string_pretty(settings) {
         if let Err(e) =
-            File::create("settings.json").and_then(|mut file| file.write_all(json.as_bytes()))
+            File::create(settings_path()).and_then(|mut file| file.write_all(json.as_bytes()))
         {
             eprintln!("Failed to save settings: {}", e);
         }
@@ -76,14 +400,13 @@ mod tests {
     use super::*;
     use once_cell::sync::Lazy;
     use std::fs;
-    use std::path::Path;
     use std::sync::Mutex;
 
     static TEST_MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
     fn cleanup() {
-        if Path::new("settings.json").exists() {
-            let _ = fs::remove_file("settings.json");
+        if settings_path().exists() {
+            let _ = fs::remove_file(settings_path());
         }
     }
 
@@ -95,10 +418,33 @@ mod tests {
             save_on_exit: true,
             auto_save: true,
             log_level: "debug".to_string(),
+            log_max_size_mb: 25,
+            log_keep_files: 5,
             last_layout_file: Some("file.json".into()),
             last_workspace_file: Some("work.json".into()),
             last_bindings_file: Some("bindings.json".into()),
+            data_dir: Some("configs".into()),
             developer_debugging: true,
+            binding_match_threshold: 0.8,
+            max_backups: 3,
+            pretty_json: false,
+            animate_moves: true,
+            animation_ms: 350,
+            theme: Theme::Dark,
+            origin_exclude_processes: vec!["notepad.exe".to_string()],
+            origin_current_desktop_only: true,
+            verify_moves: true,
+            frame_bounds_compensation: false,
+            hotkeys_enabled: false,
+            hotkeys_toggle_key: "Ctrl+Pause".to_string(),
+            hotkey_poll_ms: 30,
+            capture_countdown_secs: 5,
+            desktop_switch_delay_ms: 500,
+            desktop_capture_include: vec!["Code.exe".to_string()],
+            desktop_capture_exclude: vec!["Private".to_string()],
+            minimize_to_tray: true,
+            start_minimized: true,
+            start_with_windows: true,
         };
         save_settings(&settings);
         let loaded = load_settings();
@@ -106,10 +452,33 @@ mod tests {
         assert_eq!(loaded.save_on_exit, true);
         assert_eq!(loaded.auto_save, true);
         assert_eq!(loaded.log_level, "debug");
+        assert_eq!(loaded.log_max_size_mb, 25);
+        assert_eq!(loaded.log_keep_files, 5);
         assert_eq!(loaded.last_layout_file.as_deref(), Some("file.json"));
         assert_eq!(loaded.last_workspace_file.as_deref(), Some("work.json"));
         assert_eq!(loaded.last_bindings_file.as_deref(), Some("bindings.json"));
+        assert_eq!(loaded.data_dir.as_deref(), Some("configs"));
         assert_eq!(loaded.developer_debugging, true);
+        assert_eq!(loaded.binding_match_threshold, 0.8);
+        assert_eq!(loaded.max_backups, 3);
+        assert!(!loaded.pretty_json);
+        assert!(loaded.animate_moves);
+        assert_eq!(loaded.animation_ms, 350);
+        assert_eq!(loaded.theme, Theme::Dark);
+        assert_eq!(loaded.origin_exclude_processes, vec!["notepad.exe"]);
+        assert!(loaded.origin_current_desktop_only);
+        assert!(loaded.verify_moves);
+        assert!(!loaded.frame_bounds_compensation);
+        assert!(!loaded.hotkeys_enabled);
+        assert_eq!(loaded.hotkeys_toggle_key, "Ctrl+Pause");
+        assert_eq!(loaded.hotkey_poll_ms, 30);
+        assert_eq!(loaded.capture_countdown_secs, 5);
+        assert_eq!(loaded.desktop_switch_delay_ms, 500);
+        assert_eq!(loaded.desktop_capture_include, vec!["Code.exe"]);
+        assert_eq!(loaded.desktop_capture_exclude, vec!["Private"]);
+        assert!(loaded.minimize_to_tray);
+        assert!(loaded.start_minimized);
+        assert!(loaded.start_with_windows);
     }
 
     #[test]
@@ -120,10 +489,33 @@ mod tests {
             save_on_exit: false,
             auto_save: false,
             log_level: "info".to_string(),
+            log_max_size_mb: DEFAULT_LOG_MAX_SIZE_MB,
+            log_keep_files: DEFAULT_LOG_KEEP_FILES,
             last_layout_file: None,
             last_workspace_file: None,
             last_bindings_file: None,
+            data_dir: None,
             developer_debugging: false,
+            binding_match_threshold: DEFAULT_FUZZY_THRESHOLD,
+            max_backups: DEFAULT_MAX_BACKUPS,
+            pretty_json: true,
+            animate_moves: false,
+            animation_ms: DEFAULT_ANIMATION_MS,
+            theme: Theme::System,
+            origin_exclude_processes: Vec::new(),
+            origin_current_desktop_only: false,
+            verify_moves: false,
+            frame_bounds_compensation: true,
+            hotkeys_enabled: true,
+            hotkeys_toggle_key: DEFAULT_HOTKEYS_TOGGLE_KEY.to_string(),
+            hotkey_poll_ms: DEFAULT_HOTKEY_POLL_MS,
+            capture_countdown_secs: DEFAULT_CAPTURE_COUNTDOWN_SECS,
+            desktop_switch_delay_ms: DEFAULT_DESKTOP_SWITCH_DELAY_MS,
+            desktop_capture_include: Vec::new(),
+            desktop_capture_exclude: Vec::new(),
+            minimize_to_tray: false,
+            start_minimized: false,
+            start_with_windows: false,
         };
         save_settings(&settings);
         let loaded = load_settings();
@@ -131,9 +523,99 @@ mod tests {
         assert_eq!(loaded.save_on_exit, false);
         assert_eq!(loaded.auto_save, false);
         assert_eq!(loaded.log_level, "info");
+        assert_eq!(loaded.log_max_size_mb, DEFAULT_LOG_MAX_SIZE_MB);
+        assert_eq!(loaded.log_keep_files, DEFAULT_LOG_KEEP_FILES);
         assert_eq!(loaded.last_layout_file, None);
         assert_eq!(loaded.last_workspace_file, None);
         assert_eq!(loaded.last_bindings_file, None);
+        assert_eq!(loaded.data_dir, None);
         assert_eq!(loaded.developer_debugging, false);
+        assert!(loaded.pretty_json);
+        assert!(!loaded.animate_moves);
+        assert_eq!(loaded.theme, Theme::System);
+        assert!(loaded.origin_exclude_processes.is_empty());
+        assert!(!loaded.origin_current_desktop_only);
+        assert!(!loaded.verify_moves);
+        assert!(loaded.frame_bounds_compensation);
+        assert!(loaded.hotkeys_enabled);
+        assert_eq!(loaded.hotkeys_toggle_key, DEFAULT_HOTKEYS_TOGGLE_KEY);
+        assert_eq!(loaded.hotkey_poll_ms, DEFAULT_HOTKEY_POLL_MS);
+        assert_eq!(
+            loaded.capture_countdown_secs,
+            DEFAULT_CAPTURE_COUNTDOWN_SECS
+        );
+        assert_eq!(
+            loaded.desktop_switch_delay_ms,
+            DEFAULT_DESKTOP_SWITCH_DELAY_MS
+        );
+        assert!(loaded.desktop_capture_include.is_empty());
+        assert!(loaded.desktop_capture_exclude.is_empty());
+        assert!(!loaded.minimize_to_tray);
+        assert!(!loaded.start_minimized);
+        assert!(!loaded.start_with_windows);
+    }
+
+    #[test]
+    fn legacy_settings_keep_every_field_that_still_parses() {
+        // No `log_level`, which the current schema requires, and two fields
+        // whose type has since changed.
+        let legacy = r#"{
+            "save_on_exit": true,
+            "last_workspace_file": "old.json",
+            "max_backups": "five",
+            "theme": "Neon",
+            "animation_ms": 500,
+            "removed_option": 1
+        }"#;
+        assert!(serde_json::from_str::<Settings>(legacy).is_err());
+
+        let migration = migrate_settings(legacy).expect("object should migrate");
+        let settings = &migration.settings;
+        assert!(settings.save_on_exit);
+        assert_eq!(settings.last_workspace_file.as_deref(), Some("old.json"));
+        assert_eq!(settings.animation_ms, 500);
+        assert_eq!(settings.log_level, "info");
+        assert_eq!(settings.max_backups, DEFAULT_MAX_BACKUPS);
+        assert_eq!(settings.theme, Theme::System);
+        for field in ["save_on_exit", "last_workspace_file", "animation_ms"] {
+            assert!(migration.kept.iter().any(|f| f == field), "{}", field);
+        }
+        for field in ["log_level", "max_backups", "theme"] {
+            assert!(migration.reset.iter().any(|f| f == field), "{}", field);
+        }
+        assert!(migrate_settings("[1, 2]").is_none());
+    }
+
+    #[test]
+    fn loading_legacy_settings_rewrites_them_in_the_current_format() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        cleanup();
+        fs::write(
+            settings_path(),
+            r#"{ "save_on_exit": true, "max_backups": 4 }"#,
+        )
+        .expect("should write legacy settings");
+
+        let loaded = load_settings();
+        let rewritten = fs::read_to_string(settings_path()).expect("settings should be saved");
+        let backup = settings_path().with_extension("json.bak");
+        let original = fs::read_to_string(&backup).expect("original should be backed up");
+        let _ = fs::remove_file(&backup);
+        cleanup();
+
+        assert!(loaded.save_on_exit);
+        assert_eq!(loaded.max_backups, 4);
+        assert!(serde_json::from_str::<Settings>(&rewritten).is_ok());
+        assert!(original.contains("\"max_backups\": 4"));
+        assert!(load_reports().iter().any(
+            |report| report.starts_with("Migrated settings") && report.contains("max_backups")
+        ));
+    }
+
+    #[test]
+    fn hotkey_poll_interval_is_clamped() {
+        assert_eq!(clamp_hotkey_poll_ms(0), 10);
+        assert_eq!(clamp_hotkey_poll_ms(250), 250);
+        assert_eq!(clamp_hotkey_poll_ms(60_000), 1000);
     }
 }
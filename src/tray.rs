@@ -0,0 +1,301 @@
+use crate::gui::App;
+use crate::window_manager::toggle_hotkeys_enabled;
+use log::{info, warn};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicIsize, Ordering};
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::Shell::{
+    Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, NOTIFYICONDATAW,
+};
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+/// Message the notification icon sends to the tray window.
+const WM_TRAY_ICON: u32 = WM_APP + 1;
+
+/// Resource id winres gives the embedded application icon.
+const APP_ICON_ID: usize = 1;
+
+/// Entries of the tray icon's context menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TrayCommand {
+    ShowHide = 1,
+    SendAllHome,
+    ToggleHotkeys,
+    Exit,
+}
+
+impl TrayCommand {
+    const ALL: [TrayCommand; 4] = [
+        TrayCommand::ShowHide,
+        TrayCommand::SendAllHome,
+        TrayCommand::ToggleHotkeys,
+        TrayCommand::Exit,
+    ];
+
+    fn from_id(id: usize) -> Option<TrayCommand> {
+        Self::ALL
+            .into_iter()
+            .find(|command| *command as usize == id)
+    }
+}
+
+/// State the tray window's procedure acts on.
+struct TrayState {
+    app: App,
+    main_window: HWND,
+}
+
+thread_local! {
+    static TRAY: RefCell<Option<TrayState>> = const { RefCell::new(None) };
+}
+
+/// Handle of the hidden window that owns the notification icon, or 0.
+static TRAY_WINDOW: AtomicIsize = AtomicIsize::new(0);
+
+/// Adds the notification area icon on its own thread.
+///
+/// Left-clicking the icon restores `main_window`; right-clicking opens a menu
+/// to show or hide it, send all windows home, suspend hotkeys or exit. Exit
+/// closes `main_window` normally, so the usual save-on-exit logic still runs.
+pub fn start_tray(app: App, main_window: isize) {
+    std::thread::spawn(move || {
+        TRAY.with(|tray| {
+            *tray.borrow_mut() = Some(TrayState {
+                app,
+                main_window: HWND(main_window as *mut _),
+            })
+        });
+        if let Err(e) = run_tray() {
+            warn!("Failed to create tray icon: {}", e);
+        }
+    });
+}
+
+/// Removes the notification icon, e.g. right before the application exits.
+pub fn stop_tray() {
+    let hwnd = TRAY_WINDOW.swap(0, Ordering::SeqCst);
+    if hwnd != 0 {
+        unsafe {
+            SendMessageW(HWND(hwnd as *mut _), WM_CLOSE, WPARAM(0), LPARAM(0));
+        }
+    }
+}
+
+/// Shows `main_window` if it is hidden or minimized, and brings it to the front.
+pub fn show_main_window(main_window: HWND) {
+    unsafe {
+        let _ = ShowWindow(main_window, SW_SHOW);
+        if IsIconic(main_window).as_bool() {
+            let _ = ShowWindow(main_window, SW_RESTORE);
+        }
+        let _ = SetForegroundWindow(main_window);
+    }
+}
+
+/// Hides `main_window`, leaving only the tray icon.
+pub fn hide_main_window(main_window: HWND) {
+    unsafe {
+        let _ = ShowWindow(main_window, SW_HIDE);
+    }
+}
+
+/// Creates the hidden tray window, adds the icon and pumps messages until the
+/// window is closed.
+fn run_tray() -> windows::core::Result<()> {
+    let class_name = w!("MultiManagerTray");
+    unsafe {
+        let instance = GetModuleHandleW(None)?;
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(tray_proc),
+            hInstance: instance.into(),
+            lpszClassName: class_name,
+            ..Default::default()
+        };
+        RegisterClassW(&class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            class_name,
+            PCWSTR::null(),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            instance,
+            None,
+        )?;
+
+        let icon = LoadIconW(instance, PCWSTR(APP_ICON_ID as *const u16))
+            .or_else(|_| LoadIconW(None, IDI_APPLICATION))?;
+        let mut data = notify_icon_data(hwnd);
+        data.uFlags = NIF_ICON | NIF_MESSAGE | NIF_TIP;
+        data.uCallbackMessage = WM_TRAY_ICON;
+        data.hIcon = icon;
+        let tip: Vec<u16> = "Multi Manager".encode_utf16().collect();
+        data.szTip[..tip.len()].copy_from_slice(&tip);
+        if !Shell_NotifyIconW(NIM_ADD, &data).as_bool() {
+            let _ = DestroyWindow(hwnd);
+            return Err(windows::core::Error::from_win32());
+        }
+        TRAY_WINDOW.store(hwnd.0 as isize, Ordering::SeqCst);
+        info!("Tray icon added.");
+
+        let mut message = MSG::default();
+        while GetMessageW(&mut message, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&message);
+            DispatchMessageW(&message);
+        }
+    }
+    Ok(())
+}
+
+fn notify_icon_data(hwnd: HWND) -> NOTIFYICONDATAW {
+    NOTIFYICONDATAW {
+        cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: hwnd,
+        uID: 1,
+        ..Default::default()
+    }
+}
+
+unsafe extern "system" fn tray_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_TRAY_ICON => {
+            match lparam.0 as u32 {
+                WM_LBUTTONUP => TRAY.with(|tray| {
+                    if let Some(state) = tray.borrow().as_ref() {
+                        show_main_window(state.main_window);
+                    }
+                }),
+                WM_RBUTTONUP | WM_CONTEXTMENU => {
+                    if let Some(command) = show_menu(hwnd) {
+                        run_command(command);
+                    }
+                }
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        WM_CLOSE => {
+            let _ = DestroyWindow(hwnd);
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            let _ = Shell_NotifyIconW(NIM_DELETE, &notify_icon_data(hwnd));
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+/// Shows the context menu at the cursor and returns the chosen command.
+unsafe fn show_menu(hwnd: HWND) -> Option<TrayCommand> {
+    let (visible, hotkeys_enabled) = TRAY.with(|tray| {
+        tray.borrow().as_ref().map_or((true, true), |state| {
+            (
+                IsWindowVisible(state.main_window).as_bool(),
+                *state.app.hotkeys_enabled.lock().unwrap(),
+            )
+        })
+    });
+
+    let menu = CreatePopupMenu().ok()?;
+    let show_hide = if visible {
+        w!("Hide Window")
+    } else {
+        w!("Show Window")
+    };
+    let hotkeys_flags = if hotkeys_enabled {
+        MF_STRING | MF_CHECKED
+    } else {
+        MF_STRING
+    };
+    let _ = AppendMenuW(menu, MF_STRING, TrayCommand::ShowHide as usize, show_hide);
+    let _ = AppendMenuW(
+        menu,
+        MF_STRING,
+        TrayCommand::SendAllHome as usize,
+        w!("Send All Home"),
+    );
+    let _ = AppendMenuW(
+        menu,
+        hotkeys_flags,
+        TrayCommand::ToggleHotkeys as usize,
+        w!("Hotkeys Active"),
+    );
+    let _ = AppendMenuW(menu, MF_SEPARATOR, 0, PCWSTR::null());
+    let _ = AppendMenuW(menu, MF_STRING, TrayCommand::Exit as usize, w!("Exit"));
+
+    let mut cursor = POINT::default();
+    let _ = GetCursorPos(&mut cursor);
+    // The menu only closes on an outside click if its owner is in the foreground.
+    let _ = SetForegroundWindow(hwnd);
+    let chosen = TrackPopupMenu(
+        menu,
+        TPM_RETURNCMD | TPM_RIGHTBUTTON,
+        cursor.x,
+        cursor.y,
+        0,
+        hwnd,
+        None,
+    );
+    let _ = DestroyMenu(menu);
+    TrayCommand::from_id(chosen.0 as usize)
+}
+
+fn run_command(command: TrayCommand) {
+    TRAY.with(|tray| {
+        let tray = tray.borrow();
+        let Some(state) = tray.as_ref() else {
+            return;
+        };
+        match command {
+            TrayCommand::ShowHide => unsafe {
+                if IsWindowVisible(state.main_window).as_bool() {
+                    hide_main_window(state.main_window);
+                } else {
+                    show_main_window(state.main_window);
+                }
+            },
+            TrayCommand::SendAllHome => state.app.send_all_home(),
+            TrayCommand::ToggleHotkeys => {
+                let enabled = toggle_hotkeys_enabled(&state.app);
+                info!(
+                    "Workspace hotkeys {} from the tray.",
+                    if enabled { "resumed" } else { "suspended" }
+                );
+            }
+            TrayCommand::Exit => {
+                // Close through the GUI so `on_exit` saves as usual.
+                show_main_window(state.main_window);
+                unsafe {
+                    let _ = PostMessageW(state.main_window, WM_CLOSE, WPARAM(0), LPARAM(0));
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn menu_ids_map_back_to_commands() {
+        for command in TrayCommand::ALL {
+            assert_eq!(TrayCommand::from_id(command as usize), Some(command));
+        }
+        assert_eq!(TrayCommand::from_id(0), None);
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::{Duration, Instant};
+
+/// Number of undo steps kept; older snapshots are dropped.
+pub const MAX_UNDO_STEPS: usize = 50;
+
+/// Changes closer together than this are undone as one step, so dragging a
+/// value does not leave a snapshot per frame.
+const COALESCE_WINDOW: Duration = Duration::from_millis(1000);
+
+/// Bounded undo/redo history of snapshots of some state.
+///
+/// The history keeps a copy of the state as of the last recorded change (the
+/// baseline). When a change is recorded the baseline becomes an undo step and
+/// the new state is copied, so the state is only cloned when it changed.
+#[derive(Clone)]
+pub struct UndoHistory<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    baseline: Option<T>,
+    changed: bool,
+    last_recorded: Option<Instant>,
+}
+
+impl<T> Default for UndoHistory<T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            baseline: None,
+            changed: false,
+            last_recorded: None,
+        }
+    }
+}
+
+impl<T: Clone> UndoHistory<T> {
+    /// Forgets all steps and starts over from `current`, e.g. after loading a file.
+    pub fn reset(&mut self, current: &T) {
+        self.undo.clear();
+        self.redo.clear();
+        self.baseline = Some(current.clone());
+        self.changed = false;
+        self.last_recorded = None;
+    }
+
+    /// Returns `true` once [`reset`](Self::reset) has given the history a starting point.
+    pub fn has_baseline(&self) -> bool {
+        self.baseline.is_some()
+    }
+
+    /// Notes that the state changed; the next [`take_changed`](Self::take_changed)
+    /// reports it.
+    pub fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    /// Returns whether [`mark_changed`](Self::mark_changed) was called since the
+    /// last call, clearing the flag.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Records `current` as the state after a change, making the previous state
+    /// an undo step unless it belongs to the same burst of edits.
+    pub fn record(&mut self, current: &T) {
+        self.record_at(current, Instant::now());
+    }
+
+    fn record_at(&mut self, current: &T, now: Instant) {
+        let coalesce = self
+            .last_recorded
+            .is_some_and(|last| now.duration_since(last) < COALESCE_WINDOW);
+        let previous = self.baseline.replace(current.clone());
+        if let Some(previous) = previous.filter(|_| !coalesce) {
+            self.undo.push(previous);
+            if self.undo.len() > MAX_UNDO_STEPS {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.last_recorded = Some(now);
+    }
+
+    /// Steps back, returning the state to restore.
+    pub fn undo(&mut self) -> Option<T> {
+        let previous = self.undo.pop()?;
+        if let Some(current) = self.baseline.replace(previous.clone()) {
+            self.redo.push(current);
+        }
+        self.last_recorded = None;
+        Some(previous)
+    }
+
+    /// Re-applies the last undone step, returning the state to restore.
+    pub fn redo(&mut self) -> Option<T> {
+        let next = self.redo.pop()?;
+        if let Some(current) = self.baseline.replace(next.clone()) {
+            self.undo.push(current);
+        }
+        self.last_recorded = None;
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let start = Instant::now();
+        let mut history = UndoHistory::default();
+        history.reset(&1);
+        history.record_at(&2, start);
+        history.record_at(&3, start + COALESCE_WINDOW);
+
+        assert_eq!(history.undo(), Some(2));
+        assert_eq!(history.undo(), Some(1));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(2));
+
+        // A new change discards the redo steps.
+        history.record_at(&4, start + COALESCE_WINDOW * 2);
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(), Some(2));
+    }
+
+    #[test]
+    fn rapid_changes_are_one_step() {
+        let start = Instant::now();
+        let mut history = UndoHistory::default();
+        history.reset(&0);
+        for (i, value) in [1, 2, 3].iter().enumerate() {
+            history.record_at(value, start + Duration::from_millis(100 * i as u64));
+        }
+        assert_eq!(history.undo(), Some(0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let start = Instant::now();
+        let mut history = UndoHistory::default();
+        history.reset(&0);
+        for value in 1..=MAX_UNDO_STEPS + 5 {
+            history.record_at(&value, start + COALESCE_WINDOW * value as u32);
+        }
+        let mut steps = 0;
+        while history.undo().is_some() {
+            steps += 1;
+        }
+        assert_eq!(steps, MAX_UNDO_STEPS);
+    }
+}
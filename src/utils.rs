@@ -84,6 +84,46 @@ pub fn show_confirmation_box(message: &str, title: &str) -> bool {
     }
 }
 
+/// Answer to [`show_save_changes_box`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveChoice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+/// Asks whether to save pending changes, with "Yes" (save), "No" (discard) and
+/// "Cancel" buttons.
+///
+/// Closing the dialog counts as [`SaveChoice::Cancel`].
+pub fn show_save_changes_box(message: &str, title: &str) -> SaveChoice {
+    let result = unsafe {
+        MessageBoxW(
+            HWND(ptr::null_mut()),
+            PCWSTR(
+                message
+                    .encode_utf16()
+                    .chain(Some(0))
+                    .collect::<Vec<u16>>()
+                    .as_ptr(),
+            ),
+            PCWSTR(
+                title
+                    .encode_utf16()
+                    .chain(Some(0))
+                    .collect::<Vec<u16>>()
+                    .as_ptr(),
+            ),
+            MB_YESNOCANCEL | MB_ICONWARNING,
+        )
+    };
+    match result {
+        IDYES => SaveChoice::Save,
+        IDNO => SaveChoice::Discard,
+        _ => SaveChoice::Cancel,
+    }
+}
+
 /// Display an error message box with an "OK" button.
 ///
 /// This is similar to [`show_message_box`] but uses a red error icon.
@@ -1,14 +1,114 @@
 #[cfg(target_os = "windows")]
-use windows::core::Result;
+use log::{debug, warn};
 #[cfg(target_os = "windows")]
-use windows::Win32::Foundation::HWND;
-/// Represents a virtual desktop. Only a minimal stub implementation is
-/// provided as full virtual desktop manipulation is outside the scope of this
-/// project.
+use windows::core::{IUnknown, Interface, Result, GUID};
+#[cfg(target_os = "windows")]
+use windows::Win32::Foundation::{E_NOINTERFACE, HWND};
+#[cfg(target_os = "windows")]
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, IServiceProvider, CLSCTX_ALL,
+    CLSCTX_LOCAL_SERVER, COINIT_APARTMENTTHREADED,
+};
+#[cfg(target_os = "windows")]
+use windows::Win32::UI::Shell::Common::IObjectArray;
+#[cfg(target_os = "windows")]
+use windows::Win32::UI::Shell::{IVirtualDesktopManager, VirtualDesktopManager};
+
+/// CLSID of the immersive shell, which hosts the internal virtual desktop services.
+#[cfg(target_os = "windows")]
+const CLSID_IMMERSIVE_SHELL: GUID = GUID::from_u128(0xc2f03a33_21f5_47fa_b4bb_156362a2f239);
+
+/// Service id used to query `IVirtualDesktopManagerInternal` from the immersive shell.
+#[cfg(target_os = "windows")]
+const CLSID_VIRTUAL_DESKTOP_MANAGER_INTERNAL: GUID =
+    GUID::from_u128(0xc5e0cdca_7b6e_41b2_9fc4_d93975cc467b);
+
+/// Known `(IVirtualDesktopManagerInternal, IVirtualDesktop)` interface ids,
+/// newest Windows build first.
+///
+/// The internal interfaces are undocumented and their ids change between
+/// Windows releases. Only the leading methods, whose layout is shared by all of
+/// the versions listed here, are used.
+#[cfg(target_os = "windows")]
+const INTERFACE_VERSIONS: [(GUID, GUID); 3] = [
+    // Windows 11 24H2
+    (
+        GUID::from_u128(0x53f5ca0b_158f_4124_900c_057158060b27),
+        GUID::from_u128(0x3f07f4be_b107_441a_af0f_39d82529072c),
+    ),
+    // Windows 11 22H2/23H2
+    (
+        GUID::from_u128(0xa3175f2d_239c_4bd2_8aa0_eeba8b0b138e),
+        GUID::from_u128(0x3f07f4be_b107_441a_af0f_39d82529072c),
+    ),
+    // Windows 10
+    (
+        GUID::from_u128(0xf31574d6_b682_4cdc_bd56_1827860abec6),
+        GUID::from_u128(0xff72ffdd_be7e_43fc_9c03_ad81681a46d6),
+    ),
+];
+
+/// Raw declarations of the undocumented shell interfaces, kept in their own
+/// module so the COM method names can keep their Windows casing.
+#[cfg(target_os = "windows")]
+#[allow(non_snake_case)]
+mod interfaces {
+    use std::ffi::c_void;
+    use windows::core::{interface, GUID, HRESULT, PCWSTR};
+    use windows::Win32::Foundation::{BOOL, HWND};
+
+    /// Leading methods of the undocumented `IVirtualDesktop` interface. The id
+    /// in the attribute is a placeholder; instances are obtained through
+    /// [`super::INTERFACE_VERSIONS`].
+    #[interface("ff72ffdd-be7e-43fc-9c03-ad81681a46d6")]
+    pub unsafe trait IVirtualDesktop: windows::core::IUnknown {
+        pub unsafe fn IsViewVisible(&self, view: *mut c_void, visible: *mut BOOL) -> HRESULT;
+        pub unsafe fn GetId(&self, id: *mut GUID) -> HRESULT;
+    }
+
+    /// Leading methods of the undocumented `IVirtualDesktopManagerInternal`
+    /// interface. See [`IVirtualDesktop`] for how the interface id is chosen.
+    #[interface("f31574d6-b682-4cdc-bd56-1827860abec6")]
+    pub unsafe trait IVirtualDesktopManagerInternal: windows::core::IUnknown {
+        pub unsafe fn GetCount(&self, count: *mut u32) -> HRESULT;
+        pub unsafe fn MoveViewToDesktop(&self, view: *mut c_void, desktop: *mut c_void) -> HRESULT;
+        pub unsafe fn CanViewMoveDesktops(&self, view: *mut c_void, can_move: *mut BOOL)
+            -> HRESULT;
+        pub unsafe fn GetCurrentDesktop(&self, desktop: *mut *mut c_void) -> HRESULT;
+        pub unsafe fn GetDesktops(&self, desktops: *mut *mut c_void) -> HRESULT;
+        pub unsafe fn GetAdjacentDesktop(
+            &self,
+            from: *mut c_void,
+            direction: i32,
+            desktop: *mut *mut c_void,
+        ) -> HRESULT;
+        pub unsafe fn SwitchDesktop(&self, desktop: *mut c_void) -> HRESULT;
+    }
+
+    /// Leading methods of the undocumented `IApplicationViewCollection`
+    /// interface, used to look up the shell view that backs a window.
+    #[interface("1841c6d7-4f9d-42c0-af41-8747538f10e5")]
+    pub unsafe trait IApplicationViewCollection: windows::core::IUnknown {
+        pub unsafe fn GetViews(&self, views: *mut *mut c_void) -> HRESULT;
+        pub unsafe fn GetViewsByZOrder(&self, views: *mut *mut c_void) -> HRESULT;
+        pub unsafe fn GetViewsByAppUserModelId(
+            &self,
+            id: PCWSTR,
+            views: *mut *mut c_void,
+        ) -> HRESULT;
+        pub unsafe fn GetViewForHwnd(&self, hwnd: HWND, view: *mut *mut c_void) -> HRESULT;
+    }
+}
+#[cfg(target_os = "windows")]
+use interfaces::{IApplicationViewCollection, IVirtualDesktop, IVirtualDesktopManagerInternal};
+
+/// Represents a virtual desktop by its position in the task view and its
+/// shell-assigned id.
 #[cfg(target_os = "windows")]
 #[derive(Clone)]
 pub struct Desktop {
     index: u32,
+    id: GUID,
 }
 
 #[cfg(target_os = "windows")]
@@ -18,28 +118,233 @@ impl Desktop {
     }
 }
 
+/// Initializes COM for the current thread for as long as the guard is alive.
+#[cfg(target_os = "windows")]
+struct ComGuard {
+    initialized: bool,
+}
+
+#[cfg(target_os = "windows")]
+impl ComGuard {
+    fn new() -> Self {
+        let initialized = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }.is_ok();
+        ComGuard { initialized }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for ComGuard {
+    fn drop(&mut self) {
+        if self.initialized {
+            unsafe { CoUninitialize() };
+        }
+    }
+}
+
+/// Connection to the shell's internal virtual desktop manager.
+#[cfg(target_os = "windows")]
+struct DesktopShell {
+    provider: IServiceProvider,
+    internal: IVirtualDesktopManagerInternal,
+    desktop_iid: GUID,
+}
+
+#[cfg(target_os = "windows")]
+impl DesktopShell {
+    fn connect() -> Result<Self> {
+        let provider: IServiceProvider =
+            unsafe { CoCreateInstance(&CLSID_IMMERSIVE_SHELL, None, CLSCTX_LOCAL_SERVER)? };
+        for (internal_iid, desktop_iid) in INTERFACE_VERSIONS.iter() {
+            let mut raw = std::ptr::null_mut();
+            let hr = unsafe {
+                (Interface::vtable(&provider).QueryService)(
+                    provider.as_raw(),
+                    &CLSID_VIRTUAL_DESKTOP_MANAGER_INTERNAL,
+                    internal_iid,
+                    &mut raw,
+                )
+            };
+            if hr.is_ok() && !raw.is_null() {
+                return Ok(DesktopShell {
+                    provider,
+                    internal: unsafe { IVirtualDesktopManagerInternal::from_raw(raw) },
+                    desktop_iid: *desktop_iid,
+                });
+            }
+        }
+        Err(E_NOINTERFACE.into())
+    }
+
+    /// Returns every desktop together with its COM object, in task view order.
+    fn desktops(&self) -> Result<Vec<(Desktop, IVirtualDesktop)>> {
+        let array = unsafe {
+            let mut raw = std::ptr::null_mut();
+            self.internal.GetDesktops(&mut raw).ok()?;
+            IObjectArray::from_raw(raw)
+        };
+        let count = unsafe { array.GetCount()? };
+        let mut desktops = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let desktop = unsafe {
+                let mut raw = std::ptr::null_mut();
+                (Interface::vtable(&array).GetAt)(
+                    array.as_raw(),
+                    index,
+                    &self.desktop_iid,
+                    &mut raw,
+                )
+                .ok()?;
+                IVirtualDesktop::from_raw(raw)
+            };
+            let mut id = GUID::zeroed();
+            unsafe { desktop.GetId(&mut id).ok()? };
+            desktops.push((Desktop { index, id }, desktop));
+        }
+        Ok(desktops)
+    }
+
+    /// Moves the shell view backing `hwnd` onto `desktop`. Unlike the
+    /// documented `IVirtualDesktopManager::MoveWindowToDesktop`, this also
+    /// works for windows owned by other processes.
+    fn move_view(&self, hwnd: HWND, desktop: &IVirtualDesktop) -> Result<()> {
+        let collection: IApplicationViewCollection = unsafe {
+            self.provider
+                .QueryService(&IApplicationViewCollection::IID)?
+        };
+        let view = unsafe {
+            let mut raw = std::ptr::null_mut();
+            collection.GetViewForHwnd(hwnd, &mut raw).ok()?;
+            IUnknown::from_raw(raw)
+        };
+        unsafe {
+            self.internal
+                .MoveViewToDesktop(view.as_raw(), desktop.as_raw())
+                .ok()
+        }
+    }
+
+    fn current_id(&self) -> Result<GUID> {
+        let desktop = unsafe {
+            let mut raw = std::ptr::null_mut();
+            self.internal.GetCurrentDesktop(&mut raw).ok()?;
+            IVirtualDesktop::from_raw(raw)
+        };
+        let mut id = GUID::zeroed();
+        unsafe { desktop.GetId(&mut id).ok()? };
+        Ok(id)
+    }
+}
+
+/// Stand-in used when the shell's virtual desktop services are unavailable,
+/// e.g. on an unsupported Windows build.
+#[cfg(target_os = "windows")]
+fn fallback_desktop(reason: &windows::core::Error) -> Desktop {
+    debug!(
+        "Virtual desktop services unavailable ({}); assuming a single desktop.",
+        reason
+    );
+    Desktop {
+        index: 0,
+        id: GUID::zeroed(),
+    }
+}
+
 /// Retrieve the current active virtual desktop.
 #[cfg(target_os = "windows")]
 pub fn get_current_desktop() -> Result<Desktop> {
-    Ok(Desktop { index: 0 })
+    let _com = ComGuard::new();
+    let shell = match DesktopShell::connect() {
+        Ok(shell) => shell,
+        Err(e) => return Ok(fallback_desktop(&e)),
+    };
+    let current = shell.current_id()?;
+    shell
+        .desktops()?
+        .into_iter()
+        .map(|(desktop, _)| desktop)
+        .find(|desktop| desktop.id == current)
+        .ok_or_else(|| E_NOINTERFACE.into())
 }
 
 /// Enumerate available virtual desktops.
 #[cfg(target_os = "windows")]
 pub fn get_desktops() -> Result<Vec<Desktop>> {
-    Ok(vec![Desktop { index: 0 }])
+    let _com = ComGuard::new();
+    match DesktopShell::connect() {
+        Ok(shell) => Ok(shell
+            .desktops()?
+            .into_iter()
+            .map(|(desktop, _)| desktop)
+            .collect()),
+        Err(e) => Ok(vec![fallback_desktop(&e)]),
+    }
 }
 
 /// Switch to the provided desktop.
 #[cfg(target_os = "windows")]
-pub fn switch_desktop(_desktop: &Desktop) -> Result<()> {
-    Ok(())
+pub fn switch_desktop(desktop: &Desktop) -> Result<()> {
+    if desktop.id == GUID::zeroed() {
+        return Ok(());
+    }
+    let _com = ComGuard::new();
+    let shell = DesktopShell::connect()?;
+    match shell
+        .desktops()?
+        .into_iter()
+        .find(|(candidate, _)| candidate.id == desktop.id)
+    {
+        Some((_, target)) => unsafe { shell.internal.SwitchDesktop(target.as_raw()).ok() },
+        None => {
+            warn!("Virtual desktop {} no longer exists.", desktop.index);
+            Err(E_NOINTERFACE.into())
+        }
+    }
+}
+
+/// Move the specified window onto the provided desktop.
+#[cfg(target_os = "windows")]
+pub fn move_window_to_desktop(hwnd: HWND, desktop: &Desktop) -> Result<()> {
+    if desktop.id == GUID::zeroed() {
+        return Ok(());
+    }
+    let _com = ComGuard::new();
+    let shell = DesktopShell::connect()?;
+    match shell
+        .desktops()?
+        .into_iter()
+        .find(|(candidate, _)| candidate.id == desktop.id)
+    {
+        Some((_, target)) => shell.move_view(hwnd, &target),
+        None => {
+            warn!("Virtual desktop {} no longer exists.", desktop.index);
+            Err(E_NOINTERFACE.into())
+        }
+    }
 }
 
 /// Obtain the desktop that owns the specified window handle.
+///
+/// Windows that are not assigned to a specific desktop (for example windows
+/// pinned to all desktops) are reported as belonging to the current desktop.
 #[cfg(target_os = "windows")]
-pub fn get_desktop_by_window(_hwnd: HWND) -> Result<Desktop> {
-    Ok(Desktop { index: 0 })
+pub fn get_desktop_by_window(hwnd: HWND) -> Result<Desktop> {
+    let _com = ComGuard::new();
+    let shell = match DesktopShell::connect() {
+        Ok(shell) => shell,
+        Err(e) => return Ok(fallback_desktop(&e)),
+    };
+    let manager: IVirtualDesktopManager =
+        unsafe { CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL)? };
+    let id = match unsafe { manager.GetWindowDesktopId(hwnd) } {
+        Ok(id) if id != GUID::zeroed() => id,
+        _ => shell.current_id()?,
+    };
+    shell
+        .desktops()?
+        .into_iter()
+        .map(|(desktop, _)| desktop)
+        .find(|desktop| desktop.id == id)
+        .ok_or_else(|| E_NOINTERFACE.into())
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -74,6 +379,10 @@ pub fn switch_desktop(_: &Desktop) -> Result<()> {
     Ok(())
 }
 #[cfg(not(target_os = "windows"))]
+pub fn move_window_to_desktop(_: HWND, _: &Desktop) -> Result<()> {
+    Ok(())
+}
+#[cfg(not(target_os = "windows"))]
 pub fn get_desktop_by_window(_: HWND) -> Result<Desktop> {
     Ok(Desktop { index: 0 })
 }
@@ -1,4 +1,4 @@
-use crate::workspace::Workspace;
+use crate::workspace::{WindowFingerprint, Workspace};
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -26,13 +26,20 @@ pub struct WindowBindingSnapshot {
 }
 
 /// Aggregated statistics describing the result of applying saved bindings.
+///
+/// `fuzzy_matched` counts bindings whose window was found only through title
+/// similarity; those bindings are also counted in `restored` or `invalidated`.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct BindingApplicationStats {
     pub restored: usize,
     pub invalidated: usize,
     pub unmatched: usize,
+    pub fuzzy_matched: usize,
 }
 
+/// Default minimum similarity (0.0–1.0) for a fuzzy title match.
+pub const DEFAULT_FUZZY_THRESHOLD: f64 = 0.6;
+
 /// Errors that can occur when saving or loading bindings.
 #[derive(Debug)]
 pub enum WindowBindingError {
@@ -136,10 +143,107 @@ pub fn load_window_bindings(
     Ok(bindings)
 }
 
+/// Computes the Levenshtein edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            current[j + 1] = (previous[j + 1] + 1)
+                .min(current[j] + 1)
+                .min(previous[j] + cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Returns a similarity score between `0.0` and `1.0` for two window titles.
+///
+/// The score is the higher of the normalized Levenshtein similarity and the
+/// token (word) overlap, both computed case-insensitively.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = a.trim().to_lowercase();
+    let b = b.trim().to_lowercase();
+    if a == b {
+        return 1.0;
+    }
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    let edit_similarity = 1.0 - levenshtein(&a, &b) as f64 / max_len as f64;
+
+    let tokens = |s: &str| -> std::collections::HashSet<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string())
+            .collect()
+    };
+    let (tokens_a, tokens_b) = (tokens(&a), tokens(&b));
+    let union = tokens_a.union(&tokens_b).count();
+    let token_similarity = if union == 0 {
+        0.0
+    } else {
+        tokens_a.intersection(&tokens_b).count() as f64 / union as f64
+    };
+
+    edit_similarity.max(token_similarity)
+}
+
+/// Finds the window a saved binding refers to.
+///
+/// Matching priority is:
+/// 1. An exact title match (preferring the saved index).
+/// 2. The window at the saved index.
+/// 3. The window whose title is most similar, if the similarity is at least `threshold`.
+///
+/// Returns the index and whether the match was fuzzy.
+fn find_binding_target(
+    workspace: &Workspace,
+    binding: &WindowBindingSnapshot,
+    threshold: f64,
+) -> Option<(usize, bool)> {
+    let windows = &workspace.windows;
+
+    if windows
+        .get(binding.window_index)
+        .is_some_and(|w| w.title == binding.window_title)
+    {
+        return Some((binding.window_index, false));
+    }
+
+    if let Some(index) = windows.iter().position(|w| w.title == binding.window_title) {
+        return Some((index, false));
+    }
+
+    if binding.window_index < windows.len() {
+        return Some((binding.window_index, false));
+    }
+
+    windows
+        .iter()
+        .enumerate()
+        .map(|(i, w)| (i, title_similarity(&w.title, &binding.window_title)))
+        .filter(|(_, score)| *score >= threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| (i, true))
+}
+
 /// Apply previously saved window bindings to the provided workspaces.
+///
+/// Windows are matched by exact title, then by saved index, and finally by
+/// title similarity of at least `fuzzy_threshold` (see [`title_similarity`]).
 pub fn apply_window_bindings(
     workspaces: &mut [Workspace],
     bindings: &[WorkspaceBindingSnapshot],
+    fuzzy_threshold: f64,
 ) -> BindingApplicationStats {
     let mut stats = BindingApplicationStats::default();
 
@@ -171,27 +275,9 @@ pub fn apply_window_bindings(
         let workspace = &mut workspaces[workspace_idx];
 
         for window_binding in &binding.windows {
-            let target_index = if window_binding.window_index < workspace.windows.len() {
-                let mut index = Some(window_binding.window_index);
-
-                if workspace.windows[window_binding.window_index].title
-                    != window_binding.window_title
-                {
-                    index = workspace
-                        .windows
-                        .iter()
-                        .position(|w| w.title == window_binding.window_title);
-                }
-
-                index
-            } else {
-                workspace
-                    .windows
-                    .iter()
-                    .position(|w| w.title == window_binding.window_title)
-            };
-
-            let Some(index) = target_index else {
+            let Some((index, fuzzy)) =
+                find_binding_target(workspace, window_binding, fuzzy_threshold)
+            else {
                 stats.unmatched += 1;
                 warn!(
                     "No matching window found for '{}' in workspace '{}'.",
@@ -200,10 +286,17 @@ pub fn apply_window_bindings(
                 continue;
             };
 
+            if fuzzy {
+                stats.fuzzy_matched += 1;
+                info!(
+                    "Fuzzy matched saved binding '{}' to window '{}' in workspace '{}'.",
+                    window_binding.window_title, workspace.windows[index].title, workspace.name
+                );
+            }
+
             if let Some(window) = workspace.windows.get_mut(index) {
                 let hwnd = HWND(window_binding.hwnd as *mut c_void);
-                let is_valid = unsafe { IsWindow(hwnd).as_bool() };
-                if is_valid {
+                if window.matches_live(hwnd) {
                     window.id = window_binding.hwnd;
                     window.valid = true;
                     stats.restored += 1;
@@ -223,3 +316,202 @@ pub fn apply_window_bindings(
 
     stats
 }
+
+/// A live top-level window a missing workspace window may be bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveWindow {
+    pub hwnd: isize,
+    pub title: String,
+    pub fingerprint: Option<WindowFingerprint>,
+}
+
+/// Pairs each `(workspace, window)` entry in `missing` with the index of the
+/// live window in `live` that most resembles it.
+///
+/// A candidate must match the entry's fingerprint, when both are known, and
+/// have a title similarity of at least `threshold`. Each live window is used
+/// at most once, with entries claimed in the order they are listed.
+pub fn match_missing_windows(
+    workspaces: &[Workspace],
+    missing: &[(usize, usize)],
+    live: &[LiveWindow],
+    threshold: f64,
+) -> Vec<((usize, usize), usize)> {
+    let mut taken = vec![false; live.len()];
+    let mut matches = Vec::new();
+    for &(ws_idx, win_idx) in missing {
+        let Some(window) = workspaces
+            .get(ws_idx)
+            .and_then(|ws| ws.windows.get(win_idx))
+        else {
+            continue;
+        };
+        let best = live
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !taken[*i])
+            .filter(
+                |(_, candidate)| match (&window.fingerprint, &candidate.fingerprint) {
+                    (Some(stored), Some(live)) => stored.matches(live),
+                    _ => true,
+                },
+            )
+            .map(|(i, candidate)| (i, title_similarity(&window.title, &candidate.title)))
+            .filter(|(_, score)| *score >= threshold)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((i, _)) = best {
+            taken[i] = true;
+            matches.push(((ws_idx, win_idx), i));
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::Window;
+
+    fn workspace(titles: &[&str]) -> Workspace {
+        Workspace {
+            name: "Work".to_string(),
+            windows: titles
+                .iter()
+                .map(|title| Window {
+                    title: title.to_string(),
+                    valid: true,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn binding(window_index: usize, title: &str) -> WorkspaceBindingSnapshot {
+        WorkspaceBindingSnapshot {
+            workspace_index: 0,
+            workspace_name: "Work".to_string(),
+            windows: vec![WindowBindingSnapshot {
+                window_index,
+                window_title: title.to_string(),
+                // Never a valid handle, so matched windows are marked invalid.
+                hwnd: 0,
+            }],
+        }
+    }
+
+    #[test]
+    fn exact_title_match_takes_priority() {
+        let mut workspaces = vec![workspace(&["Editor", "Terminal"])];
+        let stats = apply_window_bindings(
+            &mut workspaces,
+            &[binding(0, "Terminal")],
+            DEFAULT_FUZZY_THRESHOLD,
+        );
+        assert_eq!(stats.invalidated, 1);
+        assert_eq!(stats.fuzzy_matched, 0);
+        assert!(workspaces[0].windows[0].valid);
+        assert!(!workspaces[0].windows[1].valid);
+    }
+
+    #[test]
+    fn falls_back_to_saved_index() {
+        let mut workspaces = vec![workspace(&["Editor", "Terminal"])];
+        let stats = apply_window_bindings(
+            &mut workspaces,
+            &[binding(1, "Something Else")],
+            DEFAULT_FUZZY_THRESHOLD,
+        );
+        assert_eq!(stats.invalidated, 1);
+        assert_eq!(stats.unmatched, 0);
+        assert_eq!(stats.fuzzy_matched, 0);
+        assert!(!workspaces[0].windows[1].valid);
+    }
+
+    #[test]
+    fn fuzzy_match_when_index_is_gone() {
+        let mut workspaces = vec![workspace(&["Notes", "Document1.docx - Word"])];
+        let stats = apply_window_bindings(
+            &mut workspaces,
+            &[binding(5, "Document1 - Word")],
+            DEFAULT_FUZZY_THRESHOLD,
+        );
+        assert_eq!(stats.fuzzy_matched, 1);
+        assert_eq!(stats.invalidated, 1);
+        assert!(workspaces[0].windows[0].valid);
+        assert!(!workspaces[0].windows[1].valid);
+    }
+
+    #[test]
+    fn dissimilar_titles_stay_unmatched() {
+        let mut workspaces = vec![workspace(&["Calculator"])];
+        let stats = apply_window_bindings(
+            &mut workspaces,
+            &[binding(3, "Document1 - Word")],
+            DEFAULT_FUZZY_THRESHOLD,
+        );
+        assert_eq!(stats.unmatched, 1);
+        assert_eq!(stats.fuzzy_matched, 0);
+    }
+
+    #[test]
+    fn missing_windows_match_by_fingerprint_and_title() {
+        let fingerprint = |process: &str, class: &str| {
+            Some(WindowFingerprint {
+                process: process.to_string(),
+                class: class.to_string(),
+            })
+        };
+        let mut workspaces = vec![workspace(&["Notes - Notepad", "Inbox - Outlook"])];
+        workspaces[0].windows[0].fingerprint = fingerprint("notepad.exe", "Notepad");
+        let live = vec![
+            LiveWindow {
+                hwnd: 1,
+                title: "Notes - Notepad".to_string(),
+                fingerprint: fingerprint("wordpad.exe", "WordPadClass"),
+            },
+            LiveWindow {
+                hwnd: 2,
+                title: "Notes - Notepad".to_string(),
+                fingerprint: fingerprint("NOTEPAD.EXE", "Notepad"),
+            },
+            LiveWindow {
+                hwnd: 3,
+                title: "Calculator".to_string(),
+                fingerprint: None,
+            },
+        ];
+
+        let matches = match_missing_windows(
+            &workspaces,
+            &[(0, 0), (0, 1)],
+            &live,
+            DEFAULT_FUZZY_THRESHOLD,
+        );
+        assert_eq!(matches, vec![((0, 0), 1)]);
+    }
+
+    #[test]
+    fn live_windows_are_claimed_once() {
+        let workspaces = vec![workspace(&["Terminal", "Terminal"])];
+        let live = vec![LiveWindow {
+            hwnd: 7,
+            title: "Terminal".to_string(),
+            fingerprint: None,
+        }];
+        let matches = match_missing_windows(
+            &workspaces,
+            &[(0, 0), (0, 1)],
+            &live,
+            DEFAULT_FUZZY_THRESHOLD,
+        );
+        assert_eq!(matches, vec![((0, 0), 0)]);
+    }
+
+    #[test]
+    fn similarity_scores() {
+        assert_eq!(title_similarity("Word", "word"), 1.0);
+        assert!(title_similarity("Document1 - Word", "Document1.docx - Word") >= 0.6);
+        assert!(title_similarity("Calculator", "Document1 - Word") < 0.6);
+    }
+}
@@ -0,0 +1,215 @@
+use eframe::egui;
+use log::debug;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::Mutex;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    DeleteObject, GetDC, GetDIBits, GetObjectW, ReleaseDC, BITMAP, BITMAPINFO, BITMAPINFOHEADER,
+    BI_RGB, DIB_RGB_COLORS, HBITMAP,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetClassLongPtrW, GetIconInfo, LoadIconW, SendMessageTimeoutW, GCLP_HICON, GCLP_HICONSM, HICON,
+    ICONINFO, ICON_BIG, ICON_SMALL, ICON_SMALL2, IDI_APPLICATION, SMTO_ABORTIFHUNG, WM_GETICON,
+};
+
+/// How long to wait for a window to answer `WM_GETICON` before giving up on it.
+const ICON_TIMEOUT_MS: u32 = 100;
+
+/// Icons read when windows were captured, waiting to be turned into textures.
+static CAPTURED_ICONS: Lazy<Mutex<HashMap<isize, egui::ColorImage>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Textures already uploaded, by window handle, so icons are converted only once.
+static ICON_TEXTURES: Lazy<Mutex<HashMap<isize, egui::TextureHandle>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Reads the icon of `hwnd` now, replacing anything cached for that handle.
+///
+/// Called when a window is captured so the list shows the icon the window had
+/// at that moment.
+pub fn capture_window_icon(hwnd: HWND) {
+    let key = hwnd.0 as isize;
+    ICON_TEXTURES.lock().unwrap().remove(&key);
+    match window_icon_image(hwnd) {
+        Some(image) => {
+            CAPTURED_ICONS.lock().unwrap().insert(key, image);
+        }
+        None => {
+            CAPTURED_ICONS.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+/// Returns the texture showing the icon of `hwnd`, or the generic application
+/// icon if the window's own icon cannot be read.
+///
+/// Returns `None` only if not even the generic icon is available.
+pub fn window_icon_texture(ctx: &egui::Context, hwnd: HWND) -> Option<egui::TextureHandle> {
+    let key = hwnd.0 as isize;
+    if let Some(texture) = ICON_TEXTURES.lock().unwrap().get(&key) {
+        return Some(texture.clone());
+    }
+
+    let captured = CAPTURED_ICONS.lock().unwrap().remove(&key);
+    let image = captured
+        .or_else(|| window_icon_image(hwnd))
+        .or_else(generic_icon_image)?;
+    let texture = ctx.load_texture(
+        format!("window_icon_{}", key),
+        image,
+        egui::TextureOptions::LINEAR,
+    );
+    ICON_TEXTURES.lock().unwrap().insert(key, texture.clone());
+    Some(texture)
+}
+
+/// Reads the small icon of `hwnd`, preferring the one the window reports over
+/// its class icon.
+fn window_icon_image(hwnd: HWND) -> Option<egui::ColorImage> {
+    let icon = window_icon_handle(hwnd)?;
+    let image = unsafe { icon_to_image(icon) };
+    if image.is_none() {
+        debug!("Failed to convert the icon of window {:?}.", hwnd);
+    }
+    image
+}
+
+fn window_icon_handle(hwnd: HWND) -> Option<HICON> {
+    unsafe {
+        for kind in [ICON_SMALL2, ICON_SMALL, ICON_BIG] {
+            let mut result = 0usize;
+            let answered = SendMessageTimeoutW(
+                hwnd,
+                WM_GETICON,
+                WPARAM(kind as usize),
+                LPARAM(0),
+                SMTO_ABORTIFHUNG,
+                ICON_TIMEOUT_MS,
+                Some(&mut result),
+            );
+            if answered.0 != 0 && result != 0 {
+                return Some(HICON(result as *mut c_void));
+            }
+        }
+        [GCLP_HICONSM, GCLP_HICON]
+            .into_iter()
+            .map(|index| GetClassLongPtrW(hwnd, index))
+            .find(|&handle| handle != 0)
+            .map(|handle| HICON(handle as *mut c_void))
+    }
+}
+
+fn generic_icon_image() -> Option<egui::ColorImage> {
+    let icon = unsafe { LoadIconW(None, IDI_APPLICATION) }.ok()?;
+    unsafe { icon_to_image(icon) }
+}
+
+/// Copies the pixels of `icon` into an RGBA image.
+///
+/// Monochrome icons have no color bitmap and are not converted.
+unsafe fn icon_to_image(icon: HICON) -> Option<egui::ColorImage> {
+    let mut info = ICONINFO::default();
+    GetIconInfo(icon, &mut info).ok()?;
+    let image = if info.hbmColor.is_invalid() {
+        None
+    } else {
+        bitmap_pixels(info.hbmColor).map(|(size, color)| {
+            let mask = bitmap_pixels(info.hbmMask)
+                .filter(|(mask_size, _)| *mask_size == size)
+                .map(|(_, mask)| mask);
+            let rgba = bgra_to_rgba(&color, mask.as_deref());
+            egui::ColorImage::from_rgba_unmultiplied(size, &rgba)
+        })
+    };
+    if !info.hbmColor.is_invalid() {
+        let _ = DeleteObject(info.hbmColor);
+    }
+    let _ = DeleteObject(info.hbmMask);
+    image
+}
+
+/// Reads `bitmap` as top-down 32-bit BGRA pixels.
+unsafe fn bitmap_pixels(bitmap: HBITMAP) -> Option<([usize; 2], Vec<u8>)> {
+    let mut header = BITMAP::default();
+    if GetObjectW(
+        bitmap,
+        std::mem::size_of::<BITMAP>() as i32,
+        Some(&mut header as *mut BITMAP as *mut c_void),
+    ) == 0
+    {
+        return None;
+    }
+    let (width, height) = (header.bmWidth, header.bmHeight);
+    if width <= 0 || height <= 0 {
+        return None;
+    }
+
+    let mut info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+    let dc = GetDC(None);
+    let lines = GetDIBits(
+        dc,
+        bitmap,
+        0,
+        height as u32,
+        Some(pixels.as_mut_ptr() as *mut c_void),
+        &mut info,
+        DIB_RGB_COLORS,
+    );
+    ReleaseDC(None, dc);
+    (lines == height).then_some(([width as usize, height as usize], pixels))
+}
+
+/// Converts BGRA pixels to RGBA.
+///
+/// Icons without an alpha channel leave every alpha byte at zero; their
+/// transparency comes from `mask` instead, where set (white) pixels are
+/// transparent.
+fn bgra_to_rgba(bgra: &[u8], mask: Option<&[u8]>) -> Vec<u8> {
+    let has_alpha = bgra.chunks_exact(4).any(|pixel| pixel[3] != 0);
+    bgra.chunks_exact(4)
+        .enumerate()
+        .flat_map(|(i, pixel)| {
+            let alpha = if has_alpha {
+                pixel[3]
+            } else {
+                match mask.and_then(|mask| mask.get(i * 4)) {
+                    Some(&bits) if bits != 0 => 0,
+                    _ => 255,
+                }
+            };
+            [pixel[2], pixel[1], pixel[0], alpha]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bgra_is_swizzled_and_mask_gives_alpha() {
+        let bgra = [1, 2, 3, 128, 4, 5, 6, 255];
+        assert_eq!(bgra_to_rgba(&bgra, None), vec![3, 2, 1, 128, 6, 5, 4, 255]);
+
+        let opaque_less = [1, 2, 3, 0, 4, 5, 6, 0];
+        let mask = [255, 255, 255, 0, 0, 0, 0, 0];
+        assert_eq!(
+            bgra_to_rgba(&opaque_less, Some(&mask)),
+            vec![3, 2, 1, 0, 6, 5, 4, 255]
+        );
+    }
+}
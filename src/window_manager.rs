@@ -1,10 +1,27 @@
 use crate::gui::App;
+use crate::monitor::{
+    primary_work_area_center, rects_intersect, screen_to_workspace, virtual_screen_rect,
+    workspace_offset_for_rect, workspace_to_screen,
+};
+use crate::settings::{load_settings, save_settings, Settings};
 use crate::utils::{show_confirmation_box, show_message_box};
-use crate::workspace::Workspace;
+use crate::workspace::{Window, WindowFingerprint, WindowState, Workspace};
 use log::{debug, info, warn};
-use std::time::Instant;
-use windows::core::{Result, PCWSTR};
-use windows::Win32::Foundation::{BOOL, HWND, LPARAM, RECT};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use windows::core::{Result, PCWSTR, PWSTR};
+use windows::Win32::Foundation::{CloseHandle, BOOL, COLORREF, HWND, LPARAM, RECT};
+use windows::Win32::Graphics::Dwm::{
+    DwmGetWindowAttribute, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS, DWM_CLOAKED_APP,
+    DWM_CLOAKED_INHERITED,
+};
+use windows::Win32::System::Threading::{
+    AttachThreadInput, GetCurrentThreadId, OpenProcess, QueryFullProcessImageNameW,
+    PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+};
 use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
 use windows::Win32::UI::Input::KeyboardAndMouse::*;
 use windows::Win32::UI::WindowsAndMessaging::*;
@@ -70,7 +87,7 @@ pub fn is_hotkey_pressed(key_sequence: &str) -> bool {
 ///   - Otherwise, it attempts to move each valid window **back to its home** position.
 /// - Restores minimized windows using `move_window`, which internally
 ///   updates the window's restore coordinates and calls `ShowWindow`.
-/// - Activates each window (via `SetForegroundWindow`) after movement completes.
+/// - Activates each window (via [`focus_window`]) after movement completes.
 ///
 /// # Side Effects
 /// - Issues multiple Win32 API calls for restoring/minimizing, moving, and activating windows.
@@ -79,7 +96,7 @@ pub fn is_hotkey_pressed(key_sequence: &str) -> bool {
 /// # Example
 /// ```rust
 /// // If all windows are at home, move them to target; otherwise back to home.
-/// toggle_workspace_windows(&mut my_workspace);
+/// toggle_workspace_windows(&mut my_workspace, MoveAnimation::default(), false);
 /// ```
 ///
 /// # Notes
@@ -87,13 +104,17 @@ pub fn is_hotkey_pressed(key_sequence: &str) -> bool {
 /// - Invalid windows (where `IsWindow(hwnd)` returns false) are skipped and logged with a warning.
 /// - This function is often bound to a hotkey press and invoked by `check_hotkeys()`.
 pub fn are_all_windows_at_home(workspace: &Workspace) -> bool {
-    workspace.windows.iter().filter(|w| w.valid).all(|w| {
-        let hwnd = HWND(w.id as *mut std::ffi::c_void);
-        unsafe {
-            IsWindow(hwnd).as_bool()
-                && is_window_at_position(hwnd, w.home.0, w.home.1, w.home.2, w.home.3)
-        }
-    })
+    workspace
+        .windows
+        .iter()
+        .filter(|w| w.valid && w.enabled)
+        .all(|w| {
+            let hwnd = HWND(w.id as *mut std::ffi::c_void);
+            w.matches_live(hwnd) && {
+                let (x, y, width, height) = applied_rect(w, hwnd, w.home_rect());
+                is_window_at_placement(hwnd, x, y, width, height, w.home_state)
+            }
+        })
 }
 
 /// Toggles workspace windows between their home and target locations.
@@ -103,45 +124,118 @@ pub fn are_all_windows_at_home(workspace: &Workspace) -> bool {
 ///
 /// - If all windows are at their home positions, they are moved to their target positions.
 /// - If any window is not at its home or target position, it is moved to its home position.
+/// - Windows with `enabled` set to `false` are left untouched.
+/// - Positions captured with a monitor anchor are translated to that monitor's current
+///   work area (or the primary monitor if it is no longer connected) before moving.
+/// - Each window is restored to its captured minimized/maximized/normal state.
+/// - With `animation` enabled, windows glide to their destination one after another
+///   (see [`animate_window_with_state`]).
+/// - With `verify_moves`, each window placed in the normal state is re-checked and moved
+///   once more if it did not stay at its destination (see [`verify_window_position`]).
+/// - A workspace `activation_delay_ms` pauses between restoring a minimized window and
+///   positioning it.
+/// - Afterwards the windows are stacked by their `z_order` (0 in front) and only the
+///   frontmost non-minimized window is activated.
+/// - Windows whose handle has been reused by a different window (see
+///   [`Window::matches_live`]) are marked invalid and skipped.
+/// - Windows with `topmost` set are pinned above all normal windows after being
+///   positioned; the others are unpinned. Windows with an `opacity` below 255 are
+///   made translucent.
+/// - With the workspace's `steal_focus` unset, windows are moved without being
+///   activated and stacked behind the current foreground window.
+/// - With the workspace's `strict_toggle` set, nothing is moved if any enabled window
+///   no longer exists; a warning is logged and shown instead.
 ///
 /// # Example
 /// ```
-/// toggle_workspace_windows(&mut workspace);
+/// toggle_workspace_windows(&mut workspace, MoveAnimation::default(), false);
 /// ```
-pub fn toggle_workspace_windows(workspace: &mut Workspace) {
-    if workspace.rotate && workspace.windows.len() > 1 {
-        let len = workspace.windows.len();
+pub fn toggle_workspace_windows(
+    workspace: &mut Workspace,
+    animation: MoveAnimation,
+    verify_moves: bool,
+) {
+    invalidate_reused_handles(workspace);
+
+    if workspace.strict_toggle {
+        let missing = missing_enabled_windows(workspace);
+        if !missing.is_empty() {
+            let message = format!(
+                "Workspace '{}' was not toggled because {} window{} no longer exist{}: {}",
+                workspace.name,
+                missing.len(),
+                if missing.len() == 1 { "" } else { "s" },
+                if missing.len() == 1 { "s" } else { "" },
+                missing.join(", ")
+            );
+            warn!("{}", message);
+            // Keep the hotkey loop responsive while the message is shown.
+            std::thread::spawn(move || show_message_box(&message, "Workspace Not Toggled"));
+            return;
+        }
+    }
+
+    let steal_focus = workspace.steal_focus;
+    let enabled_indices: Vec<usize> = workspace
+        .windows
+        .iter()
+        .enumerate()
+        .filter(|(_, window)| window.enabled)
+        .map(|(i, _)| i)
+        .collect();
+
+    if workspace.rotate && enabled_indices.len() > 1 {
+        let len = enabled_indices.len();
         let offset = workspace.rotation_offset % len;
 
-        let primary_rect = workspace
-            .windows
-            .first()
-            .map(|window| window.target)
-            .unwrap_or((0, 0, 800, 600));
+        let primary = &workspace.windows[enabled_indices[0]];
+        let primary_slot = (primary.target_rect(), primary.target_state);
 
-        let slot_rects: Vec<(i32, i32, i32, i32)> =
-            workspace.windows.iter().map(|window| window.home).collect();
+        let slots: Vec<((i32, i32, i32, i32), WindowState)> = enabled_indices
+            .iter()
+            .map(|&i| {
+                let window = &workspace.windows[i];
+                (window.home_rect(), window.home_state)
+            })
+            .collect();
 
         for slot_index in 0..len {
-            let window_index = (slot_index + offset) % len;
+            let window_index = enabled_indices[(slot_index + offset) % len];
 
             if let Some(window) = workspace.windows.get(window_index) {
                 let hwnd = HWND(window.id as *mut std::ffi::c_void);
 
-                unsafe {
-                    if !IsWindow(hwnd).as_bool() {
-                        warn!("Skipping invalid window '{}'.", window.title);
-                        continue;
-                    }
+                if !window.matches_live(hwnd) {
+                    warn!("Skipping invalid window '{}'.", window.title);
+                    continue;
                 }
 
-                let rect = if slot_index == 0 {
-                    primary_rect
+                move_to_assigned_desktop(window, hwnd);
+
+                if rotation_slot_action(slot_index, workspace.minimize_others)
+                    == SlotAction::Minimize
+                {
+                    // Never activate the next window: that would take the
+                    // focus from the window rotated into the target slot.
+                    minimize_window(hwnd, false);
+                    info!(
+                        "Minimized window '{}' outside the target slot.",
+                        window.title
+                    );
+                    continue;
+                }
+
+                let (rect, state) = if slot_index == 0 {
+                    primary_slot
                 } else {
-                    slot_rects.get(slot_index).copied().unwrap_or(primary_rect)
+                    slots.get(slot_index).copied().unwrap_or(primary_slot)
                 };
 
-                if let Err(e) = move_window(hwnd, rect.0, rect.1, rect.2, rect.3) {
+                let rect = applied_rect(window, hwnd, rect);
+                restore_before_move(hwnd, state, workspace.activation_delay_ms, steal_focus);
+
+                if let Err(e) = animate_window_with_state(hwnd, rect, state, animation, steal_focus)
+                {
                     warn!("Failed to move window '{}': {}", window.title, e);
                 } else {
                     info!(
@@ -150,45 +244,50 @@ pub fn toggle_workspace_windows(workspace: &mut Workspace) {
                     );
                 }
 
-                if slot_index == 0 {
-                    unsafe {
-                        if SetForegroundWindow(hwnd).as_bool() {
-                            info!("Activated window '{}'", window.title);
-                        } else {
-                            warn!("Failed to activate window '{}'", window.title);
-                        }
+                if verify_moves {
+                    verify_window_position(window, hwnd, rect, state);
+                }
+                apply_topmost(window, hwnd);
+                apply_opacity(window, hwnd);
+
+                if slot_index == 0 && steal_focus {
+                    if focus_window(hwnd) {
+                        info!("Activated window '{}'", window.title);
+                    } else {
+                        warn!("Failed to activate window '{}'", window.title);
                     }
                 }
             }
         }
-        workspace.rotation_offset = (workspace.rotation_offset + 1) % len;
+        workspace.rotation_offset =
+            next_rotation_offset(offset, len, workspace.rotate_reverse, workspace.rotate_wrap);
     } else {
         let all_at_home = are_all_windows_at_home(workspace);
         debug!("all_at_home={}", all_at_home);
 
-        for window in &workspace.windows {
+        let mut moved = Vec::new();
+        for window in workspace.windows.iter().filter(|w| w.enabled) {
             let hwnd = HWND(window.id as *mut std::ffi::c_void);
 
-            unsafe {
-                if !IsWindow(hwnd).as_bool() {
-                    warn!("Skipping invalid window '{}'.", window.title);
-                    continue;
-                }
+            if !window.matches_live(hwnd) {
+                warn!("Skipping invalid window '{}'.", window.title);
+                continue;
             }
 
-            let target_position = if all_at_home {
-                window.target
+            move_to_assigned_desktop(window, hwnd);
+
+            let (target_position, state) = if all_at_home {
+                (window.target_rect(), window.target_state)
             } else {
-                window.home
+                (window.home_rect(), window.home_state)
             };
 
-            if let Err(e) = move_window(
-                hwnd,
-                target_position.0,
-                target_position.1,
-                target_position.2,
-                target_position.3,
-            ) {
+            let target_position = applied_rect(window, hwnd, target_position);
+            restore_before_move(hwnd, state, workspace.activation_delay_ms, steal_focus);
+
+            if let Err(e) =
+                animate_window_with_state(hwnd, target_position, state, animation, steal_focus)
+            {
                 warn!("Failed to move window '{}': {}", window.title, e);
             } else {
                 info!(
@@ -197,14 +296,504 @@ pub fn toggle_workspace_windows(workspace: &mut Workspace) {
                 );
             }
 
-            unsafe {
-                if SetForegroundWindow(hwnd).as_bool() {
-                    info!("Activated window '{}'", window.title);
-                } else {
-                    warn!("Failed to activate window '{}'", window.title);
-                }
+            if verify_moves {
+                verify_window_position(window, hwnd, target_position, state);
+            }
+
+            moved.push((window, state));
+        }
+
+        apply_stacking_order(&moved, steal_focus);
+        // Restacking behind a normal window clears the topmost flag, so pin last.
+        for (window, _) in &moved {
+            let hwnd = HWND(window.id as *mut std::ffi::c_void);
+            apply_topmost(window, hwnd);
+            apply_opacity(window, hwnd);
+        }
+    }
+}
+
+/// What the rotate branch of [`toggle_workspace_windows`] does with a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotAction {
+    /// Move the window into its slot.
+    Move,
+    /// Minimize the window instead of giving it a slot.
+    Minimize,
+}
+
+/// Picks what happens to the window rotated into `slot_index`; slot 0 is the
+/// target slot and always gets a window.
+pub fn rotation_slot_action(slot_index: usize, minimize_others: bool) -> SlotAction {
+    if minimize_others && slot_index != 0 {
+        SlotAction::Minimize
+    } else {
+        SlotAction::Move
+    }
+}
+
+/// Minimizes `hwnd`, letting Windows activate the next window only if `activate` is set.
+fn minimize_window(hwnd: HWND, activate: bool) {
+    let command = if activate {
+        SW_MINIMIZE
+    } else {
+        SW_SHOWMINNOACTIVE
+    };
+    unsafe {
+        let _ = ShowWindow(hwnd, command);
+    }
+}
+
+/// Windows pinned always-on-top by [`apply_topmost`].
+static PINNED_WINDOWS: Mutex<Vec<isize>> = Mutex::new(Vec::new());
+
+/// Pins `hwnd` above all normal windows if `window.topmost` is set, or unpins
+/// it if this function pinned it earlier, without moving, resizing or
+/// activating it. Windows the user or the application made always-on-top
+/// themselves are left pinned.
+fn apply_topmost(window: &Window, hwnd: HWND) {
+    let key = hwnd.0 as isize;
+    let mut pinned = PINNED_WINDOWS.lock().unwrap();
+    let insert_after = if window.topmost {
+        if !pinned.contains(&key) {
+            pinned.push(key);
+        }
+        HWND_TOPMOST
+    } else if let Some(index) = pinned.iter().position(|&h| h == key) {
+        pinned.remove(index);
+        HWND_NOTOPMOST
+    } else {
+        return;
+    };
+    unsafe {
+        if let Err(e) = SetWindowPos(
+            hwnd,
+            insert_after,
+            0,
+            0,
+            0,
+            0,
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        ) {
+            warn!(
+                "Failed to update always-on-top for window '{}': {}",
+                window.title, e
+            );
+        }
+    }
+}
+
+/// Windows made translucent by [`apply_opacity`], with whether it added
+/// `WS_EX_LAYERED` to them.
+static TRANSLUCENT_WINDOWS: Mutex<Vec<(isize, bool)>> = Mutex::new(Vec::new());
+
+/// Applies `window.opacity` to `hwnd` through the layered window style.
+///
+/// Fully opaque windows are left alone unless this function made them
+/// translucent earlier, so windows that draw their own transparency are not
+/// disturbed; those it did change get their full opacity back, and lose
+/// `WS_EX_LAYERED` again if it was added here. Some windows, e.g. those drawn
+/// with DirectX or owned by an elevated process, may reject or ignore the
+/// change.
+fn apply_opacity(window: &Window, hwnd: HWND) {
+    let key = hwnd.0 as isize;
+    let mut translucent = TRANSLUCENT_WINDOWS.lock().unwrap();
+    let tracked = translucent.iter().position(|&(h, _)| h == key);
+    if window.opacity == u8::MAX {
+        let Some(index) = tracked else {
+            return;
+        };
+        let (_, added_layered) = translucent.remove(index);
+        unsafe {
+            if added_layered {
+                let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+                SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style & !(WS_EX_LAYERED.0 as i32));
+            } else if let Err(e) = SetLayeredWindowAttributes(hwnd, COLORREF(0), u8::MAX, LWA_ALPHA)
+            {
+                warn!("Window '{}' rejected full opacity: {}", window.title, e);
+            }
+        }
+        return;
+    }
+    unsafe {
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+        let added_layered = ex_style as u32 & WS_EX_LAYERED.0 == 0;
+        if added_layered {
+            SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as i32);
+        }
+        if tracked.is_none() {
+            translucent.push((key, added_layered));
+        }
+        if let Err(e) = SetLayeredWindowAttributes(hwnd, COLORREF(0), window.opacity, LWA_ALPHA) {
+            warn!(
+                "Window '{}' rejected opacity {}: {}",
+                window.title, window.opacity, e
+            );
+        }
+    }
+}
+
+/// Returns indices into `windows` ordered front to back by `z_order`.
+///
+/// Windows sharing a `z_order` keep the order they were activated in before
+/// stacking was configurable: the later window in the list ends up in front.
+fn front_to_back(windows: &[&Window]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..windows.len()).rev().collect();
+    order.sort_by_key(|&i| windows[i].z_order);
+    order
+}
+
+/// Stacks `windows` by their `z_order` and, with `focus` set, focuses the
+/// frontmost one that is not minimized.
+///
+/// Each window is inserted directly behind the previous one, so the relative
+/// order is exact regardless of which windows were on top beforehand. Without
+/// `focus` the stack goes behind the foreground window, so whatever the user is
+/// working in stays in front.
+fn apply_stacking_order(windows: &[(&Window, WindowState)], focus: bool) {
+    let stack: Vec<&Window> = windows.iter().map(|(window, _)| *window).collect();
+    let order = front_to_back(&stack);
+
+    let foreground = unsafe { GetForegroundWindow() };
+    let mut insert_after = if focus
+        || foreground.is_invalid()
+        || stack
+            .iter()
+            .any(|window| window.id == foreground.0 as usize)
+    {
+        HWND_TOP
+    } else {
+        foreground
+    };
+    for &i in &order {
+        let hwnd = HWND(stack[i].id as *mut std::ffi::c_void);
+        unsafe {
+            if let Err(e) = SetWindowPos(
+                hwnd,
+                insert_after,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            ) {
+                warn!("Failed to restack window '{}': {}", stack[i].title, e);
+            }
+        }
+        insert_after = hwnd;
+    }
+
+    if !focus {
+        return;
+    }
+    let front = order
+        .into_iter()
+        .find(|&i| windows[i].1 != WindowState::Minimized);
+    if let Some(window) = front.map(|i| stack[i]) {
+        if focus_window(HWND(window.id as *mut std::ffi::c_void)) {
+            info!("Activated window '{}'", window.title);
+        } else {
+            warn!("Failed to activate window '{}'", window.title);
+        }
+    }
+}
+
+/// Number of times [`focus_window`] retries the thread-input workaround.
+const FOCUS_RETRIES: u32 = 3;
+/// Pause between [`focus_window`] retries.
+const FOCUS_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Brings `hwnd` to the foreground, working around the foreground lock that
+/// makes a plain `SetForegroundWindow` fail when another process has focus.
+///
+/// If the direct call fails, the current thread temporarily attaches its input
+/// to the foreground window's thread, which lets `BringWindowToTop` and
+/// `SetForegroundWindow` succeed, then detaches again. This is retried a few
+/// times with a short pause before giving up.
+///
+/// Returns `true` if `hwnd` ended up as the foreground window.
+pub fn focus_window(hwnd: HWND) -> bool {
+    unsafe {
+        if SetForegroundWindow(hwnd).as_bool() {
+            return true;
+        }
+
+        let current_thread = GetCurrentThreadId();
+        for attempt in 1..=FOCUS_RETRIES {
+            let foreground_thread = GetWindowThreadProcessId(GetForegroundWindow(), None);
+            let attached = foreground_thread != 0
+                && foreground_thread != current_thread
+                && AttachThreadInput(current_thread, foreground_thread, true).as_bool();
+
+            let _ = BringWindowToTop(hwnd);
+            let _ = SetForegroundWindow(hwnd);
+
+            if attached {
+                let _ = AttachThreadInput(current_thread, foreground_thread, false);
             }
+
+            if GetForegroundWindow() == hwnd {
+                info!(
+                    "Focused window {:?} after {} fallback attempt(s).",
+                    hwnd, attempt
+                );
+                return true;
+            }
+            std::thread::sleep(FOCUS_RETRY_DELAY);
         }
+
+        warn!(
+            "Fallback focus failed for window {:?} after {} attempts.",
+            hwnd, FOCUS_RETRIES
+        );
+        false
+    }
+}
+
+/// Returns the rotation offset to use on the toggle after one that used `offset`
+/// with `len` windows.
+///
+/// Forward rotation counts up and reverse rotation counts down, so both visit
+/// every arrangement starting from offset 0. With `wrap` the offset starts over
+/// after the last arrangement; without it, it stays there (`len - 1` forwards,
+/// `1` in reverse).
+pub fn next_rotation_offset(offset: usize, len: usize, reverse: bool, wrap: bool) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    match (reverse, wrap) {
+        (false, true) => (offset + 1) % len,
+        (false, false) => (offset + 1).min(len - 1),
+        (true, true) => (offset + len - 1) % len,
+        (true, false) => match offset {
+            0 => len - 1,
+            1 => 1,
+            offset => offset - 1,
+        },
+    }
+}
+
+/// Restores `hwnd` if it is minimized and brings it to the foreground with
+/// [`focus_window`], without changing its position.
+pub fn activate_window(hwnd: HWND) -> bool {
+    unsafe {
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+    }
+    focus_window(hwnd)
+}
+
+/// Returns each of `hwnds`' position in the current desktop Z-order, front
+/// first, or `None` for handles that are not top-level windows.
+pub fn current_stacking_positions(hwnds: &[HWND]) -> Vec<Option<usize>> {
+    let mut positions = vec![None; hwnds.len()];
+    unsafe {
+        let mut position = 0;
+        let mut current = GetTopWindow(None).ok();
+        while let Some(hwnd) = current {
+            if let Some(index) = hwnds.iter().position(|&candidate| candidate == hwnd) {
+                positions[index] = Some(position);
+            }
+            position += 1;
+            current = GetWindow(hwnd, GW_HWNDNEXT).ok();
+        }
+    }
+    positions
+}
+
+/// How far, in pixels, a verified window may sit from its destination before
+/// [`verify_window_position`] moves it again.
+const VERIFY_TOLERANCE: i32 = 2;
+
+/// Returns `true` if every edge of `actual` is within `tolerance` pixels of `expected`.
+fn rect_within_tolerance(
+    actual: (i32, i32, i32, i32),
+    expected: (i32, i32, i32, i32),
+    tolerance: i32,
+) -> bool {
+    (actual.0 - expected.0).abs() <= tolerance
+        && (actual.1 - expected.1).abs() <= tolerance
+        && (actual.2 - expected.2).abs() <= tolerance
+        && (actual.3 - expected.3).abs() <= tolerance
+}
+
+/// Re-reads the position of a window just moved to `rect` and, if it drifted
+/// (e.g. an app that maximizes itself on restore), clears the maximized state
+/// and issues one more `SetWindowPos`.
+///
+/// Only windows placed in the [`WindowState::Normal`] state are checked, since
+/// maximized and minimized windows intentionally do not occupy `rect`.
+fn verify_window_position(
+    window: &Window,
+    hwnd: HWND,
+    rect: (i32, i32, i32, i32),
+    state: WindowState,
+) {
+    if state != WindowState::Normal {
+        return;
+    }
+    let Ok(actual) = get_window_position(hwnd) else {
+        return;
+    };
+    let zoomed = unsafe { IsZoomed(hwnd).as_bool() };
+    if !zoomed && rect_within_tolerance(actual, rect, VERIFY_TOLERANCE) {
+        return;
+    }
+
+    info!(
+        "Window '{}' drifted to {:?} instead of {:?}; moving it again.",
+        window.title, actual, rect
+    );
+    // The first move already activated the window if it was meant to be.
+    unsafe {
+        if zoomed {
+            let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+        }
+        let (x, y, w, h) = expand_by_margins(rect, invisible_frame_margins(hwnd));
+        if let Err(e) = SetWindowPos(hwnd, HWND_TOP, x, y, w, h, SWP_NOZORDER | SWP_NOACTIVATE) {
+            warn!(
+                "Failed to correct position of window '{}': {}",
+                window.title, e
+            );
+        }
+    }
+}
+
+/// Returns `rect` with the position or size `window` does not apply replaced
+/// by the live window's current placement (see [`Window::mask_rect`]).
+///
+/// Working on the rectangle rather than `SWP_NOMOVE`/`SWP_NOSIZE` flags keeps
+/// animations, minimized restore rectangles and the at-home check consistent.
+pub fn applied_rect(
+    window: &Window,
+    hwnd: HWND,
+    rect: (i32, i32, i32, i32),
+) -> (i32, i32, i32, i32) {
+    if window.apply_position && window.apply_size {
+        return rect;
+    }
+    match capture_window_placement(hwnd) {
+        Ok((current, _)) => window.mask_rect(rect, current),
+        Err(_) => rect,
+    }
+}
+
+/// Restores a minimized `hwnd` that is about to be shown in `state`, then waits
+/// `delay_ms` so the following move is not lost while the window reopens.
+///
+/// Does nothing when `delay_ms` is 0; the move itself restores the window then.
+fn restore_before_move(hwnd: HWND, state: WindowState, delay_ms: u32, activate: bool) {
+    if delay_ms == 0 || state == WindowState::Minimized {
+        return;
+    }
+    unsafe {
+        if !IsIconic(hwnd).as_bool() {
+            return;
+        }
+        let _ = ShowWindow(hwnd, restore_command(activate));
+    }
+    std::thread::sleep(Duration::from_millis(u64::from(delay_ms)));
+}
+
+/// Returns the labels of the enabled windows of `workspace` whose handle no
+/// longer refers to a window.
+pub fn missing_enabled_windows(workspace: &Workspace) -> Vec<String> {
+    workspace
+        .windows
+        .iter()
+        .filter(|window| window.enabled)
+        .filter(|window| !window.matches_live(HWND(window.id as *mut std::ffi::c_void)))
+        .map(|window| window.display_label())
+        .collect()
+}
+
+/// Outcome of [`gather_workspace_windows`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GatherSummary {
+    pub moved: usize,
+    pub already_here: usize,
+    pub failed: usize,
+}
+
+/// Moves every live window of `workspace` onto the current virtual desktop,
+/// leaving windows that are already there alone.
+///
+/// Returns an error only if the current desktop cannot be determined.
+pub fn gather_workspace_windows(
+    workspace: &Workspace,
+) -> std::result::Result<GatherSummary, String> {
+    let current = virtual_desktop::get_current_desktop()
+        .map_err(|e| format!("Failed to determine the current desktop: {:?}", e))?;
+    let current_index = current.get_index().unwrap_or(0);
+
+    let mut summary = GatherSummary::default();
+    for window in &workspace.windows {
+        let hwnd = HWND(window.id as *mut std::ffi::c_void);
+        if !unsafe { IsWindow(hwnd).as_bool() } {
+            continue;
+        }
+        let desktop = virtual_desktop::get_desktop_by_window(hwnd).and_then(|d| d.get_index());
+        if matches!(desktop, Ok(index) if index == current_index) {
+            summary.already_here += 1;
+            continue;
+        }
+        match virtual_desktop::move_window_to_desktop(hwnd, &current) {
+            Ok(()) => {
+                info!(
+                    "Moved window '{}' to the current desktop {}.",
+                    window.title,
+                    current_index + 1
+                );
+                summary.moved += 1;
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to move window '{}' to the current desktop: {:?}",
+                    window.title, e
+                );
+                summary.failed += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Moves `window` onto its assigned virtual desktop, if it has one.
+///
+/// A stored `desktop_index` that no longer refers to a live desktop is logged
+/// and skipped so the window is still repositioned on its current desktop.
+fn move_to_assigned_desktop(window: &Window, hwnd: HWND) {
+    let Some(index) = window.desktop_index else {
+        return;
+    };
+
+    let desktops = match virtual_desktop::get_desktops() {
+        Ok(desktops) => desktops,
+        Err(e) => {
+            warn!("Failed to enumerate desktops: {:?}", e);
+            return;
+        }
+    };
+
+    match desktops.get(index as usize) {
+        Some(desktop) => {
+            if let Err(e) = virtual_desktop::move_window_to_desktop(hwnd, desktop) {
+                warn!(
+                    "Failed to move window '{}' to desktop {}: {:?}",
+                    window.title,
+                    index + 1,
+                    e
+                );
+            }
+        }
+        None => warn!(
+            "Window '{}' is assigned to desktop {}, but only {} desktop{} available; skipping desktop move.",
+            window.title,
+            index + 1,
+            desktops.len(),
+            if desktops.len() == 1 { "" } else { "s" }
+        ),
     }
 }
 
@@ -213,12 +802,13 @@ pub fn toggle_workspace_windows(workspace: &mut Workspace) {
 /// # Behavior
 /// - Restores minimized windows automatically when moved.
 /// - Uses [`move_window`](fn.move_window.html) to reposition each window.
+/// - Skips windows whose `enabled` flag is cleared.
 /// - Attempts to activate each window after it has been moved.
 ///
 /// # Parameters
 /// - `workspace`: The workspace whose windows should be returned home.
 pub fn send_workspace_windows_home(workspace: &Workspace) {
-    for window in &workspace.windows {
+    for window in workspace.windows.iter().filter(|w| w.enabled) {
         let hwnd = HWND(window.id as *mut std::ffi::c_void);
 
         unsafe {
@@ -231,27 +821,22 @@ pub fn send_workspace_windows_home(workspace: &Workspace) {
             }
         }
 
-        if let Err(e) = move_window(
-            hwnd,
-            window.home.0,
-            window.home.1,
-            window.home.2,
-            window.home.3,
-        ) {
+        let home = applied_rect(window, hwnd, window.home_rect());
+        if let Err(e) =
+            move_window_with_state(hwnd, home.0, home.1, home.2, home.3, window.home_state)
+        {
             warn!("Failed to move window '{}': {}", window.title, e);
         } else {
             info!(
                 "Moved window '{}' to home position: {:?}",
-                window.title, window.home
+                window.title, home
             );
         }
 
-        unsafe {
-            if SetForegroundWindow(hwnd).as_bool() {
-                info!("Activated window '{}'", window.title);
-            } else {
-                warn!("Failed to activate window '{}'", window.title);
-            }
+        if focus_window(hwnd) {
+            info!("Activated window '{}'", window.title);
+        } else {
+            warn!("Failed to activate window '{}'", window.title);
         }
     }
 }
@@ -268,116 +853,505 @@ pub fn send_all_windows_home(workspaces: &[Workspace]) {
     }
 }
 
+/// Returns where a window at `rect` should be moved so it can be seen again, or
+/// `None` if it still overlaps `screen`, the virtual screen.
+///
+/// Stranded windows keep their size and are centered on `primary_center`.
+pub fn rescued_rect(
+    rect: (i32, i32, i32, i32),
+    screen: (i32, i32, i32, i32),
+    primary_center: (i32, i32),
+) -> Option<(i32, i32, i32, i32)> {
+    if rects_intersect(rect, screen) {
+        return None;
+    }
+    Some(centered_rect(primary_center, (rect.2, rect.3)))
+}
+
+/// Moves every workspace window that lies entirely outside the virtual screen
+/// to the center of the primary monitor, keeping its size.
+///
+/// Minimized windows and windows whose handle no longer refers to the captured
+/// window are left alone. Returns the number of windows that were moved.
+pub fn rescue_offscreen_windows(workspaces: &[Workspace]) -> usize {
+    let screen = virtual_screen_rect();
+    let center = primary_work_area_center();
+    let mut seen = std::collections::HashSet::new();
+    let mut rescued = 0;
+    for window in workspaces.iter().flat_map(|ws| ws.windows.iter()) {
+        let hwnd = HWND(window.id as *mut std::ffi::c_void);
+        if !seen.insert(window.id) || !window.matches_live(hwnd) {
+            continue;
+        }
+        if unsafe { IsIconic(hwnd) }.as_bool() {
+            continue;
+        }
+        let Ok(rect) = get_window_position(hwnd) else {
+            continue;
+        };
+        let Some((x, y, w, h)) = rescued_rect(rect, screen, center) else {
+            continue;
+        };
+        match move_window(hwnd, x, y, w, h) {
+            Ok(()) => {
+                info!(
+                    "Rescued off-screen window '{}' from {:?} to ({}, {}).",
+                    window.title, rect, x, y
+                );
+                rescued += 1;
+            }
+            Err(e) => warn!("Failed to rescue window '{}': {}", window.title, e),
+        }
+    }
+    rescued
+}
+
 use crate::desktop_window_info::DesktopWindowInfo;
 use crate::virtual_desktop;
 use serde_json;
 use std::fs::File;
 use std::io::Write;
 
+/// Returns `true` if `hwnd` is a visible, titled application window.
+///
+/// Rejects hidden windows, tool windows (`WS_EX_TOOLWINDOW`), windows without a
+/// title, and windows an app cloaked itself, such as suspended UWP apps.
+/// Windows the shell cloaks because they sit on another virtual desktop
+/// (`DWM_CLOAKED_SHELL`) are kept, so "Save All Desktops" sees every desktop.
+pub fn is_real_app_window(hwnd: HWND) -> bool {
+    unsafe {
+        if !IsWindow(hwnd).as_bool() || !IsWindowVisible(hwnd).as_bool() {
+            return false;
+        }
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+        if ex_style & WS_EX_TOOLWINDOW.0 != 0 {
+            return false;
+        }
+        if GetWindowTextLengthW(hwnd) == 0 {
+            return false;
+        }
+        let mut cloaked: u32 = 0;
+        let cloaked_check = DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_CLOAKED,
+            &mut cloaked as *mut u32 as *mut std::ffi::c_void,
+            std::mem::size_of::<u32>() as u32,
+        );
+        cloaked_check.is_err() || cloaked & (DWM_CLOAKED_APP | DWM_CLOAKED_INHERITED) == 0
+    }
+}
+
+/// Progress of a desktop capture or restore, shared with the thread running it.
+///
+/// The worker sets the total once it knows how many windows it will process
+/// and advances the count after each one. Setting the cancel flag makes it
+/// stop before the next window.
+#[derive(Debug, Default)]
+pub struct DesktopProgress {
+    processed: AtomicUsize,
+    total: AtomicUsize,
+    cancelled: AtomicBool,
+}
+
+impl DesktopProgress {
+    /// Returns `(processed, total)`.
+    pub fn counts(&self) -> (usize, usize) {
+        (
+            self.processed.load(Ordering::SeqCst),
+            self.total.load(Ordering::SeqCst),
+        )
+    }
+
+    /// Asks the worker to stop before the next window.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn set_total(&self, total: usize) {
+        self.total.store(total, Ordering::SeqCst);
+    }
+
+    fn advance(&self) {
+        self.processed.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Chooses which windows "Save All Desktops" records.
+///
+/// An entry matches a window whose title contains it, ignoring case, or whose
+/// executable has that name (see [`is_excluded_process`]). With no include
+/// entries every window is a candidate; exclude entries win over include ones.
+#[derive(Debug, Default, Clone)]
+pub struct CaptureFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl CaptureFilter {
+    pub fn from_settings(settings: &Settings) -> Self {
+        CaptureFilter {
+            include: settings.desktop_capture_include.clone(),
+            exclude: settings.desktop_capture_exclude.clone(),
+        }
+    }
+
+    /// Returns `true` if the filter lets every window through.
+    pub fn is_empty(&self) -> bool {
+        self.include
+            .iter()
+            .chain(&self.exclude)
+            .all(|entry| entry.trim().is_empty())
+    }
+
+    /// Returns `true` if a window titled `title` owned by the executable at
+    /// `process_path` should be captured.
+    pub fn allows(&self, title: &str, process_path: &str) -> bool {
+        let title = title.to_lowercase();
+        let matches = |entries: &[String]| {
+            entries.iter().any(|entry| {
+                let needle = entry.trim();
+                !needle.is_empty()
+                    && (title.contains(&needle.to_lowercase())
+                        || is_excluded_process(process_path, std::slice::from_ref(entry)))
+            })
+        };
+        let included =
+            self.include.iter().all(|entry| entry.trim().is_empty()) || matches(&self.include);
+        included && !matches(&self.exclude)
+    }
+}
+
 /// Capture window positions for all desktops and store them as JSON.
+///
+/// Only windows allowed by `filter` are recorded. Returns the number of
+/// windows saved. If `progress` is cancelled the file is
+/// left untouched and an error is returned.
 #[cfg(target_os = "windows")]
-pub fn capture_all_desktops(file: &str) {
-    let mut infos: Vec<DesktopWindowInfo> = Vec::new();
+pub fn capture_all_desktops(
+    file: &str,
+    filter: &CaptureFilter,
+    progress: &DesktopProgress,
+) -> std::result::Result<usize, String> {
+    let mut candidates: Vec<isize> = Vec::new();
     unsafe {
         let _ = EnumWindows(
             Some(enum_capture_proc),
-            LPARAM(&mut infos as *mut _ as isize),
+            LPARAM(&mut candidates as *mut _ as isize),
         );
     }
-    if let Ok(json) = serde_json::to_string_pretty(&infos) {
-        if let Err(e) = File::create(file).and_then(|mut f| f.write_all(json.as_bytes())) {
-            warn!("Failed to save desktop data: {}", e);
-        } else {
-            info!("Saved desktop layout to {}", file);
+    progress.set_total(candidates.len());
+
+    let mut infos: Vec<DesktopWindowInfo> = Vec::new();
+    for hwnd in candidates {
+        if progress.is_cancelled() {
+            info!("Desktop capture cancelled.");
+            return Err("Capture cancelled; the layout file was not changed.".to_string());
         }
+        let hwnd = HWND(hwnd as *mut _);
+        if let Some(info) = capture_desktop_window(hwnd) {
+            let process = process_path_for_window(hwnd).unwrap_or_default();
+            if filter.is_empty() || filter.allows(&info.title, &process) {
+                infos.push(info);
+            } else {
+                debug!(
+                    "Not capturing '{}' ({}) due to filters",
+                    info.title, process
+                );
+            }
+        }
+        progress.advance();
     }
+
+    let json = serde_json::to_string_pretty(&infos)
+        .map_err(|e| format!("Failed to serialize desktop data: {}", e))?;
+    File::create(file)
+        .and_then(|mut f| f.write_all(json.as_bytes()))
+        .map_err(|e| format!("Failed to save desktop data: {}", e))?;
+    info!("Saved desktop layout to {}", file);
+    Ok(infos.len())
 }
 
 #[cfg(target_os = "windows")]
 unsafe extern "system" fn enum_capture_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
-    if !IsWindow(hwnd).as_bool() || !IsWindowVisible(hwnd).as_bool() {
-        return BOOL(1);
+    if is_real_app_window(hwnd) {
+        let list = &mut *(lparam.0 as *mut Vec<isize>);
+        list.push(hwnd.0 as isize);
     }
-    let list = &mut *(lparam.0 as *mut Vec<DesktopWindowInfo>);
-    if let Ok(desktop) = virtual_desktop::get_desktop_by_window(hwnd) {
-        if let Ok(index) = desktop.get_index() {
-            let mut buffer = [0u16; 256];
-            let len = GetWindowTextW(hwnd, &mut buffer);
-            let title = String::from_utf16_lossy(&buffer[..len as usize]);
-            if let Ok((x, y, w, h)) = get_window_position(hwnd) {
-                list.push(DesktopWindowInfo {
-                    desktop_index: index,
-                    hwnd: hwnd.0 as isize,
-                    title,
-                    rect: (x, y, w, h),
-                });
+    BOOL(1)
+}
+
+/// Reads the desktop, title and position of `hwnd` for a desktop layout file.
+#[cfg(target_os = "windows")]
+fn capture_desktop_window(hwnd: HWND) -> Option<DesktopWindowInfo> {
+    let index = virtual_desktop::get_desktop_by_window(hwnd)
+        .and_then(|desktop| desktop.get_index())
+        .ok()?;
+    let mut buffer = [0u16; 256];
+    let len = unsafe { GetWindowTextW(hwnd, &mut buffer) };
+    let title = String::from_utf16_lossy(&buffer[..len as usize]);
+    let rect = get_window_position(hwnd).ok()?;
+    Some(DesktopWindowInfo {
+        desktop_index: index,
+        hwnd: hwnd.0 as isize,
+        title,
+        rect,
+    })
+}
+
+/// Counts describing how much of a saved layout [`restore_all_desktops`] applied.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RestoreStats {
+    /// Windows moved into place, or for a dry run, windows that would be.
+    pub matched: usize,
+    /// Saved entries without a live window, whose desktop no longer exists,
+    /// or whose window closed or refused to move during the restore.
+    pub skipped_invalid: usize,
+    /// Desktop switches that failed; windows on that desktop are still moved.
+    pub desktop_switch_failures: usize,
+}
+
+impl fmt::Display for RestoreStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Restored {}, skipped {}, desktop switch failures {}",
+            self.matched, self.skipped_invalid, self.desktop_switch_failures
+        )
+    }
+}
+
+/// Outcome of [`restore_all_desktops`].
+#[derive(Debug, Default)]
+pub struct RestoreSummary {
+    /// Saved entries paired with the HWND of the live window they matched.
+    pub matched: Vec<(DesktopWindowInfo, isize)>,
+    /// Saved entries with no matching live window or whose desktop no longer exists.
+    pub missing: Vec<DesktopWindowInfo>,
+    /// Set if the restore was cancelled before every matched window was moved.
+    pub cancelled: bool,
+    pub stats: RestoreStats,
+}
+
+/// Pairs each saved entry with a live window, first by HWND and then by exact
+/// title. A live window is matched at most once.
+fn match_saved_windows(infos: Vec<DesktopWindowInfo>, live: &[(isize, String)]) -> RestoreSummary {
+    let mut summary = RestoreSummary::default();
+    let mut taken = vec![false; live.len()];
+    let mut unmatched = Vec::new();
+
+    for info in infos {
+        match live.iter().position(|(hwnd, _)| *hwnd == info.hwnd) {
+            Some(i) if !taken[i] => {
+                taken[i] = true;
+                summary.matched.push((info, live[i].0));
             }
+            _ => unmatched.push(info),
         }
     }
-    BOOL(1)
+    for info in unmatched {
+        match live
+            .iter()
+            .enumerate()
+            .position(|(i, (_, title))| !taken[i] && *title == info.title)
+        {
+            Some(i) => {
+                taken[i] = true;
+                summary.matched.push((info, live[i].0));
+            }
+            None => summary.missing.push(info),
+        }
+    }
+    summary
+}
+
+/// Orders `matched` so the windows of each desktop are contiguous, keeping
+/// desktops in the order they first appear and windows in file order within a
+/// desktop, so restoring switches to each desktop exactly once.
+fn group_by_desktop(matched: &mut [(DesktopWindowInfo, isize)]) {
+    let mut first_seen: Vec<u32> = Vec::new();
+    for (info, _) in matched.iter() {
+        if !first_seen.contains(&info.desktop_index) {
+            first_seen.push(info.desktop_index);
+        }
+    }
+    matched.sort_by_key(|(info, _)| {
+        first_seen
+            .iter()
+            .position(|&desktop| desktop == info.desktop_index)
+    });
 }
 
 /// Restore window positions across all desktops from a JSON file.
+///
+/// Saved windows are matched to live ones by HWND, then by title. With
+/// `dry_run` set, the planned moves are only logged: no desktop is switched and
+/// no window is moved.
+///
+/// Windows are grouped by desktop (see [`group_by_desktop`]) so each desktop
+/// is switched to once, and each switch is followed by `switch_delay` so it
+/// has finished before windows are moved. The original desktop is restored
+/// afterwards, also when `progress` is cancelled part way through.
 #[cfg(target_os = "windows")]
-pub fn restore_all_desktops(file: &str) {
-    let data = match std::fs::read_to_string(file) {
-        Ok(s) => s,
-        Err(e) => {
-            warn!("Failed to read {}: {}", file, e);
-            return;
-        }
-    };
-    let infos: Vec<DesktopWindowInfo> = match serde_json::from_str(&data) {
-        Ok(v) => v,
-        Err(e) => {
-            warn!("Failed to parse {}: {}", file, e);
-            return;
-        }
-    };
-    let desktops = match virtual_desktop::get_desktops() {
-        Ok(d) => d,
-        Err(e) => {
-            warn!("Failed to enumerate desktops: {:?}", e);
-            return;
+pub fn restore_all_desktops(
+    file: &str,
+    dry_run: bool,
+    switch_delay: Duration,
+    progress: &DesktopProgress,
+) -> std::result::Result<RestoreSummary, String> {
+    let data =
+        std::fs::read_to_string(file).map_err(|e| format!("Failed to read {}: {}", file, e))?;
+    let infos: Vec<DesktopWindowInfo> =
+        serde_json::from_str(&data).map_err(|e| format!("Failed to parse {}: {}", file, e))?;
+    let desktops = virtual_desktop::get_desktops()
+        .map_err(|e| format!("Failed to enumerate desktops: {:?}", e))?;
+
+    let mut live: Vec<(isize, String)> = Vec::new();
+    unsafe {
+        let _ = EnumWindows(Some(enum_titles_proc), LPARAM(&mut live as *mut _ as isize));
+    }
+
+    let mut summary = match_saved_windows(infos, &live);
+    let (on_known_desktop, on_missing_desktop): (Vec<_>, Vec<_>) = summary
+        .matched
+        .drain(..)
+        .partition(|(info, _)| (info.desktop_index as usize) < desktops.len());
+    summary.matched = on_known_desktop;
+    for (info, _) in on_missing_desktop {
+        warn!(
+            "Desktop {} for '{}' no longer exists.",
+            info.desktop_index, info.title
+        );
+        summary.missing.push(info);
+    }
+
+    for info in &summary.missing {
+        info!("No live window found for '{}'.", info.title);
+    }
+    group_by_desktop(&mut summary.matched);
+    summary.stats.skipped_invalid = summary.missing.len();
+
+    if dry_run {
+        summary.stats.matched = summary.matched.len();
+        for (info, hwnd) in &summary.matched {
+            info!(
+                "Would move '{}' (HWND {:#x}) on desktop {} to {:?}.",
+                info.title, hwnd, info.desktop_index, info.rect
+            );
         }
-    };
+        return Ok(summary);
+    }
+
+    progress.set_total(summary.matched.len());
     let current = virtual_desktop::get_current_desktop().ok();
-    for info in &infos {
-        if let Some(target) = desktops.get(info.desktop_index as usize) {
+    let mut active_desktop = None;
+    for (info, hwnd) in &summary.matched {
+        if progress.is_cancelled() {
+            info!("Desktop restore cancelled.");
+            summary.cancelled = true;
+            break;
+        }
+        if active_desktop != Some(info.desktop_index) {
+            let target = &desktops[info.desktop_index as usize];
             if let Err(e) = virtual_desktop::switch_desktop(target) {
                 warn!("Failed to switch desktop: {:?}", e);
+                summary.stats.desktop_switch_failures += 1;
             }
-            let hwnd = HWND(info.hwnd as *mut _);
-            unsafe {
-                if IsWindow(hwnd).as_bool() {
-                    move_window(hwnd, info.rect.0, info.rect.1, info.rect.2, info.rect.3).ok();
-                }
-            }
+            std::thread::sleep(switch_delay);
+            active_desktop = Some(info.desktop_index);
+        }
+        let hwnd = HWND(*hwnd as *mut _);
+        let moved = if unsafe { IsWindow(hwnd) }.as_bool() {
+            move_window(hwnd, info.rect.0, info.rect.1, info.rect.2, info.rect.3)
+                .map_err(|e| warn!("Failed to restore '{}': {}", info.title, e))
+                .is_ok()
+        } else {
+            warn!("'{}' closed before it could be restored.", info.title);
+            false
+        };
+        if moved {
+            summary.stats.matched += 1;
+        } else {
+            summary.stats.skipped_invalid += 1;
         }
+        progress.advance();
     }
     if let Some(d) = current {
         let _ = virtual_desktop::switch_desktop(&d);
     }
+    Ok(summary)
+}
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn enum_titles_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    if IsWindowVisible(hwnd).as_bool() {
+        let list = &mut *(lparam.0 as *mut Vec<(isize, String)>);
+        let mut buffer = [0u16; 256];
+        let len = GetWindowTextW(hwnd, &mut buffer);
+        list.push((
+            hwnd.0 as isize,
+            String::from_utf16_lossy(&buffer[..len as usize]),
+        ));
+    }
+    BOOL(1)
+}
+
+/// Lists visible top-level windows that can be added to a workspace, as
+/// `(HWND, title)` pairs in Z-order.
+///
+/// Windows rejected by [`is_real_app_window`] are left out.
+pub fn list_capturable_windows() -> Vec<(isize, String)> {
+    let mut windows: Vec<(isize, String)> = Vec::new();
+    unsafe {
+        let _ = EnumWindows(
+            Some(enum_capturable_proc),
+            LPARAM(&mut windows as *mut _ as isize),
+        );
+    }
+    windows
+}
+
+unsafe extern "system" fn enum_capturable_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    if !is_real_app_window(hwnd) {
+        return BOOL(1);
+    }
+    let mut buffer = [0u16; 256];
+    let len = GetWindowTextW(hwnd, &mut buffer);
+    let title = String::from_utf16_lossy(&buffer[..len as usize]);
+    if !title.trim().is_empty() {
+        let list = &mut *(lparam.0 as *mut Vec<(isize, String)>);
+        list.push((hwnd.0 as isize, title));
+    }
+    BOOL(1)
 }
 
 #[cfg(target_os = "windows")]
-/// Helper structure passed to `EnumWindows` containing the primary monitor
-/// dimensions. The enumeration callback uses these values to calculate the
-/// centered coordinates for each window it visits.
+/// Helper structure passed to `EnumWindows` containing the center of the
+/// primary monitor's work area. The enumeration callback uses it to calculate
+/// the centered coordinates for each window it visits.
 struct OriginData {
-    /// Width of the primary monitor in physical pixels.
-    width: i32,
-    /// Height of the primary monitor in physical pixels.
-    height: i32,
+    /// Center of the primary monitor's work area in screen coordinates.
+    center: (i32, i32),
+    /// Executable names whose windows are left where they are.
+    exclude_processes: Vec<String>,
+    /// When set, only windows on the virtual desktop with this index are moved.
+    current_desktop: Option<u32>,
 }
 
 #[cfg(target_os = "windows")]
 /// Moves every visible top-level window so that it is centered on the primary
 /// monitor. A confirmation dialog is displayed before any action is taken.
 ///
+/// Windows owned by a process listed in `settings.origin_exclude_processes`
+/// are skipped, as are windows on other virtual desktops when
+/// `settings.origin_current_desktop_only` is set.
+///
 /// # Behavior
-/// - Retrieves the primary monitor's dimensions using
-///   [`GetSystemMetrics`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getsystemmetrics).
+/// - Finds the center of the primary monitor's work area with
+///   [`primary_work_area_center`].
 /// - Enumerates all top-level windows via [`EnumWindows`]. For each valid and
 ///   visible window, the helper callback (`enum_origin_proc`) is invoked.
 /// - The callback calculates the centered coordinates for the window based on
@@ -392,19 +1366,31 @@ struct OriginData {
 ///
 /// # Example
 /// ```no_run
-/// move_all_to_origin(); // Centers every visible window on the primary screen
+/// move_all_to_origin(&load_settings()); // Centers every visible window on the primary screen
 /// ```
-pub fn move_all_to_origin() {
+pub fn move_all_to_origin(settings: &Settings) {
     if !show_confirmation_box(
         "Move all windows to the center of the primary monitor?",
         "Confirm",
     ) {
         return;
     }
+    let current_desktop = if settings.origin_current_desktop_only {
+        match virtual_desktop::get_current_desktop().and_then(|desktop| desktop.get_index()) {
+            Ok(index) => Some(index),
+            Err(e) => {
+                warn!("Failed to determine the current desktop: {:?}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
     unsafe {
         let mut data = OriginData {
-            width: GetSystemMetrics(SM_CXSCREEN),
-            height: GetSystemMetrics(SM_CYSCREEN),
+            center: primary_work_area_center(),
+            exclude_processes: settings.origin_exclude_processes.clone(),
+            current_desktop,
         };
         // Enumerate every top-level window, passing a pointer to `data` so the
         // callback can compute centered positions.
@@ -427,28 +1413,123 @@ pub fn move_all_to_origin() {
 /// - `BOOL(1)` to continue enumeration regardless of success or failure.
 ///
 /// # Behavior
-/// - Skips windows that are invalid or not visible.
+/// - Skips windows rejected by [`is_real_app_window`], windows of excluded processes
+///   and, if requested, windows on other virtual desktops.
 /// - Retrieves the window's size using [`get_window_position`].
 /// - Calculates centered coordinates and calls [`move_window`].
 /// - Logs the outcome of the move for debugging purposes.
 unsafe extern "system" fn enum_origin_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
-    // Skip invalid or hidden windows.
-    if !IsWindow(hwnd).as_bool() || !IsWindowVisible(hwnd).as_bool() {
+    // Skip invalid, hidden, cloaked and tool windows.
+    if !is_real_app_window(hwnd) {
         return BOOL(1);
     }
-    // Extract the screen dimensions from lparam.
-    let data = &*(lparam.0 as *const OriginData);
+    // Extract the screen dimensions from lparam.
+    let data = &*(lparam.0 as *const OriginData);
+
+    if !data.exclude_processes.is_empty() {
+        if let Some(path) = process_path_for_window(hwnd) {
+            if is_excluded_process(&path, &data.exclude_processes) {
+                debug!("Leaving window {:?} of excluded process '{}'", hwnd, path);
+                return BOOL(1);
+            }
+        }
+    }
+
+    if let Some(current) = data.current_desktop {
+        let desktop = virtual_desktop::get_desktop_by_window(hwnd).and_then(|d| d.get_index());
+        if !matches!(desktop, Ok(index) if index == current) {
+            debug!(
+                "Leaving window {:?} on desktop {:?}; current desktop is {}",
+                hwnd, desktop, current
+            );
+            return BOOL(1);
+        }
+    }
+
+    if let Ok((_, _, w, h)) = get_window_position(hwnd) {
+        let (x, y, w, h) = centered_rect(data.center, (w, h));
+        match move_window(hwnd, x, y, w, h) {
+            Ok(_) => info!("Moved window {:?} to center ({}, {})", hwnd, x, y),
+            Err(e) => warn!("Failed to move window {:?}: {}", hwnd, e),
+        }
+    }
+    BOOL(1)
+}
+
+/// Reads the executable name and class of `hwnd`, or `None` if the window
+/// does not exist.
+pub fn window_fingerprint(hwnd: HWND) -> Option<WindowFingerprint> {
+    let mut buffer = [0u16; 256];
+    let len = unsafe { GetClassNameW(hwnd, &mut buffer) };
+    if len == 0 {
+        return None;
+    }
+    let process = process_path_for_window(hwnd)
+        .map(|path| path.rsplit(['\\', '/']).next().unwrap_or(&path).to_string())
+        .unwrap_or_default();
+    Some(WindowFingerprint {
+        process,
+        class: String::from_utf16_lossy(&buffer[..len as usize]),
+    })
+}
+
+/// Marks enabled windows whose handle now belongs to a different window as
+/// invalid, so toggling leaves the unrelated window alone.
+fn invalidate_reused_handles(workspace: &mut Workspace) {
+    for window in workspace
+        .windows
+        .iter_mut()
+        .filter(|w| w.enabled && w.valid)
+    {
+        let hwnd = HWND(window.id as *mut std::ffi::c_void);
+        if unsafe { IsWindow(hwnd).as_bool() } && !window.matches_live(hwnd) {
+            warn!(
+                "Handle {:?} of window '{}' in workspace '{}' now belongs to a different window; skipping it.",
+                hwnd, window.title, workspace.name
+            );
+            window.valid = false;
+        }
+    }
+}
 
-    if let Ok((_, _, w, h)) = get_window_position(hwnd) {
-        // Compute centered coordinates.
-        let x = (data.width - w) / 2;
-        let y = (data.height - h) / 2;
-        match move_window(hwnd, x, y, w, h) {
-            Ok(_) => info!("Moved window {:?} to center ({}, {})", hwnd, x, y),
-            Err(e) => warn!("Failed to move window {:?}: {}", hwnd, e),
+/// Returns the full executable path of the process that owns `hwnd`.
+#[cfg(target_os = "windows")]
+fn process_path_for_window(hwnd: HWND) -> Option<String> {
+    unsafe {
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        if pid == 0 {
+            return None;
         }
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut len,
+        );
+        let _ = CloseHandle(process);
+        result.ok()?;
+        Some(String::from_utf16_lossy(&buffer[..len as usize]))
     }
-    BOOL(1)
+}
+
+/// Returns `true` if the executable at `path` matches one of `excluded`.
+///
+/// Entries are compared case-insensitively against the file name, and the
+/// `.exe` extension may be omitted (`notepad` matches `C:\Windows\notepad.exe`).
+fn is_excluded_process(path: &str, excluded: &[String]) -> bool {
+    let file_name = path.rsplit(['\\', '/']).next().unwrap_or(path);
+    let stem = file_name
+        .strip_suffix(".exe")
+        .or_else(|| file_name.strip_suffix(".EXE"))
+        .unwrap_or(file_name);
+    excluded.iter().map(|entry| entry.trim()).any(|entry| {
+        !entry.is_empty()
+            && (entry.eq_ignore_ascii_case(file_name) || entry.eq_ignore_ascii_case(stem))
+    })
 }
 
 #[cfg(target_os = "windows")]
@@ -465,12 +1546,8 @@ pub fn move_window_to_origin(hwnd: HWND) {
         }
     }
 
-    let screen_width = unsafe { GetSystemMetrics(SM_CXSCREEN) };
-    let screen_height = unsafe { GetSystemMetrics(SM_CYSCREEN) };
-
     if let Ok((_, _, w, h)) = get_window_position(hwnd) {
-        let x = (screen_width - w) / 2;
-        let y = (screen_height - h) / 2;
+        let (x, y, w, h) = centered_rect(primary_work_area_center(), (w, h));
         match move_window(hwnd, x, y, w, h) {
             Ok(_) => info!("Moved window {:?} to center ({}, {})", hwnd, x, y),
             Err(e) => warn!("Failed to move window {:?}: {}", hwnd, e),
@@ -489,18 +1566,27 @@ pub fn move_window_to_origin(_hwnd: HWND) {
 #[cfg(not(target_os = "windows"))]
 /// Stub implementation for non-Windows platforms. Calling this function on a
 /// non-Windows build logs a warning and performs no action.
-pub fn move_all_to_origin() {
+pub fn move_all_to_origin(_settings: &Settings) {
     warn!("move_all_to_origin is only available on Windows");
 }
 
 #[cfg(not(target_os = "windows"))]
-pub fn capture_all_desktops(_file: &str) {
-    warn!("capture_all_desktops is only available on Windows");
+pub fn capture_all_desktops(
+    _file: &str,
+    _filter: &CaptureFilter,
+    _progress: &DesktopProgress,
+) -> std::result::Result<usize, String> {
+    Err("capture_all_desktops is only available on Windows".to_string())
 }
 
 #[cfg(not(target_os = "windows"))]
-pub fn restore_all_desktops(_file: &str) {
-    warn!("restore_all_desktops is only available on Windows");
+pub fn restore_all_desktops(
+    _file: &str,
+    _dry_run: bool,
+    _switch_delay: Duration,
+    _progress: &DesktopProgress,
+) -> std::result::Result<RestoreSummary, String> {
+    Err("restore_all_desktops is only available on Windows".to_string())
 }
 
 /// Determines whether the specified `hwnd` is currently located at the given **(x, y)** coordinates
@@ -575,10 +1661,113 @@ pub fn is_window_at_position(hwnd: HWND, x: i32, y: i32, w: i32, h: i32) -> bool
 ///
 /// # Notes
 /// - Ensure the `hwnd` passed to this function is valid before calling.
+/// - The process is per-monitor DPI aware (see `monitor::enable_dpi_awareness`), so the
+///   rectangle is in physical pixels of the monitor the window is on.
+/// - While frame bounds compensation is on (see [`set_frame_bounds_compensation`]) the
+///   visible frame from [`get_window_frame_bounds`] is returned instead, so the invisible
+///   resize borders are not counted.
 ///
 /// # Win32 API Reference
 /// - [`GetWindowRect`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-getwindowrect)
 pub fn get_window_position(hwnd: HWND) -> Result<(i32, i32, i32, i32)> {
+    if frame_bounds_compensation() {
+        return get_window_frame_bounds(hwnd);
+    }
+    get_outer_window_rect(hwnd)
+}
+
+/// Whether window rectangles are the visible frame rather than the full window rectangle.
+static FRAME_BOUNDS_COMPENSATION: AtomicBool = AtomicBool::new(true);
+
+/// Chooses whether captured and applied rectangles describe the visible frame
+/// (`true`) or the full `GetWindowRect` rectangle including the invisible borders
+/// Windows 10 and later draw around most windows.
+pub fn set_frame_bounds_compensation(enabled: bool) {
+    FRAME_BOUNDS_COMPENSATION.store(enabled, Ordering::SeqCst);
+}
+
+/// Returns the value last passed to [`set_frame_bounds_compensation`].
+pub fn frame_bounds_compensation() -> bool {
+    FRAME_BOUNDS_COMPENSATION.load(Ordering::SeqCst)
+}
+
+/// Returns the visible bounds of a window from `DWMWA_EXTENDED_FRAME_BOUNDS`,
+/// falling back to `GetWindowRect` when DWM cannot report them.
+pub fn get_window_frame_bounds(hwnd: HWND) -> Result<(i32, i32, i32, i32)> {
+    let mut rect = RECT::default();
+    let result = unsafe {
+        DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut rect as *mut RECT as *mut std::ffi::c_void,
+            std::mem::size_of::<RECT>() as u32,
+        )
+    };
+    if result.is_err() || rect.right <= rect.left || rect.bottom <= rect.top {
+        return get_outer_window_rect(hwnd);
+    }
+    Ok((
+        rect.left,
+        rect.top,
+        rect.right - rect.left,
+        rect.bottom - rect.top,
+    ))
+}
+
+/// Returns the invisible border widths `(left, top, right, bottom)` around the
+/// visible frame of `hwnd`, or zeros when compensation is off or unavailable.
+fn invisible_frame_margins(hwnd: HWND) -> (i32, i32, i32, i32) {
+    if !frame_bounds_compensation() {
+        return (0, 0, 0, 0);
+    }
+    match (get_outer_window_rect(hwnd), get_window_frame_bounds(hwnd)) {
+        (Ok(outer), Ok(frame)) => frame_margins(outer, frame),
+        _ => (0, 0, 0, 0),
+    }
+}
+
+/// Computes how far the `outer` window rectangle extends past the visible `frame`
+/// on each side, as `(left, top, right, bottom)`.
+fn frame_margins(outer: (i32, i32, i32, i32), frame: (i32, i32, i32, i32)) -> (i32, i32, i32, i32) {
+    let left = frame.0 - outer.0;
+    let top = frame.1 - outer.1;
+    let right = (outer.0 + outer.2) - (frame.0 + frame.2);
+    let bottom = (outer.1 + outer.3) - (frame.1 + frame.3);
+    (left, top, right, bottom)
+}
+
+/// Grows a visible-frame rectangle by `margins` into the rectangle to pass to
+/// `SetWindowPos`.
+fn expand_by_margins(
+    rect: (i32, i32, i32, i32),
+    margins: (i32, i32, i32, i32),
+) -> (i32, i32, i32, i32) {
+    let (left, top, right, bottom) = margins;
+    (
+        rect.0 - left,
+        rect.1 - top,
+        rect.2 + left + right,
+        rect.3 + top + bottom,
+    )
+}
+
+/// Shrinks an outer window rectangle by `margins` into its visible frame; the
+/// inverse of [`expand_by_margins`].
+fn shrink_by_margins(
+    rect: (i32, i32, i32, i32),
+    margins: (i32, i32, i32, i32),
+) -> (i32, i32, i32, i32) {
+    let (left, top, right, bottom) = margins;
+    (
+        rect.0 + left,
+        rect.1 + top,
+        rect.2 - left - right,
+        rect.3 - top - bottom,
+    )
+}
+
+/// Reads the full window rectangle with `GetWindowRect`.
+fn get_outer_window_rect(hwnd: HWND) -> Result<(i32, i32, i32, i32)> {
     unsafe {
         let mut rect = RECT::default();
         if GetWindowRect(hwnd, &mut rect).is_ok() {
@@ -594,9 +1783,22 @@ pub fn get_window_position(hwnd: HWND) -> Result<(i32, i32, i32, i32)> {
     }
 }
 
+/// Returns `true` if the placement of `hwnd` is reported in workspace
+/// coordinates, which is the case for top-level windows that are not tool windows.
+fn uses_workspace_coordinates(hwnd: HWND) -> bool {
+    let ex_style = unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) } as u32;
+    ex_style & WS_EX_TOOLWINDOW.0 == 0
+}
+
 /// Sets the restore rectangle for a minimized window so it will
-/// reappear at the specified coordinates when restored.
+/// reappear at the specified coordinates (screen coordinates, which may be
+/// negative on monitors left of or above the primary one) when restored.
 pub fn set_restore_position(hwnd: HWND, x: i32, y: i32, w: i32, h: i32) -> Result<()> {
+    let (x, y, w, h) = if uses_workspace_coordinates(hwnd) {
+        screen_to_workspace((x, y, w, h), workspace_offset_for_rect((x, y, w, h)))
+    } else {
+        (x, y, w, h)
+    };
     unsafe {
         let mut placement = WINDOWPLACEMENT::default();
         placement.length = std::mem::size_of::<WINDOWPLACEMENT>() as u32;
@@ -617,7 +1819,7 @@ pub fn set_restore_position(hwnd: HWND, x: i32, y: i32, w: i32, h: i32) -> Resul
 /// # Behavior
 /// - Matches the input `key` (converted to uppercase) against a predefined list of known key names (e.g. `"F1"`, `"NUMPAD0"`, `"LEFTALT"`, etc.).
 /// - Returns the matching `u32` virtual key code if recognized, or `None` if the `key` is unrecognized.
-/// - Handles a wide variety of function, alphanumeric, numpad, arrow, and special keys.
+/// - Handles a wide variety of function, alphanumeric, numpad, arrow, media, browser, and special keys.
 /// - Case-insensitive for recognized tokens.
 ///
 /// # Side Effects
@@ -769,10 +1971,168 @@ pub fn virtual_key_from_string(key: &str) -> Option<u32> {
         "LEFTALT" => Some(0xA4),
         "RIGHTALT" => Some(0xA5),
 
+        // Browser keys
+        "BROWSERBACK" => Some(0xA6),
+        "BROWSERFORWARD" => Some(0xA7),
+        "BROWSERREFRESH" => Some(0xA8),
+        "BROWSERSTOP" => Some(0xA9),
+        "BROWSERSEARCH" => Some(0xAA),
+        "BROWSERFAVORITES" => Some(0xAB),
+        "BROWSERHOME" => Some(0xAC),
+
+        // Media keys
+        "VOLUMEMUTE" => Some(0xAD),
+        "VOLUMEDOWN" => Some(0xAE),
+        "VOLUMEUP" => Some(0xAF),
+        "MEDIANEXT" => Some(0xB0),
+        "MEDIAPREV" => Some(0xB1),
+        "MEDIASTOP" => Some(0xB2),
+        "MEDIAPLAYPAUSE" => Some(0xB3),
+
+        _ => None,
+    }
+}
+
+/// Character the symbol key `vk` types on a US layout, which is what the
+/// `OEM_*` names in [`virtual_key_from_string`] describe. `None` for keys that
+/// do not depend on the layout.
+fn us_layout_char(vk: u32) -> Option<char> {
+    match vk {
+        0xBA => Some(';'),
+        0xBB => Some('='),
+        0xBC => Some(','),
+        0xBD => Some('-'),
+        0xBE => Some('.'),
+        0xBF => Some('/'),
+        0xC0 => Some('`'),
+        0xDB => Some('['),
+        0xDC => Some('\\'),
+        0xDD => Some(']'),
+        0xDE => Some('\''),
         _ => None,
     }
 }
 
+/// Compares what the symbol key `vk` (named `key`) types on the active layout,
+/// as reported by `MapVirtualKeyW(vk, MAPVK_VK_TO_CHAR)` in `produced`, with
+/// the character it types on a US layout. Returns a warning if they differ.
+fn layout_mismatch(key: &str, vk: u32, produced: u32) -> Option<String> {
+    let expected = us_layout_char(vk)?;
+    // The high bit marks a dead key; the low word holds the character.
+    let produced = char::from_u32(produced & 0xFFFF).filter(|c| *c != '\0');
+    match produced {
+        Some(c) if c == expected => None,
+        Some(c) => Some(format!(
+            "On the current keyboard layout the {} key types '{}' instead of '{}', so this hotkey may not be the key you expect.",
+            key, c, expected
+        )),
+        None => Some(format!(
+            "The {} key ('{}' on a US layout) does not exist on the current keyboard layout, so this hotkey may never fire.",
+            key, expected
+        )),
+    }
+}
+
+/// Checks whether the main key of `sequence` is a symbol key that types a
+/// different character, or none at all, on the active keyboard layout.
+///
+/// Returns a warning to show next to the hotkey, or `None` if the key does not
+/// depend on the layout or matches the US layout its name refers to.
+pub fn hotkey_layout_warning(sequence: &str) -> Option<String> {
+    let key = sequence.split('+').map(str::trim).rfind(|part| {
+        !matches!(
+            part.to_lowercase().as_str(),
+            "ctrl" | "alt" | "shift" | "win"
+        )
+    })?;
+    let vk = virtual_key_from_string(key)?;
+    us_layout_char(vk)?;
+    let produced = unsafe { MapVirtualKeyW(vk, MAPVK_VK_TO_CHAR) };
+    layout_mismatch(key, vk, produced)
+}
+
+/// Converts a user-typed or captured hotkey string into its **canonical** form.
+///
+/// # Behavior
+/// - Splits `input` on `'+'` and trims each part.
+/// - Recognizes the modifiers `Ctrl`, `Alt`, `Shift`, and `Win` case-insensitively and
+///   emits them in the fixed order Ctrl → Alt → Shift → Win, dropping duplicates.
+/// - Requires exactly one non-modifier key that is known to [`virtual_key_from_string`];
+///   the key is emitted in uppercase (e.g. `"h+ctrl"` becomes `"Ctrl+H"`).
+///
+/// # Example
+/// ```rust
+/// assert_eq!(normalize_hotkey("shift+ctrl+p"), Ok("Ctrl+Shift+P".to_string()));
+/// ```
+///
+/// # Error Conditions
+/// - Returns `Err` with a descriptive message if the string is empty, contains an empty
+///   part, has no main key, has more than one main key, or uses an unknown key.
+pub fn normalize_hotkey(input: &str) -> std::result::Result<String, String> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err("Hotkey is empty.".to_string());
+    }
+
+    let (mut ctrl, mut alt, mut shift, mut win) = (false, false, false, false);
+    let mut main_key: Option<String> = None;
+
+    for part in trimmed.split('+') {
+        let part = part.trim();
+        if part.is_empty() {
+            return Err(format!("Hotkey '{}' contains an empty key.", input));
+        }
+        match part.to_lowercase().as_str() {
+            "ctrl" => ctrl = true,
+            "alt" => alt = true,
+            "shift" => shift = true,
+            "win" => win = true,
+            _ => {
+                if let Some(existing) = &main_key {
+                    return Err(format!(
+                        "Hotkey '{}' has more than one main key ('{}' and '{}').",
+                        input, existing, part
+                    ));
+                }
+                if virtual_key_from_string(part).is_none() {
+                    return Err(format!("Hotkey '{}' uses unknown key '{}'.", input, part));
+                }
+                main_key = Some(part.to_uppercase());
+            }
+        }
+    }
+
+    let Some(main_key) = main_key else {
+        return Err(format!("Hotkey '{}' has no main key.", input));
+    };
+
+    let mut parts = Vec::new();
+    if ctrl {
+        parts.push("Ctrl".to_string());
+    }
+    if alt {
+        parts.push("Alt".to_string());
+    }
+    if shift {
+        parts.push("Shift".to_string());
+    }
+    if win {
+        parts.push("Win".to_string());
+    }
+    parts.push(main_key);
+    Ok(parts.join("+"))
+}
+
+/// Returns `true` if `sequence` includes at least one of Ctrl, Alt, Shift or Win.
+pub fn hotkey_has_modifier(sequence: &str) -> bool {
+    sequence.split('+').any(|part| {
+        matches!(
+            part.trim().to_lowercase().as_str(),
+            "ctrl" | "alt" | "shift" | "win"
+        )
+    })
+}
+
 /// Retrieves the **currently active window** (foreground window) along with its **title**.
 ///
 /// # Behavior
@@ -847,14 +2207,25 @@ pub fn get_active_window() -> Option<(HWND, String)> {
 /// - Typically called within `toggle_workspace_windows` and during manual “Move to Home/Target” user actions.
 /// - Only valid on Windows, where `SetWindowPos` is available.
 pub fn move_window(hwnd: HWND, x: i32, y: i32, w: i32, h: i32) -> Result<()> {
+    place_window(hwnd, x, y, w, h, true)
+}
+
+/// Like [`move_window`], but with `activate` set to `false` the window is
+/// restored and positioned without being activated.
+fn place_window(hwnd: HWND, x: i32, y: i32, w: i32, h: i32, activate: bool) -> Result<()> {
     unsafe {
         if IsIconic(hwnd).as_bool() {
             set_restore_position(hwnd, x, y, w, h)?;
-            ShowWindow(hwnd, SW_RESTORE);
-            SetWindowPos(hwnd, HWND_TOP, x, y, w, h, SWP_NOZORDER)?;
-        } else {
-            SetWindowPos(hwnd, HWND_TOP, x, y, w, h, SWP_NOZORDER)?;
+            ShowWindow(hwnd, restore_command(activate));
         }
+        // The borders are only measurable once the window is restored.
+        let (ox, oy, ow, oh) = expand_by_margins((x, y, w, h), invisible_frame_margins(hwnd));
+        let flags = if activate {
+            SWP_NOZORDER
+        } else {
+            SWP_NOZORDER | SWP_NOACTIVATE
+        };
+        SetWindowPos(hwnd, HWND_TOP, ox, oy, ow, oh, flags)?;
         info!(
             "Moved window (HWND: {:?}) to position ({}, {}, {}, {}).",
             hwnd.0, x, y, w, h
@@ -863,6 +2234,250 @@ pub fn move_window(hwnd: HWND, x: i32, y: i32, w: i32, h: i32) -> Result<()> {
     }
 }
 
+/// Returns the rectangle of size `(w, h)` whose center is `(cx, cy)`.
+pub fn centered_rect(center: (i32, i32), size: (i32, i32)) -> (i32, i32, i32, i32) {
+    let (cx, cy) = center;
+    let (w, h) = size;
+    (cx - w / 2, cy - h / 2, w, h)
+}
+
+/// Moves a window so its center lands on `(cx, cy)`, keeping its current size.
+pub fn move_window_centered_at(hwnd: HWND, cx: i32, cy: i32) -> Result<()> {
+    let (_, _, w, h) = get_window_position(hwnd)?;
+    let (x, y, w, h) = centered_rect((cx, cy), (w, h));
+    move_window(hwnd, x, y, w, h)
+}
+
+/// Moves a window to `(x, y, w, h)` and then applies the captured [`WindowState`].
+///
+/// # Behavior
+/// - `Normal`: restores a maximized window first, then positions it with [`move_window`].
+/// - `Maximized`: positions the window on the destination monitor and maximizes it there
+///   with `ShowWindow(SW_MAXIMIZE)`.
+/// - `Minimized`: stores `(x, y, w, h)` as the restore rectangle and minimizes the window
+///   with `ShowWindow(SW_MINIMIZE)`.
+///
+/// # Error Conditions
+/// - Returns the underlying Win32 error if positioning or updating the placement fails.
+pub fn move_window_with_state(
+    hwnd: HWND,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    state: WindowState,
+) -> Result<()> {
+    place_window_with_state(hwnd, (x, y, w, h), state, true)
+}
+
+/// Like [`move_window_with_state`], but with `activate` set to `false` windows
+/// are restored and minimized without taking or passing on the focus.
+///
+/// Windows has no way to maximize a window without activating it, so a
+/// maximized destination still activates the window.
+fn place_window_with_state(
+    hwnd: HWND,
+    rect: (i32, i32, i32, i32),
+    state: WindowState,
+    activate: bool,
+) -> Result<()> {
+    let (x, y, w, h) = rect;
+    unsafe {
+        match state {
+            WindowState::Normal => {
+                if IsZoomed(hwnd).as_bool() {
+                    let _ = ShowWindow(hwnd, restore_command(activate));
+                }
+                place_window(hwnd, x, y, w, h, activate)
+            }
+            WindowState::Maximized => {
+                if IsZoomed(hwnd).as_bool() {
+                    let _ = ShowWindow(hwnd, restore_command(activate));
+                }
+                place_window(hwnd, x, y, w, h, activate)?;
+                let _ = ShowWindow(hwnd, SW_MAXIMIZE);
+                Ok(())
+            }
+            WindowState::Minimized => {
+                set_restore_position(hwnd, x, y, w, h)?;
+                minimize_window(hwnd, activate);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// `ShowWindow` command that restores a minimized or maximized window,
+/// activating it only if `activate` is set.
+fn restore_command(activate: bool) -> SHOW_WINDOW_CMD {
+    if activate {
+        SW_RESTORE
+    } else {
+        SW_SHOWNOACTIVATE
+    }
+}
+
+/// Default duration of an animated window move in milliseconds.
+pub const DEFAULT_ANIMATION_MS: u32 = 200;
+
+/// Delay between the intermediate `SetWindowPos` calls of an animated move.
+const ANIMATION_FRAME: Duration = Duration::from_millis(15);
+
+/// Controls whether toggled windows are animated and for how long.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveAnimation {
+    pub enabled: bool,
+    pub duration_ms: u32,
+}
+
+impl Default for MoveAnimation {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            duration_ms: DEFAULT_ANIMATION_MS,
+        }
+    }
+}
+
+/// Cubic ease-out: fast at the start and slowing into the destination.
+fn ease_out_cubic(t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Linearly interpolates between two rectangles at progress `t` (0.0–1.0).
+fn interpolate_rect(
+    from: (i32, i32, i32, i32),
+    to: (i32, i32, i32, i32),
+    t: f64,
+) -> (i32, i32, i32, i32) {
+    let lerp = |a: i32, b: i32| a + ((b - a) as f64 * t).round() as i32;
+    (
+        lerp(from.0, to.0),
+        lerp(from.1, to.1),
+        lerp(from.2, to.2),
+        lerp(from.3, to.3),
+    )
+}
+
+/// Like [`move_window_with_state`], but glides the window from its current
+/// rectangle to `rect` over `animation.duration_ms` when animation is enabled.
+///
+/// Minimized and maximized windows are restored first, as in the instant path,
+/// and the animation starts from the restored rectangle. Moves into the
+/// minimized state are never animated. The final position is always applied
+/// with [`move_window_with_state`] so the result matches an instant move.
+///
+/// With `activate` set to `false` the window is moved without being activated
+/// (see [`place_window_with_state`]).
+pub fn animate_window_with_state(
+    hwnd: HWND,
+    rect: (i32, i32, i32, i32),
+    state: WindowState,
+    animation: MoveAnimation,
+    activate: bool,
+) -> Result<()> {
+    if animation.enabled && animation.duration_ms > 0 && state != WindowState::Minimized {
+        unsafe {
+            if IsIconic(hwnd).as_bool() || IsZoomed(hwnd).as_bool() {
+                let _ = ShowWindow(hwnd, restore_command(activate));
+            }
+        }
+        if let Ok(from) = get_window_position(hwnd) {
+            let margins = invisible_frame_margins(hwnd);
+            let duration = Duration::from_millis(u64::from(animation.duration_ms));
+            let start = Instant::now();
+            loop {
+                let elapsed = start.elapsed();
+                if elapsed >= duration {
+                    break;
+                }
+                let t = ease_out_cubic(elapsed.as_secs_f64() / duration.as_secs_f64());
+                let (x, y, w, h) = expand_by_margins(interpolate_rect(from, rect, t), margins);
+                unsafe {
+                    SetWindowPos(hwnd, HWND_TOP, x, y, w, h, SWP_NOZORDER | SWP_NOACTIVATE)?;
+                }
+                std::thread::sleep(ANIMATION_FRAME);
+            }
+        }
+    }
+    place_window_with_state(hwnd, rect, state, activate)
+}
+
+/// Reads the current show state of a window via `GetWindowPlacement`.
+///
+/// Returns [`WindowState::Normal`] if the placement cannot be read.
+pub fn get_window_state(hwnd: HWND) -> WindowState {
+    unsafe {
+        let mut placement = WINDOWPLACEMENT {
+            length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+            ..Default::default()
+        };
+        if GetWindowPlacement(hwnd, &mut placement).is_err() {
+            return WindowState::Normal;
+        }
+        match SHOW_WINDOW_CMD(placement.showCmd as i32) {
+            SW_SHOWMINIMIZED | SW_MINIMIZE | SW_SHOWMINNOACTIVE => WindowState::Minimized,
+            SW_SHOWMAXIMIZED => WindowState::Maximized,
+            _ => WindowState::Normal,
+        }
+    }
+}
+
+/// Captures a window's position together with its show state.
+///
+/// For minimized windows the restore rectangle from `GetWindowPlacement` is
+/// returned instead of the off-screen iconic rectangle reported by `GetWindowRect`.
+pub fn capture_window_placement(hwnd: HWND) -> Result<((i32, i32, i32, i32), WindowState)> {
+    let state = get_window_state(hwnd);
+    if state == WindowState::Minimized {
+        unsafe {
+            let mut placement = WINDOWPLACEMENT {
+                length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+                ..Default::default()
+            };
+            GetWindowPlacement(hwnd, &mut placement)?;
+            let r = placement.rcNormalPosition;
+            let rect = (r.left, r.top, r.right - r.left, r.bottom - r.top);
+            let rect = if uses_workspace_coordinates(hwnd) {
+                workspace_to_screen(rect, workspace_offset_for_rect(rect))
+            } else {
+                rect
+            };
+            // `rcNormalPosition` is the outer rectangle; match the visible frame
+            // `get_window_position` captures for other windows.
+            let rect = shrink_by_margins(rect, invisible_frame_margins(hwnd));
+            return Ok((rect, state));
+        }
+    }
+    Ok((get_window_position(hwnd)?, state))
+}
+
+/// Returns `true` if the window is at `(x, y, w, h)` and in the given `state`.
+///
+/// Minimized windows are only checked for being iconic since their on-screen
+/// rectangle is not meaningful.
+pub fn is_window_at_placement(
+    hwnd: HWND,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    state: WindowState,
+) -> bool {
+    unsafe {
+        match state {
+            WindowState::Minimized => IsIconic(hwnd).as_bool(),
+            WindowState::Maximized => {
+                IsZoomed(hwnd).as_bool() && is_window_at_position(hwnd, x, y, w, h)
+            }
+            WindowState::Normal => {
+                !IsIconic(hwnd).as_bool() && is_window_at_position(hwnd, x, y, w, h)
+            }
+        }
+    }
+}
+
 /// Displays a dialog message prompting the user to press **Enter** or **Esc**, and upon Enter,
 /// also retrieves the **currently active window** (its handle and title).
 ///
@@ -931,15 +2546,55 @@ pub fn listen_for_keys_with_dialog() -> Option<&'static str> {
     }
 }
 
+/// Whether the hotkeys toggle key was down on the previous poll, so holding it
+/// flips the master switch only once.
+static TOGGLE_KEY_HELD: AtomicBool = AtomicBool::new(false);
+
+/// Flips `app.hotkeys_enabled` when the configured toggle key is pressed and
+/// persists the new state.
+fn check_hotkeys_toggle(app: &App) {
+    let toggle_key = app.hotkeys_toggle_key.lock().unwrap().clone();
+    let pressed = !toggle_key.trim().is_empty() && is_hotkey_pressed(toggle_key.trim());
+    let was_held = TOGGLE_KEY_HELD.swap(pressed, Ordering::Relaxed);
+    if !pressed || was_held {
+        return;
+    }
+
+    let enabled = toggle_hotkeys_enabled(app);
+    info!(
+        "Workspace hotkeys {} via '{}'.",
+        if enabled { "resumed" } else { "suspended" },
+        toggle_key
+    );
+    *app.last_hotkey_info.lock().unwrap() = Some((toggle_key, Instant::now()));
+}
+
+/// Flips `app.hotkeys_enabled`, persists it to `settings.json` and returns the
+/// new state.
+pub fn toggle_hotkeys_enabled(app: &App) -> bool {
+    let enabled = {
+        let mut enabled = app.hotkeys_enabled.lock().unwrap();
+        *enabled = !*enabled;
+        *enabled
+    };
+    let mut settings = load_settings();
+    settings.hotkeys_enabled = enabled;
+    save_settings(&settings);
+    enabled
+}
+
 /// Periodically checks for **pressed hotkeys** across all workspaces and toggles the associated workspace windows if matched.
 ///
 /// # Behavior
 /// - Locks the `workspaces` from the `app` to iterate over each `Workspace`.
+/// - Flips the global hotkeys switch when its toggle key is pressed, and returns early
+///   while hotkeys are suspended.
 /// - Skips any workspace that is marked `disabled`.
 /// - For each workspace with a valid `hotkey`, calls `is_hotkey_pressed(...)`.
 ///   - If true, **collects** that workspace’s index in a local list (`workspaces_to_toggle`).
 /// - After releasing the lock, toggles windows for each collected workspace via `toggle_workspace_windows(...)`.
 /// - Updates `last_hotkey_info` for any triggered hotkey, capturing the sequence and a timestamp.
+/// - Records each toggled workspace in `recent` via [`push_recent`].
 ///
 /// # Side Effects
 /// - May call Win32 API functions through `is_hotkey_pressed` (for checking key states) and `toggle_workspace_windows` (for re-positioning windows).
@@ -959,8 +2614,17 @@ pub fn listen_for_keys_with_dialog() -> Option<&'static str> {
 /// - This function is central to the application’s hotkey-based workspace toggling.
 /// - Must be invoked repeatedly (e.g., via a timed loop) to capture newly pressed keys.
 pub fn check_hotkeys(app: &App) {
+    check_hotkeys_toggle(app);
+    if !*app.hotkeys_enabled.lock().unwrap() {
+        return;
+    }
+
     let mut workspaces_to_toggle = Vec::new();
-    let workspaces = app.workspaces.lock().unwrap();
+    // Hold the lock from lookup to toggle so a workspace list swapped in by a
+    // load in between cannot shift the indices.
+    let mut workspaces = app.workspaces.lock().unwrap();
+    // Read before toggling anything, since toggling moves the focus.
+    let foreground = unsafe { GetForegroundWindow() }.0 as isize;
 
     for (i, workspace) in workspaces.iter().enumerate() {
         if workspace.disabled {
@@ -969,6 +2633,13 @@ pub fn check_hotkeys(app: &App) {
 
         if let Some(ref hotkey) = workspace.hotkey {
             if is_hotkey_pressed(&hotkey.key_sequence) {
+                if workspace.require_focus_member && !workspace.contains_window(foreground) {
+                    debug!(
+                        "Ignoring hotkey '{}' of '{}': the foreground window is not one of its windows.",
+                        hotkey.key_sequence, workspace.name
+                    );
+                    continue;
+                }
                 workspaces_to_toggle.push(i);
                 let mut last_hotkey_info = app.last_hotkey_info.lock().unwrap();
                 *last_hotkey_info = Some((hotkey.key_sequence.clone(), Instant::now()));
@@ -976,16 +2647,28 @@ pub fn check_hotkeys(app: &App) {
         }
     }
 
-    drop(workspaces); // Release lock before toggling
-
-    let mut workspaces = app.workspaces.lock().unwrap();
+    let animation = *app.move_animation.lock().unwrap();
+    let verify_moves = *app.verify_moves.lock().unwrap();
     for index in workspaces_to_toggle {
         if let Some(workspace) = workspaces.get_mut(index) {
-            toggle_workspace_windows(workspace);
+            toggle_workspace_windows(workspace, animation, verify_moves);
+            push_recent(&mut app.recent.lock().unwrap(), &workspace.name);
         }
     }
 }
 
+/// Number of workspaces kept in the "Recent" list.
+pub const RECENT_WORKSPACES_MAX: usize = 8;
+
+/// Moves the workspace `name` to the front of `recent`, dropping an earlier
+/// entry for the same workspace and the oldest entries beyond
+/// [`RECENT_WORKSPACES_MAX`].
+pub fn push_recent(recent: &mut VecDeque<String>, name: &str) {
+    recent.retain(|n| n != name);
+    recent.push_front(name.to_string());
+    recent.truncate(RECENT_WORKSPACES_MAX);
+}
+
 pub fn listen_for_keys_with_dialog_and_window() -> Option<(&'static str, HWND, String)> {
     unsafe {
         MessageBoxW(
@@ -1053,3 +2736,324 @@ pub fn poll_recapture_keys() -> Option<RecaptureAction> {
 pub fn poll_recapture_keys() -> Option<RecaptureAction> {
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn media_and_browser_keys_map_to_vk_codes() {
+        assert_eq!(virtual_key_from_string("VOLUMEMUTE"), Some(0xAD));
+        assert_eq!(virtual_key_from_string("VOLUMEDOWN"), Some(0xAE));
+        assert_eq!(virtual_key_from_string("VOLUMEUP"), Some(0xAF));
+        assert_eq!(virtual_key_from_string("MEDIANEXT"), Some(0xB0));
+        assert_eq!(virtual_key_from_string("MEDIAPREV"), Some(0xB1));
+        assert_eq!(virtual_key_from_string("MEDIASTOP"), Some(0xB2));
+        assert_eq!(virtual_key_from_string("MEDIAPLAYPAUSE"), Some(0xB3));
+        assert_eq!(virtual_key_from_string("BROWSERBACK"), Some(0xA6));
+        assert_eq!(virtual_key_from_string("BROWSERFORWARD"), Some(0xA7));
+        assert_eq!(virtual_key_from_string("BROWSERHOME"), Some(0xAC));
+        assert_eq!(virtual_key_from_string("mediaPlayPause"), Some(0xB3));
+    }
+
+    #[test]
+    fn frame_margins_round_trip() {
+        let outer = (93, 193, 814, 607);
+        let frame = (100, 200, 800, 600);
+        let margins = frame_margins(outer, frame);
+        assert_eq!(margins, (7, 7, 7, 0));
+        assert_eq!(expand_by_margins(frame, margins), outer);
+        assert_eq!(shrink_by_margins(outer, margins), frame);
+
+        // Monitors left of the primary one have negative coordinates.
+        let outer = (-1927, -7, 814, 607);
+        let frame = (-1920, 0, 800, 600);
+        assert_eq!(frame_margins(outer, frame), (7, 7, 7, 0));
+        assert_eq!(expand_by_margins(frame, (7, 7, 7, 0)), outer);
+    }
+
+    #[test]
+    fn centered_rect_places_center_on_point() {
+        assert_eq!(centered_rect((960, 540), (800, 600)), (560, 240, 800, 600));
+        assert_eq!(centered_rect((0, 0), (101, 51)), (-50, -25, 101, 51));
+    }
+
+    #[test]
+    fn only_windows_off_the_virtual_screen_are_rescued() {
+        let screen = (-1920, 0, 3840, 1080);
+        assert_eq!(
+            rescued_rect((-1900, 100, 800, 600), screen, (960, 540)),
+            None
+        );
+        assert_eq!(
+            rescued_rect((1800, 900, 800, 600), screen, (960, 540)),
+            None
+        );
+        assert_eq!(
+            rescued_rect((-4000, 100, 800, 600), screen, (960, 540)),
+            Some((560, 240, 800, 600))
+        );
+        assert_eq!(
+            rescued_rect((100, 1080, 800, 600), screen, (960, 540)),
+            Some((560, 240, 800, 600))
+        );
+    }
+
+    #[test]
+    fn media_keys_are_valid_hotkeys() {
+        assert!(crate::workspace::is_valid_key_combo("MEDIAPLAYPAUSE"));
+        assert!(crate::workspace::is_valid_key_combo("Ctrl+VOLUMEUP"));
+        assert!(crate::workspace::is_valid_key_combo("Alt+BROWSERBACK"));
+    }
+
+    #[test]
+    fn normalize_hotkey_reorders_modifiers() {
+        assert_eq!(normalize_hotkey("h+ctrl"), Ok("Ctrl+H".to_string()));
+        assert_eq!(normalize_hotkey("CTRL+ALT+H"), Ok("Ctrl+Alt+H".to_string()));
+        assert_eq!(
+            normalize_hotkey("win+shift+alt+ctrl+f5"),
+            Ok("Ctrl+Alt+Shift+Win+F5".to_string())
+        );
+        assert_eq!(normalize_hotkey("ctrl+Ctrl+a"), Ok("Ctrl+A".to_string()));
+    }
+
+    #[test]
+    fn minimize_others_minimizes_all_but_the_target_slot() {
+        assert_eq!(rotation_slot_action(0, true), SlotAction::Move);
+        assert_eq!(rotation_slot_action(1, true), SlotAction::Minimize);
+        assert_eq!(rotation_slot_action(2, true), SlotAction::Minimize);
+        assert_eq!(rotation_slot_action(1, false), SlotAction::Move);
+    }
+
+    #[test]
+    fn rotation_offsets_follow_direction_and_wrap() {
+        let progression = |start: usize, reverse, wrap| {
+            let mut offset = start;
+            (0..4)
+                .map(|_| {
+                    offset = next_rotation_offset(offset, 3, reverse, wrap);
+                    offset
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(progression(0, false, true), vec![1, 2, 0, 1]);
+        assert_eq!(progression(0, false, false), vec![1, 2, 2, 2]);
+        assert_eq!(progression(0, true, true), vec![2, 1, 0, 2]);
+        assert_eq!(progression(0, true, false), vec![2, 1, 1, 1]);
+    }
+
+    #[test]
+    fn bare_keys_have_no_modifier() {
+        assert!(!hotkey_has_modifier("H"));
+        assert!(!hotkey_has_modifier("F5"));
+        assert!(hotkey_has_modifier("Ctrl+H"));
+        assert!(hotkey_has_modifier("win+F5"));
+    }
+
+    #[test]
+    fn normalize_hotkey_rejects_unknown_keys() {
+        assert!(normalize_hotkey("Ctrl+Bogus").is_err());
+        assert!(normalize_hotkey("Ctrl+A+B").is_err());
+        assert!(normalize_hotkey("Ctrl++A").is_err());
+    }
+
+    #[test]
+    fn normalize_hotkey_requires_main_key() {
+        assert!(normalize_hotkey("Ctrl+Alt").is_err());
+        assert!(normalize_hotkey("").is_err());
+    }
+
+    fn saved(hwnd: isize, title: &str) -> DesktopWindowInfo {
+        DesktopWindowInfo {
+            desktop_index: 0,
+            hwnd,
+            title: title.to_string(),
+            rect: (0, 0, 100, 100),
+        }
+    }
+
+    #[test]
+    fn saved_windows_match_by_hwnd_then_title() {
+        let live = vec![
+            (1, "Editor".to_string()),
+            (7, "Browser".to_string()),
+            (9, "Terminal".to_string()),
+        ];
+        let summary = match_saved_windows(
+            vec![
+                saved(1, "Old editor title"),
+                saved(2, "Browser"),
+                saved(3, "Editor"),
+                saved(4, "Mail"),
+            ],
+            &live,
+        );
+
+        let matched: Vec<(&str, isize)> = summary
+            .matched
+            .iter()
+            .map(|(info, hwnd)| (info.title.as_str(), *hwnd))
+            .collect();
+        assert_eq!(matched, vec![("Old editor title", 1), ("Browser", 7)]);
+        let missing: Vec<&str> = summary.missing.iter().map(|i| i.title.as_str()).collect();
+        // HWND 1 is already taken, so the second "Editor" entry has nothing left.
+        assert_eq!(missing, vec!["Editor", "Mail"]);
+    }
+
+    #[test]
+    fn symbol_keys_are_checked_against_the_us_layout() {
+        // US layout: OEM_2 types '/'.
+        assert_eq!(layout_mismatch("OEM_2", 0xBF, '/' as u32), None);
+        // German layout: the same key types '#'.
+        let warning = layout_mismatch("OEM_2", 0xBF, '#' as u32).expect("should warn");
+        assert!(warning.contains("'#'") && warning.contains("'/'"));
+        // A dead key keeps its character in the low word.
+        assert_eq!(
+            layout_mismatch("OEM_3", 0xC0, 0x8000_0000 | '`' as u32),
+            None
+        );
+        // No mapping on this layout.
+        assert!(layout_mismatch("OEM_5", 0xDC, 0)
+            .expect("should warn")
+            .contains("does not exist"));
+        // Letters do not depend on the layout here.
+        assert_eq!(layout_mismatch("A", 0x41, 'Q' as u32), None);
+    }
+
+    #[test]
+    fn recent_workspaces_are_unique_newest_first_and_capped() {
+        let mut recent = VecDeque::new();
+        push_recent(&mut recent, "Code");
+        push_recent(&mut recent, "Mail");
+        push_recent(&mut recent, "Code");
+        assert_eq!(recent, ["Code", "Mail"]);
+
+        for index in 10..20 {
+            push_recent(&mut recent, &format!("Workspace {}", index));
+        }
+        assert_eq!(recent.len(), RECENT_WORKSPACES_MAX);
+        assert_eq!(recent.front().map(String::as_str), Some("Workspace 19"));
+        assert!(!recent.iter().any(|name| name == "Code"));
+    }
+
+    #[test]
+    fn restore_stats_describe_each_count() {
+        let stats = RestoreStats {
+            matched: 4,
+            skipped_invalid: 2,
+            desktop_switch_failures: 1,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "Restored 4, skipped 2, desktop switch failures 1"
+        );
+    }
+
+    #[test]
+    fn restore_visits_each_desktop_once() {
+        let on = |desktop_index: u32, hwnd: isize| {
+            (
+                DesktopWindowInfo {
+                    desktop_index,
+                    ..saved(hwnd, "Window")
+                },
+                hwnd,
+            )
+        };
+        let mut matched = vec![on(2, 1), on(0, 2), on(2, 3), on(1, 4), on(0, 5)];
+        group_by_desktop(&mut matched);
+        let order: Vec<(u32, isize)> = matched
+            .iter()
+            .map(|(info, hwnd)| (info.desktop_index, *hwnd))
+            .collect();
+        assert_eq!(order, vec![(2, 1), (2, 3), (0, 2), (0, 5), (1, 4)]);
+    }
+
+    #[test]
+    fn easing_starts_fast_and_ends_at_destination() {
+        assert_eq!(ease_out_cubic(0.0), 0.0);
+        assert_eq!(ease_out_cubic(1.0), 1.0);
+        assert_eq!(ease_out_cubic(2.0), 1.0);
+        assert!(ease_out_cubic(0.5) > 0.5);
+    }
+
+    #[test]
+    fn interpolated_rect_moves_between_endpoints() {
+        let from = (0, 0, 100, 100);
+        let to = (200, -100, 300, 100);
+        assert_eq!(interpolate_rect(from, to, 0.0), from);
+        assert_eq!(interpolate_rect(from, to, 1.0), to);
+        assert_eq!(interpolate_rect(from, to, 0.5), (100, -50, 200, 100));
+    }
+
+    #[test]
+    fn excluded_processes_match_file_name_with_or_without_extension() {
+        let excluded = vec!["Notepad.exe".to_string(), "explorer".to_string()];
+        assert!(is_excluded_process(r"C:\Windows\notepad.exe", &excluded));
+        assert!(is_excluded_process(r"C:\Windows\EXPLORER.EXE", &excluded));
+        assert!(!is_excluded_process(r"C:\Tools\notepad++.exe", &excluded));
+        assert!(!is_excluded_process(
+            r"C:\Windows\notepad.exe",
+            &[" ".to_string()]
+        ));
+    }
+
+    #[test]
+    fn capture_filter_matches_titles_and_executables() {
+        let code = r"C:\Program Files\Code\Code.exe";
+        let notepad = r"C:\Windows\notepad.exe";
+        assert!(CaptureFilter::default().allows("Anything", notepad));
+
+        let filter = CaptureFilter {
+            include: vec!["code".to_string(), "report".to_string(), " ".to_string()],
+            exclude: vec!["Secret".to_string()],
+        };
+        assert!(filter.allows("main.rs - Visual Studio", code));
+        assert!(filter.allows("Quarterly Report - Notepad", notepad));
+        assert!(!filter.allows("Untitled - Notepad", notepad));
+        assert!(!filter.allows("secret.txt - Visual Studio", code));
+
+        let exclude_only = CaptureFilter {
+            include: vec![" ".to_string()],
+            exclude: vec!["notepad".to_string()],
+        };
+        assert!(!exclude_only.is_empty());
+        assert!(exclude_only.allows("main.rs - Visual Studio", code));
+        assert!(!exclude_only.allows("Untitled - Notepad", notepad));
+    }
+
+    #[test]
+    fn stacking_sorts_by_z_order_then_later_windows_first() {
+        let window = |title: &str, z_order: usize| Window {
+            title: title.to_string(),
+            z_order,
+            ..Default::default()
+        };
+        let (a, b, c) = (window("A", 1), window("B", 0), window("C", 1));
+        assert_eq!(front_to_back(&[&a, &b, &c]), vec![1, 2, 0]);
+
+        let (d, e) = (window("D", 0), window("E", 0));
+        assert_eq!(front_to_back(&[&d, &e]), vec![1, 0]);
+    }
+
+    #[test]
+    fn drift_tolerance_allows_small_offsets_only() {
+        let expected = (100, 100, 800, 600);
+        assert!(rect_within_tolerance(expected, expected, VERIFY_TOLERANCE));
+        assert!(rect_within_tolerance(
+            (102, 98, 801, 600),
+            expected,
+            VERIFY_TOLERANCE
+        ));
+        assert!(!rect_within_tolerance(
+            (0, 0, 1920, 1080),
+            expected,
+            VERIFY_TOLERANCE
+        ));
+        assert!(!rect_within_tolerance(
+            (100, 100, 800, 603),
+            expected,
+            VERIFY_TOLERANCE
+        ));
+    }
+}
@@ -1,9 +1,18 @@
+use crate::backup::{backup_file, write_atomically};
 use crate::gui::App;
 use crate::hotkey::Hotkey;
+use crate::monitor::{
+    anchor_for_rect, anchor_for_window, describe_monitor, dpi_for_window, fraction_to_rect,
+    list_monitors, monitor_for_rect, monitor_for_window, rect_to_fraction, rects_intersect,
+    resolve_rect, scale_rect_between, virtual_screen_rect, work_area_for_device, MonitorAnchor,
+    MonitorDetails,
+};
+use crate::overlay::show_layout_preview;
+use crate::virtual_desktop::get_desktops;
+use crate::window_icon::{capture_window_icon, window_icon_texture};
 use crate::window_manager::get_window_position;
 use crate::window_manager::is_window_at_position;
 use crate::window_manager::listen_for_keys_with_dialog_and_window;
-use crate::window_manager::move_window;
 use crate::window_manager::move_window_to_origin;
 use crate::window_manager::*;
 use eframe::egui;
@@ -11,16 +20,39 @@ use log::{debug, error, info, warn};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::ffi::c_void;
+use std::fmt;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::Read;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::WindowsAndMessaging::IsWindow;
 
 static HOTKEY_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^(?:(?:Ctrl|Alt|Shift|Win)\+)?(?:(?:Ctrl|Alt|Shift|Win)\+)?(?:(?:Ctrl|Alt|Shift|Win)\+)?(?:(?:Ctrl|Alt|Shift|Win)\+)?(?:F(?:[1-9]|1[0-2]|1[3-9]|2[0-4])|[A-Z]|[0-9]|NUMPAD[0-9]|NUMPAD(?:MULTIPLY|ADD|SEPARATOR|SUBTRACT|DOT|DIVIDE)|UP|DOWN|LEFT|RIGHT|BACKSPACE|TAB|ENTER|PAUSE|CAPSLOCK|ESCAPE|SPACE|PAGEUP|PAGEDOWN|END|HOME|INSERT|DELETE|OEM_(?:PLUS|COMMA|MINUS|PERIOD|[1-7])|PRINTSCREEN|SCROLLLOCK|NUMLOCK|LEFT(?:SHIFT|CTRL|ALT)|RIGHT(?:SHIFT|CTRL|ALT))$").unwrap()
+    Regex::new(r"^(?:(?:Ctrl|Alt|Shift|Win)\+)?(?:(?:Ctrl|Alt|Shift|Win)\+)?(?:(?:Ctrl|Alt|Shift|Win)\+)?(?:(?:Ctrl|Alt|Shift|Win)\+)?(?:F(?:[1-9]|1[0-2]|1[3-9]|2[0-4])|[A-Z]|[0-9]|NUMPAD[0-9]|NUMPAD(?:MULTIPLY|ADD|SEPARATOR|SUBTRACT|DOT|DIVIDE)|UP|DOWN|LEFT|RIGHT|BACKSPACE|TAB|ENTER|PAUSE|CAPSLOCK|ESCAPE|SPACE|PAGEUP|PAGEDOWN|END|HOME|INSERT|DELETE|OEM_(?:PLUS|COMMA|MINUS|PERIOD|[1-7])|PRINTSCREEN|SCROLLLOCK|NUMLOCK|LEFT(?:SHIFT|CTRL|ALT)|RIGHT(?:SHIFT|CTRL|ALT)|VOLUME(?:UP|DOWN|MUTE)|MEDIA(?:NEXT|PREV|STOP|PLAYPAUSE)|BROWSER(?:BACK|FORWARD|REFRESH|STOP|SEARCH|FAVORITES|HOME))$").unwrap()
 });
 
+/// Actions requested while rendering a workspace's details.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DetailsResponse {
+    /// The workspace was edited and should be marked unsaved.
+    pub changed: bool,
+    /// The hotkey capture dialog should be opened.
+    pub open_hotkey_dialog: bool,
+    /// The "Import Windows" dialog should be opened.
+    pub open_import_dialog: bool,
+    /// The "Add Windows" picker should be opened.
+    pub open_add_windows_dialog: bool,
+    /// Index of a window whose handle should be recaptured.
+    pub recapture_window: Option<usize>,
+    /// A countdown should start, after which the foreground window is added.
+    pub capture_with_countdown: bool,
+    /// The workspace's windows should be gathered onto the current desktop.
+    pub gather_here: bool,
+}
+
 /// Represents a workspace, which groups multiple windows and allows toggling between specific positions.
 ///
 /// # Fields
@@ -28,6 +60,7 @@ static HOTKEY_REGEX: Lazy<Regex> = Lazy::new(|| {
 /// - `hotkey`: An optional hotkey assigned to the workspace for activation.
 /// - `windows`: A list of windows belonging to this workspace.
 /// - `disabled`: A flag indicating whether the workspace is disabled.
+/// - `expanded`: Whether the workspace's section is expanded in the GUI.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Workspace {
     pub name: String,
@@ -37,31 +70,111 @@ pub struct Workspace {
     pub valid: bool,
     #[serde(default)]
     pub rotate: bool,
+    /// Rotate windows through the slots backwards instead of forwards.
+    #[serde(default)]
+    pub rotate_reverse: bool,
+    /// If `false`, rotation stops at the last (or, in reverse, first) step
+    /// instead of starting over.
+    #[serde(default = "default_rotate_wrap")]
+    pub rotate_wrap: bool,
+    /// In rotation, minimize every window except the one rotated into the
+    /// target slot instead of moving it to a home slot.
+    #[serde(default)]
+    pub minimize_others: bool,
     #[serde(skip)]
     pub rotation_offset: usize,
+    /// Set by [`Workspace::validate_workspace`] when an enabled window's home or
+    /// target rectangle lies entirely outside the virtual screen.
+    #[serde(skip)]
+    pub offscreen: bool,
+    /// Number of windows whose handle no longer exists, set by
+    /// [`Workspace::validate_workspace`].
+    #[serde(skip)]
+    pub invalid_windows: usize,
+    #[serde(default = "default_expanded")]
+    pub expanded: bool,
+    /// Pause between restoring a minimized window and positioning it, for apps
+    /// that ignore a move made right after `SW_RESTORE`.
+    #[serde(default)]
+    pub activation_delay_ms: u32,
+    /// If `true`, toggling moves nothing unless every enabled window still exists.
+    #[serde(default)]
+    pub strict_toggle: bool,
+    /// If `false`, toggling repositions windows without activating them, so
+    /// the window being worked in keeps the focus.
+    #[serde(default = "default_steal_focus")]
+    pub steal_focus: bool,
+    /// If `true`, the hotkey only toggles the workspace while one of its
+    /// windows is in the foreground.
+    #[serde(default)]
+    pub require_focus_member: bool,
+}
+
+fn default_expanded() -> bool {
+    true
+}
+
+fn default_rotate_wrap() -> bool {
+    true
+}
+
+fn default_steal_focus() -> bool {
+    true
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            hotkey: None,
+            windows: Vec::new(),
+            disabled: false,
+            valid: false,
+            rotate: false,
+            rotate_reverse: false,
+            rotate_wrap: true,
+            minimize_others: false,
+            rotation_offset: 0,
+            offscreen: false,
+            invalid_windows: 0,
+            expanded: true,
+            activation_delay_ms: 0,
+            strict_toggle: false,
+            steal_focus: true,
+            require_focus_member: false,
+        }
+    }
 }
 
 impl Workspace {
     /// Sets the hotkey for the workspace.
     ///
-    /// Validates the provided hotkey and registers it for the workspace if valid.
+    /// Normalizes the provided hotkey with [`normalize_hotkey`], validates it and
+    /// registers it for the workspace if valid.
     ///
     /// # Arguments
     /// - `hotkey`: The key combination to assign as the workspace hotkey (e.g., "Ctrl+Alt+H").
     ///
+    /// Callers confirm hotkeys without modifiers (see [`hotkey_has_modifier`])
+    /// before calling this, without holding the workspaces lock.
+    ///
     /// # Returns
-    /// - `Ok(())` if the hotkey is valid and successfully set.
-    /// - `Err` with an error message if the hotkey is invalid or fails to register.
+    /// - `Ok(true)` if the hotkey is valid and was set.
+    /// - `Ok(false)` if it equals the current hotkey, which is left unchanged.
+    /// - `Err` with an error message if the hotkey cannot be normalized, is invalid, or
+    ///   fails to register.
     ///   In this case, any previously registered hotkey remains active.
     ///
     /// # Example
     /// ```
     /// let mut workspace = Workspace::new("Example");
-    /// if let Err(e) = workspace.set_hotkey("Ctrl+Shift+P") {
+    /// if let Err(e) = workspace.set_hotkey(&app, "Ctrl+Shift+P") {
     ///     println!("Failed to set hotkey: {}", e);
     /// }
     /// ```
-    pub fn set_hotkey(&mut self, app: &App, hotkey: &str) -> Result<(), String> {
+    pub fn set_hotkey(&mut self, app: &App, hotkey: &str) -> Result<bool, String> {
+        let hotkey = normalize_hotkey(hotkey)?;
+        let hotkey = hotkey.as_str();
         match Hotkey::new(hotkey) {
             Ok(mut new_hotkey) => {
                 // If the new sequence is identical to the current one, nothing to do
@@ -70,23 +183,24 @@ impl Workspace {
                     .as_ref()
                     .is_some_and(|hk| hk.key_sequence == hotkey)
                 {
-                    return Ok(());
+                    return Ok(false);
                 }
 
                 // Pick a unique id for the new hotkey while keeping the old registered
                 let id = {
                     let registered = app.registered_hotkeys.lock().unwrap();
-                    registered.values().max().copied().unwrap_or(0) as i32 + 1
+                    registered.values().map(|r| r.id).max().unwrap_or(0) as i32 + 1
                 };
 
-                // Attempt to register the new hotkey first
-                if new_hotkey.register(app, id) {
+                // Attempt to register the new hotkey first. A workspace that only
+                // reacts while focused may reuse another workspace's hotkey.
+                if new_hotkey.register_or_share(app, id, self.require_focus_member) {
                     // If successful, unregister the old hotkey and store the new one
                     if let Some(ref old_hotkey) = self.hotkey {
                         old_hotkey.unregister(app);
                     }
                     self.hotkey = Some(new_hotkey);
-                    Ok(())
+                    Ok(true)
                 } else {
                     // Registration failed; keep the old hotkey registered
                     Err(format!("Failed to register hotkey: {}", hotkey))
@@ -111,8 +225,11 @@ impl Workspace {
     /// # Behavior
     /// - Checks the `disabled` and `valid` fields of `self`:
     ///   - **Disabled** workspaces: orange text
+    ///   - **Off-screen** workspaces (a window would be moved outside every monitor): yellow
+    ///     text with an "(off-screen)" suffix
     ///   - **Valid** workspaces (i.e., at least one valid window + valid hotkey): green text
     ///   - **Invalid** workspaces: red text
+    /// - Appends a "(⚠ N invalid)" marker when `N` windows need to be recaptured.
     /// - Returns an `egui::RichText` object, which can be displayed in the GUI (e.g., in a collapsible header).
     ///
     /// # Side Effects
@@ -128,13 +245,18 @@ impl Workspace {
     /// - Commonly used in the collapsible headers of each workspace in the UI.
     /// - Helps visually distinguish disabled/invalid workspaces at a glance.
     pub fn get_header_text(&self) -> egui::RichText {
-        let label = match &self.hotkey {
+        let mut label = match &self.hotkey {
             Some(hk) => format!("{} - {}", self.name, hk.key_sequence),
             None => self.name.clone(),
         };
+        if self.invalid_windows > 0 {
+            label.push_str(&format!(" (⚠ {} invalid)", self.invalid_windows));
+        }
 
         if self.disabled {
             egui::RichText::new(label).color(egui::Color32::ORANGE)
+        } else if self.offscreen {
+            egui::RichText::new(format!("{} (off-screen)", label)).color(egui::Color32::YELLOW)
         } else if self.valid {
             egui::RichText::new(label).color(egui::Color32::GREEN)
         } else {
@@ -146,9 +268,31 @@ impl Workspace {
     ///
     /// The `app` reference is required so that the hotkey can be unregistered
     /// when resetting it back to the default state.
-    pub fn render_details(&mut self, ui: &mut egui::Ui, app: &App) -> (bool, bool) {
+    ///
+    /// Returns which edits and follow-up actions the user requested.
+    pub fn render_details(&mut self, ui: &mut egui::Ui, app: &App) -> DetailsResponse {
         let mut changed = false;
         let mut open_dialog = false;
+        let mut open_import = false;
+        let mut open_add_windows = false;
+        let mut recapture_window = None;
+        let mut capture_with_countdown = false;
+        let mut gather_here = false;
+
+        if app.developer_debugging {
+            ui.colored_label(
+                egui::Color32::GRAY,
+                format!(
+                    "rotate: {}, rotation_offset: {}, valid: {}, invalid_windows: {}, offscreen: {}",
+                    self.rotate,
+                    self.rotation_offset,
+                    self.valid,
+                    self.invalid_windows,
+                    self.offscreen
+                ),
+            );
+        }
+
         // Hotkey section
         ui.horizontal(|ui| {
             ui.label("Hotkey:");
@@ -212,18 +356,117 @@ impl Workspace {
             }
             changed = true;
         }
+        if self.rotate {
+            ui.horizontal(|ui| {
+                if ui
+                    .checkbox(&mut self.rotate_reverse, "Reverse")
+                    .on_hover_text("Rotate windows through the slots backwards")
+                    .changed()
+                {
+                    changed = true;
+                }
+                if ui
+                    .checkbox(&mut self.rotate_wrap, "Wrap around")
+                    .on_hover_text("Start over after the last step instead of stopping there")
+                    .changed()
+                {
+                    changed = true;
+                }
+                if ui
+                    .checkbox(&mut self.minimize_others, "Minimize others")
+                    .on_hover_text(
+                        "Minimize every window except the one moved to the target \
+                         instead of moving it home",
+                    )
+                    .changed()
+                {
+                    changed = true;
+                }
+            });
+        }
+
+        if ui
+            .checkbox(
+                &mut self.strict_toggle,
+                "Only toggle when all windows are valid",
+            )
+            .on_hover_text("Move nothing if any enabled window no longer exists")
+            .changed()
+        {
+            changed = true;
+        }
+
+        if ui
+            .checkbox(&mut self.steal_focus, "Focus windows after toggling")
+            .on_hover_text(
+                "Untick to rearrange the windows in the background without \
+                 taking the focus from the window you are working in",
+            )
+            .changed()
+        {
+            changed = true;
+        }
+
+        if ui
+            .checkbox(
+                &mut self.require_focus_member,
+                "Only toggle while one of its windows is focused",
+            )
+            .on_hover_text(
+                "Ignore the hotkey unless the foreground window belongs to this \
+                 workspace, so the same hotkey can serve several workspaces",
+            )
+            .changed()
+        {
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Activation delay:");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut self.activation_delay_ms)
+                        .range(0..=5000)
+                        .suffix(" ms"),
+                )
+                .on_hover_text("Wait this long after restoring a minimized window before moving it")
+                .changed()
+            {
+                changed = true;
+            }
+        });
 
         // Create a copy of windows for iteration
         let windows_len = self.windows.len();
-        let windows: Vec<_> = self.windows.iter_mut().collect();
+        let groups = window_groups(&self.windows);
+        let mut windows: Vec<_> = self.windows.iter_mut().collect();
         let mut window_to_delete = None;
         let mut move_up_index: Option<usize> = None;
         let mut move_down_index: Option<usize> = None;
 
-        for (i, window) in windows.into_iter().enumerate() {
+        let mut render_row = |ui: &mut egui::Ui, i: usize, window: &mut Window| {
             ui.horizontal(|ui| {
+                if ui
+                    .checkbox(&mut window.enabled, "")
+                    .on_hover_text("Include this window when toggling")
+                    .changed()
+                {
+                    changed = true;
+                }
+
+                render_live_position(ui, window);
+
+                if let Some(icon) =
+                    window_icon_texture(ui.ctx(), HWND(window.id as *mut std::ffi::c_void))
+                {
+                    ui.image((icon.id(), egui::vec2(16.0, 16.0)));
+                }
+
                 // Display window title or alias
                 ui.label(window.display_label());
+                if let Some(monitor) = monitor_label(ui.ctx(), window) {
+                    ui.weak(monitor);
+                }
 
                 if i > 0 && ui.button("Move ⏶").clicked() {
                     move_up_index = Some(i);
@@ -240,9 +483,46 @@ impl Workspace {
                     changed = true;
                 }
 
-                // Handle HWND validity and right-click menu for individual windows
                 let exists =
                     unsafe { IsWindow(HWND(window.id as *mut std::ffi::c_void)).as_bool() };
+
+                // Move just this window, leaving the rest of the workspace alone
+                if ui
+                    .add_enabled(exists, egui::Button::new("Home").small())
+                    .on_hover_text("Move only this window to its home position")
+                    .clicked()
+                {
+                    move_single_window(window, window.home_rect(), window.home_state, "home");
+                }
+                if ui
+                    .add_enabled(exists, egui::Button::new("Target").small())
+                    .on_hover_text("Move only this window to its target position")
+                    .clicked()
+                {
+                    move_single_window(
+                        window,
+                        window.target_rect(),
+                        window.target_state,
+                        "target",
+                    );
+                }
+                if ui
+                    .add_enabled(exists, egui::Button::new("Focus").small())
+                    .on_hover_text("Bring this window to the front without moving it")
+                    .clicked()
+                    && !activate_window(HWND(window.id as *mut std::ffi::c_void))
+                {
+                    warn!("Could not bring '{}' to the front.", window.display_label());
+                }
+                if ui
+                    .small_button("Recapture")
+                    .on_hover_text("Focus the window to use and press Enter to rebind this entry")
+                    .clicked()
+                {
+                    recapture_window = Some(i);
+                }
+
+                // Handle HWND validity and right-click menu for individual windows
                 if exists {
                     // Determine current location if debugging is enabled
                     let debug_info = if app.developer_debugging {
@@ -312,6 +592,7 @@ impl Workspace {
                                             // Update the HWND and title
                                             window.id = new_hwnd.0 as usize;
                                             window.title = new_title;
+                                            window.fingerprint = window_fingerprint(new_hwnd);
                                             window.sync_alias_from_title_if_missing();
                                             info!(
                                                 "Force Recaptured window '{}', new HWND: {:?}",
@@ -329,6 +610,12 @@ impl Workspace {
 
                             if ui.button("Swap Home/Target").clicked() {
                                 std::mem::swap(&mut window.home, &mut window.target);
+                                std::mem::swap(
+                                    &mut window.home_monitor,
+                                    &mut window.target_monitor,
+                                );
+                                std::mem::swap(&mut window.home_dpi, &mut window.target_dpi);
+                                std::mem::swap(&mut window.home_state, &mut window.target_state);
                                 ui.memory_mut(|mem| mem.close_popup());
                                 changed = true;
                             }
@@ -345,25 +632,11 @@ impl Workspace {
 
         } else {
                 ui.colored_label(egui::Color32::RED, format!("HWND: {:?}", window.id));
-                if ui.button("Recapture").clicked() {
-                    if let Some("Enter") = listen_for_keys_with_dialog() {
-                        if let Some((new_hwnd, new_title)) = get_active_window() {
-                            // Update the invalid window with the new HWND but retain home/target
-                            window.id = new_hwnd.0 as usize;
-                            window.title = new_title;
-                            window.sync_alias_from_title_if_missing();
-                            info!(
-                                "Recaptured window '{}', new HWND: {:?}",
-                                window.display_label(), new_hwnd
-                                );
-                            changed = true;
-                        } else {
-                            warn!("Recapture canceled or no active window detected.");
-                        }
-                    }
-                }
                 }
             });
+            if app.developer_debugging {
+                render_window_diagnostics(ui, window);
+            }
             ui.horizontal(|ui| {
                 ui.label("Alias (optional):");
                 let mut alias_text = window.alias.clone().unwrap_or_default();
@@ -380,9 +653,161 @@ impl Workspace {
                     };
                     changed = true;
                 }
+                ui.label("Group:");
+                let mut group_text = window.group.clone().unwrap_or_default();
+                let response = ui
+                    .add(
+                        // A fixed id keeps the focus when the row moves to another group.
+                        egui::TextEdit::singleline(&mut group_text)
+                            .id(egui::Id::new(("window_group_name", window.id, i)))
+                            .hint_text("none")
+                            .desired_width(120.0),
+                    )
+                    .on_hover_text(
+                        "Windows sharing a group are listed together and can be moved as one",
+                    );
+                if response.changed() {
+                    window.group = Some(group_text.trim().to_string()).filter(|g| !g.is_empty());
+                    changed = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Desktop:");
+                let selected_text = match window.desktop_index {
+                    Some(index) => format!("Desktop {}", index + 1),
+                    None => "Don't move".to_string(),
+                };
+                egui::ComboBox::from_id_salt(("desktop_index", window.id))
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        if ui
+                            .selectable_value(&mut window.desktop_index, None, "Don't move")
+                            .changed()
+                        {
+                            changed = true;
+                        }
+                        let desktops = get_desktops().unwrap_or_default();
+                        for desktop in desktops {
+                            let Ok(index) = desktop.get_index() else {
+                                continue;
+                            };
+                            if ui
+                                .selectable_value(
+                                    &mut window.desktop_index,
+                                    Some(index),
+                                    format!("Desktop {}", index + 1),
+                                )
+                                .changed()
+                            {
+                                changed = true;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("Virtual desktop to move this window to when toggling");
+                if ui
+                    .checkbox(&mut window.apply_position, "Move")
+                    .on_hover_text("Untick to keep the window where it is and only resize it")
+                    .changed()
+                {
+                    changed = true;
+                }
+                if ui
+                    .checkbox(&mut window.apply_size, "Resize")
+                    .on_hover_text("Untick to keep the window's size and only reposition it")
+                    .changed()
+                {
+                    changed = true;
+                }
+                ui.label("Stack order:");
+                if ui
+                    .add(egui::DragValue::new(&mut window.z_order).range(0..=99))
+                    .on_hover_text("0 is in front and receives focus after toggling")
+                    .changed()
+                {
+                    changed = true;
+                }
+                if ui
+                    .checkbox(&mut window.topmost, "Always on top")
+                    .on_hover_text("Pin this window above other windows when the workspace toggles")
+                    .changed()
+                {
+                    changed = true;
+                }
+                ui.label("Opacity:");
+                if ui
+                    .add(egui::Slider::new(
+                        &mut window.opacity,
+                        MIN_WINDOW_OPACITY..=u8::MAX,
+                    ))
+                    .on_hover_text(
+                        "Applied when the workspace toggles; 255 leaves the window untouched. \
+                         Some windows do not support transparency.",
+                    )
+                    .changed()
+                {
+                    changed = true;
+                }
             });
             // Render controls for individual window
             render_window_controls(ui, window, &mut changed);
+        };
+
+        if groups.iter().all(Option::is_none) {
+            for (i, window) in windows.iter_mut().enumerate() {
+                render_row(ui, i, window);
+            }
+        } else {
+            for group in &groups {
+                let indices: Vec<usize> = (0..windows.len())
+                    .filter(|&i| windows[i].group == *group)
+                    .collect();
+                let label = group.as_deref().unwrap_or("Ungrouped");
+                egui::CollapsingHeader::new(format!("{} ({})", label, indices.len()))
+                    .id_salt(("window_group", label))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button("Send Group Home")
+                                .on_hover_text("Move the enabled windows of this group home")
+                                .clicked()
+                            {
+                                for window in indices.iter().map(|&i| &*windows[i]) {
+                                    if window.enabled {
+                                        move_single_window(
+                                            window,
+                                            window.home_rect(),
+                                            window.home_state,
+                                            "home",
+                                        );
+                                    }
+                                }
+                            }
+                            if ui
+                                .button("Send Group to Target")
+                                .on_hover_text(
+                                    "Move the enabled windows of this group to their targets",
+                                )
+                                .clicked()
+                            {
+                                for window in indices.iter().map(|&i| &*windows[i]) {
+                                    if window.enabled {
+                                        move_single_window(
+                                            window,
+                                            window.target_rect(),
+                                            window.target_state,
+                                            "target",
+                                        );
+                                    }
+                                }
+                            }
+                        });
+                        for &i in &indices {
+                            render_row(ui, i, windows[i]);
+                        }
+                    });
+            }
         }
 
         if let Some(i) = move_up_index {
@@ -402,22 +827,188 @@ impl Workspace {
         }
 
         // Capture active window button
-        if ui.button("Capture Active Window").clicked() {
-            if let Some(("Enter", hwnd, title)) = listen_for_keys_with_dialog_and_window() {
-                let rect = get_window_position(hwnd).unwrap_or((0, 0, 800, 600));
-                self.windows.push(Window {
-                    id: hwnd.0 as usize,
-                    title,
-                    alias: None,
-                    home: rect,
-                    target: rect,
-                    valid: true,
+        ui.horizontal(|ui| {
+            if ui.button("Capture Active Window").clicked() {
+                if let Some(("Enter", hwnd, title)) = listen_for_keys_with_dialog_and_window() {
+                    self.windows.push(Window::capture(hwnd, title));
+                    changed = true;
+                }
+            }
+            if ui
+                .add_enabled(
+                    app.capture_countdown.is_none(),
+                    egui::Button::new(format!("Capture ({}s)", app.capture_countdown_secs)),
+                )
+                .on_hover_text(
+                    "Capture whichever window is in the foreground when the countdown ends, \
+                     for windows that lose focus when a dialog appears",
+                )
+                .clicked()
+            {
+                capture_with_countdown = true;
+            }
+        });
+
+        if ui
+            .button("Add Windows...")
+            .on_hover_text("Pick several open windows to add at their current positions")
+            .clicked()
+        {
+            open_add_windows = true;
+        }
+
+        if ui
+            .button("Import Windows...")
+            .on_hover_text("Copy windows captured in another workspace")
+            .clicked()
+        {
+            open_import = true;
+        }
+
+        if ui
+            .button("Capture Stacking Order")
+            .on_hover_text("Set each window's stack order from how the windows overlap right now")
+            .clicked()
+        {
+            self.capture_stacking_order();
+            changed = true;
+        }
+
+        if ui
+            .button("Gather Here")
+            .on_hover_text("Move this workspace's windows onto the current virtual desktop")
+            .clicked()
+        {
+            gather_here = true;
+        }
+
+        if ui
+            .button("Preview Layout")
+            .on_hover_text("Show where each window's home and target positions are on screen")
+            .clicked()
+        {
+            show_layout_preview(self);
+        }
+
+        ui.horizontal(|ui| {
+            let mode_id = ui.id().with("tile_mode");
+            let mut mode = ui.data_mut(|data| *data.get_temp_mut_or(mode_id, TileMode::Columns));
+            egui::ComboBox::from_id_salt(mode_id)
+                .selected_text(mode.label())
+                .width(80.0)
+                .show_ui(ui, |ui| {
+                    for option in TileMode::ALL {
+                        ui.selectable_value(&mut mode, option, option.label());
+                    }
                 });
+            ui.data_mut(|data| data.insert_temp(mode_id, mode));
+            if ui
+                .button("Apply to All Windows")
+                .on_hover_text("Tile the targets of all enabled windows across the primary monitor")
+                .clicked()
+                && self.tile_window_targets(mode)
+            {
                 changed = true;
             }
+        });
+
+        DetailsResponse {
+            changed,
+            open_hotkey_dialog: open_dialog,
+            open_import_dialog: open_import,
+            open_add_windows_dialog: open_add_windows,
+            recapture_window,
+            capture_with_countdown,
+            gather_here,
+        }
+    }
+
+    /// Sets each window's `z_order` from the current desktop Z-order.
+    ///
+    /// Windows that no longer exist keep their position behind all live ones.
+    pub fn capture_stacking_order(&mut self) {
+        let hwnds: Vec<HWND> = self
+            .windows
+            .iter()
+            .map(|window| HWND(window.id as *mut c_void))
+            .collect();
+        let positions = current_stacking_positions(&hwnds);
+        let mut order: Vec<usize> = (0..self.windows.len()).collect();
+        order.sort_by_key(|&i| {
+            (
+                positions[i].is_none(),
+                positions[i],
+                self.windows[i].z_order,
+            )
+        });
+        for (z_order, i) in order.into_iter().enumerate() {
+            self.windows[i].z_order = z_order;
+        }
+    }
+
+    /// Rewrites the targets of all enabled windows so they tile the primary
+    /// monitor's work area in the given `mode`.
+    ///
+    /// Returns `false` if there is nothing to tile or no monitor was found.
+    pub fn tile_window_targets(&mut self, mode: TileMode) -> bool {
+        let Some(primary) = list_monitors().into_iter().find(|m| m.primary) else {
+            warn!("No primary monitor found to tile '{}' across.", self.name);
+            return false;
+        };
+        let count = self.windows.iter().filter(|w| w.enabled).count();
+        if count == 0 {
+            return false;
+        }
+        let rects = tile_targets(count, primary.work_area, mode);
+        for (window, rect) in self.windows.iter_mut().filter(|w| w.enabled).zip(rects) {
+            window.target_layout = TargetLayout::Absolute;
+            window.target = rect;
+            window.target_monitor = anchor_for_rect(rect);
+            window.target_dpi = Some(primary.dpi);
+            window.target_state = WindowState::Normal;
+        }
+        info!(
+            "Tiled {} window target(s) in workspace '{}' as {}.",
+            count,
+            self.name,
+            mode.label().to_lowercase()
+        );
+        true
+    }
+
+    /// Returns `true` if `hwnd` is the handle of one of this workspace's windows.
+    pub fn contains_window(&self, hwnd: isize) -> bool {
+        self.windows.iter().any(|w| w.id as isize == hwnd)
+    }
+
+    /// Returns a copy of this workspace suitable for inserting alongside it.
+    ///
+    /// The copy's name gets a " (copy)" suffix, its hotkey is cleared so it does
+    /// not collide with the original's registration, and rotation starts over.
+    pub fn duplicated(&self) -> Workspace {
+        Workspace {
+            name: format!("{} (copy)", self.name),
+            hotkey: None,
+            rotation_offset: 0,
+            ..self.clone()
         }
+    }
 
-        (changed, open_dialog)
+    /// Copies the windows at `indices` from `other` into this workspace.
+    ///
+    /// The copies keep their title, alias, positions and placement settings but
+    /// start out invalid so they can be bound to live windows with Recapture.
+    /// Indices that are out of range are ignored.
+    pub fn import_windows_from(&mut self, other: &Workspace, indices: &[usize]) {
+        for &index in indices {
+            if let Some(window) = other.windows.get(index) {
+                self.windows.push(Window {
+                    id: 0,
+                    valid: false,
+                    ..window.clone()
+                });
+            }
+        }
     }
 
     /// Attaches a context menu to a UI widget.
@@ -506,26 +1097,50 @@ impl Workspace {
     ///
     /// # Dependencies
     /// - Relies on `is_valid_key_combo` for hotkey validation.
-    /// - Uses the Win32 API `IsWindow` to check window validity.
+    /// - Uses the Win32 API `IsWindow` to check window validity, and compares
+    ///   fingerprints read at most once per [`LIVE_FINGERPRINT_REFRESH`] (see
+    ///   [`Window::matches_live_cached`]).
     ///
     /// # Parameters
     /// - No parameters. Operates directly on the instance of the `Workspace`.
     ///
     /// # Side Effects
     /// - Updates the `valid` field of the `Workspace` struct.
+    /// - Sets `offscreen` if any enabled window's home or target rectangle falls
+    ///   entirely outside the virtual screen spanned by all monitors.
+    /// - Repairs `rotation_offset` if windows were removed (see
+    ///   [`Workspace::repair_rotation_offset`]).
     ///
     /// # Notes
     /// - This function should be called whenever the state of a workspace changes (e.g., hotkey or windows are modified).
     /// - If the workspace is disabled, validation is skipped and the workspace is marked invalid.
     pub fn validate_workspace(&mut self) {
+        self.repair_rotation_offset();
+        let screen = virtual_screen_rect();
+        let was_offscreen = self.offscreen;
+        self.offscreen = self.windows.iter().any(|window| {
+            window.enabled
+                && (!rects_intersect(screen, window.home_rect())
+                    || !rects_intersect(screen, window.target_rect()))
+        });
+        if self.offscreen && !was_offscreen {
+            warn!(
+                "Workspace '{}' has a window positioned outside all monitors.",
+                self.name
+            );
+        }
+
         let mut any_valid_window = false;
+        self.invalid_windows = 0;
         for window in self.windows.iter_mut() {
             window.sync_alias_from_title_if_missing();
             let hwnd = HWND(window.id as *mut c_void);
-            let is_valid = unsafe { IsWindow(hwnd).as_bool() };
+            let is_valid = window.matches_live_cached(hwnd);
             window.valid = is_valid;
             if is_valid {
                 any_valid_window = true;
+            } else {
+                self.invalid_windows += 1;
             }
         }
 
@@ -541,127 +1156,668 @@ impl Workspace {
 
         self.valid = hotkey_valid && any_valid_window;
     }
+
+    /// Keeps `rotation_offset` below the number of windows (or at `0` when there
+    /// are none), e.g. after windows were removed mid-rotation.
+    ///
+    /// Returns `true` and logs the change if the offset had to be repaired.
+    pub fn repair_rotation_offset(&mut self) -> bool {
+        let repaired = self
+            .rotation_offset
+            .checked_rem(self.windows.len())
+            .unwrap_or(0);
+        if repaired == self.rotation_offset {
+            return false;
+        }
+        info!(
+            "Repaired rotation offset of workspace '{}' from {} to {} ({} windows).",
+            self.name,
+            self.rotation_offset,
+            repaired,
+            self.windows.len()
+        );
+        self.rotation_offset = repaired;
+        true
+    }
 }
-/// Presents egui UI elements for configuring **one** `Window`’s positioning data:
-/// its **Home** and **Target** coordinates, plus actions to **capture** or **move** the window.
-///
-/// # Behavior
-/// - Displays two horizontal rows, one for the `home` position and one for the `target` position.
-/// - In each row:
-///   - Shows editable numeric fields (`DragValue`) for `x`, `y`, `width (w)`, and `height (h)`.
-///   - Offers a **“Capture”** button to read the current on-screen position via
-///     [`get_window_position`](../../window_manager/fn.get_window_position.html).
-///   - Offers a **“Move to ...”** button that calls [`move_window`](../../window_manager/fn.move_window.html)
-///     to reposition the window immediately.
-/// - Any failures (e.g., the window is invalid or moving fails) are logged as warnings.
-///
-/// # Side Effects
-/// - Potentially moves the window on the user’s desktop if “Move” is clicked.
-/// - Updates the `home` or `target` fields in `window` when “Capture” is used.
-/// - Logs messages about actions taken (`info!`, `warn!`) via the `log` crate.
+/// Builds a workspace named `name` holding every capturable window, with each
+/// window's home and target set to where it is now.
 ///
-/// # Example
-/// ```rust
-/// egui::CentralPanel::default().show(ctx, |ui| {
-///     render_window_controls(ui, &mut my_window);
-/// });
-/// ```
+/// The window `exclude` (normally Multi Manager's own) is left out.
+pub fn snapshot_workspace(name: String, exclude: Option<isize>) -> Workspace {
+    let windows = list_capturable_windows()
+        .into_iter()
+        .filter(|(hwnd, _)| Some(*hwnd) != exclude)
+        .map(|(hwnd, title)| Window::capture(HWND(hwnd as *mut c_void), title))
+        .collect();
+    Workspace {
+        name,
+        windows,
+        ..Default::default()
+    }
+}
+
+/// Scales every stored position in `workspaces` from a screen of size `from`
+/// to one of size `to` (both `(width, height)`), e.g. when moving a setup to a
+/// laptop with a smaller screen.
 ///
-/// # Notes
-/// - This function is called inside `render_details(...)` to iterate over each `Window` in a `Workspace`.
-/// - Relies on Win32 calls under the hood to interact with actual OS-level windows (via `HWND`).
-pub fn render_window_controls(ui: &mut egui::Ui, window: &mut Window, changed: &mut bool) {
-    // Home position controls
-    ui.horizontal(|ui| {
-        ui.label("Home:");
-        if ui
-            .add(egui::DragValue::new(&mut window.home.0).prefix("x: "))
-            .changed()
+/// Home and target rectangles, their monitor-relative anchors and centered
+/// targets are scaled; relative targets already adapt to their monitor and are
+/// left alone. Returns the rescaled workspaces and the number of windows whose
+/// positions changed.
+pub fn rescale_workspaces(
+    mut workspaces: Vec<Workspace>,
+    from: (i32, i32),
+    to: (i32, i32),
+) -> (Vec<Workspace>, usize) {
+    let scale = |rect: (i32, i32, i32, i32)| scale_rect_between(rect, from, to);
+    let mut changed = 0;
+    for window in workspaces.iter_mut().flat_map(|ws| ws.windows.iter_mut()) {
+        let before = (window.home, window.target, window.target_layout.clone());
+        window.home = scale(window.home);
+        window.target = scale(window.target);
+        for anchor in [&mut window.home_monitor, &mut window.target_monitor]
+            .into_iter()
+            .flatten()
         {
-            *changed = true;
+            anchor.rect = scale(anchor.rect);
         }
-        if ui
-            .add(egui::DragValue::new(&mut window.home.1).prefix("y: "))
-            .changed()
-        {
-            *changed = true;
+        if let TargetLayout::Centered { center } = &mut window.target_layout {
+            let (x, y, _, _) = scale((center.0, center.1, 0, 0));
+            *center = (x, y);
         }
-        if ui
-            .add(egui::DragValue::new(&mut window.home.2).prefix("w: "))
-            .changed()
-        {
-            *changed = true;
+        if before != (window.home, window.target, window.target_layout.clone()) {
+            changed += 1;
         }
-        if ui
-            .add(egui::DragValue::new(&mut window.home.3).prefix("h: "))
-            .changed()
-        {
-            *changed = true;
+    }
+    (workspaces, changed)
+}
+
+/// Lowest opacity offered in the GUI, so a window cannot be made invisible by accident.
+const MIN_WINDOW_OPACITY: u8 = 25;
+
+/// Returns the distinct groups of `windows` in order of first appearance, with
+/// `None` standing for windows that belong to no group.
+fn window_groups(windows: &[Window]) -> Vec<Option<String>> {
+    let mut groups: Vec<Option<String>> = Vec::new();
+    for window in windows {
+        if !groups.contains(&window.group) {
+            groups.push(window.group.clone());
         }
-        if ui.button("Capture Home").clicked() {
-            if let Ok((x, y, w, h)) = get_window_position(HWND(window.id as *mut _)) {
-                window.home = (x, y, w, h);
-                *changed = true;
-            }
+    }
+    groups
+}
+
+/// How long a window's live position is cached before it is queried again.
+const LIVE_POSITION_REFRESH: Duration = Duration::from_millis(500);
+
+/// Where a window currently sits relative to its stored positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LivePosition {
+    Home,
+    Target,
+    Elsewhere,
+    Missing,
+}
+
+/// Monitors and per-window monitor descriptions, valid for one egui pass.
+/// How long a window's live fingerprint is cached before it is read again.
+const LIVE_FINGERPRINT_REFRESH: Duration = Duration::from_secs(1);
+
+type FingerprintCache = HashMap<isize, (Instant, Option<WindowFingerprint>)>;
+
+static LIVE_FINGERPRINTS: Lazy<Mutex<FingerprintCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the fingerprint of `hwnd`, read at most once per
+/// [`LIVE_FINGERPRINT_REFRESH`].
+fn cached_window_fingerprint(hwnd: HWND) -> Option<WindowFingerprint> {
+    let now = Instant::now();
+    let mut cache = LIVE_FINGERPRINTS.lock().unwrap();
+    if let Some((checked_at, fingerprint)) = cache.get(&(hwnd.0 as isize)) {
+        if now - *checked_at < LIVE_FINGERPRINT_REFRESH {
+            return fingerprint.clone();
         }
-        if ui.button("Move to Home").clicked() {
-            if let Err(e) = move_window(
-                HWND(window.id as *mut _),
-                window.home.0,
-                window.home.1,
-                window.home.2,
-                window.home.3,
-            ) {
-                warn!("Failed to move window to home: {}", e);
+    }
+    cache.retain(|_, (checked_at, _)| now - *checked_at < LIVE_FINGERPRINT_REFRESH);
+    let fingerprint = window_fingerprint(hwnd);
+    cache.insert(hwnd.0 as isize, (now, fingerprint.clone()));
+    fingerprint
+}
+
+static MONITOR_LABELS: Lazy<Mutex<MonitorLabelCache>> =
+    Lazy::new(|| Mutex::new(MonitorLabelCache::default()));
+
+#[derive(Default)]
+struct MonitorLabelCache {
+    pass: u64,
+    monitors: Vec<MonitorDetails>,
+    labels: HashMap<usize, Option<String>>,
+}
+
+/// Describes the monitor the live `window` is on, or `None` if it does not exist.
+///
+/// Monitors are enumerated at most once per frame and each window is looked up
+/// once per frame, however many times it is drawn.
+fn monitor_label(ctx: &egui::Context, window: &Window) -> Option<String> {
+    let pass = ctx.cumulative_pass_nr();
+    let mut cache = MONITOR_LABELS.lock().unwrap();
+    if cache.pass != pass || cache.monitors.is_empty() {
+        *cache = MonitorLabelCache {
+            pass,
+            monitors: list_monitors(),
+            labels: HashMap::new(),
+        };
+    }
+    let MonitorLabelCache {
+        monitors, labels, ..
+    } = &mut *cache;
+    labels
+        .entry(window.id)
+        .or_insert_with(|| {
+            let hwnd = HWND(window.id as *mut c_void);
+            if !unsafe { IsWindow(hwnd).as_bool() } {
+                return None;
             }
+            monitor_for_window(hwnd).map(|monitor| describe_monitor(monitors, &monitor.device))
+        })
+        .clone()
+}
+
+/// Compares the live window with its home and target placements, using the same
+/// state-aware check as [`are_all_windows_at_home`].
+fn live_position(window: &Window) -> LivePosition {
+    let hwnd = HWND(window.id as *mut c_void);
+    if !unsafe { IsWindow(hwnd).as_bool() } {
+        return LivePosition::Missing;
+    }
+    let (x, y, w, h) = applied_rect(window, hwnd, window.home_rect());
+    if is_window_at_placement(hwnd, x, y, w, h, window.home_state) {
+        return LivePosition::Home;
+    }
+    let (x, y, w, h) = applied_rect(window, hwnd, window.target_rect());
+    if is_window_at_placement(hwnd, x, y, w, h, window.target_state) {
+        return LivePosition::Target;
+    }
+    LivePosition::Elsewhere
+}
+
+/// Shows the live handle, rectangle and show state of `window` next to its
+/// resolved home and target, for the developer debugging setting.
+fn render_window_diagnostics(ui: &mut egui::Ui, window: &Window) {
+    let hwnd = HWND(window.id as *mut c_void);
+    let text = if unsafe { IsWindow(hwnd).as_bool() } {
+        let rect = get_window_position(hwnd)
+            .map(|rect| format!("{:?}", rect))
+            .unwrap_or_else(|e| format!("unavailable ({})", e));
+        let position = match live_position(window) {
+            LivePosition::Home => "home",
+            LivePosition::Target => "target",
+            LivePosition::Elsewhere => "neither",
+            LivePosition::Missing => "missing",
+        };
+        format!(
+            "live HWND {:#x}: rect {}, state {}, matches {}; home {:?} {}, target {:?} {}",
+            window.id,
+            rect,
+            get_window_state(hwnd).label(),
+            position,
+            window.home_rect(),
+            window.home_state.label(),
+            window.target_rect(),
+            window.target_state.label()
+        )
+    } else {
+        format!("HWND {:#x} no longer exists", window.id)
+    };
+    ui.colored_label(egui::Color32::GRAY, text);
+}
+
+/// Draws a colored dot showing whether `window` is at home, at target, or neither.
+///
+/// Only expanded workspaces render their details, and results are cached in egui's
+/// temporary memory for [`LIVE_POSITION_REFRESH`], so the Win32 queries run at most
+/// twice a second per visible window.
+fn render_live_position(ui: &mut egui::Ui, window: &Window) {
+    let id = egui::Id::new(("live_position", window.id));
+    let now = Instant::now();
+    let cached = ui.data(|data| data.get_temp::<(Instant, LivePosition)>(id));
+    let position = match cached {
+        Some((checked_at, position)) if now - checked_at < LIVE_POSITION_REFRESH => position,
+        _ => {
+            let position = live_position(window);
+            ui.data_mut(|data| data.insert_temp(id, (now, position)));
+            position
         }
-    });
+    };
+
+    let (color, hover) = match position {
+        LivePosition::Home => (egui::Color32::LIGHT_BLUE, "At home position"),
+        LivePosition::Target => (egui::Color32::GREEN, "At target position"),
+        LivePosition::Elsewhere => (egui::Color32::YELLOW, "Neither at home nor at target"),
+        LivePosition::Missing => (egui::Color32::GRAY, "Window not found"),
+    };
+    ui.colored_label(color, "●").on_hover_text(hover);
+}
 
-    // Target position controls
+/// Moves a single `window` to `rect` without touching the rest of its workspace.
+///
+/// The HWND is checked with `IsWindow` first; failures are logged with `warn!`
+/// in the same way as [`toggle_workspace_windows`].
+fn move_single_window(
+    window: &Window,
+    rect: (i32, i32, i32, i32),
+    state: WindowState,
+    position_name: &str,
+) {
+    let hwnd = HWND(window.id as *mut c_void);
+    if !unsafe { IsWindow(hwnd).as_bool() } {
+        warn!("Skipping invalid window '{}'.", window.title);
+        return;
+    }
+
+    let rect = applied_rect(window, hwnd, rect);
+    if let Err(e) = move_window_with_state(hwnd, rect.0, rect.1, rect.2, rect.3, state) {
+        warn!("Failed to move window '{}': {}", window.title, e);
+    } else {
+        info!(
+            "Moved window '{}' to {} position: {:?}",
+            window.title, position_name, rect
+        );
+    }
+}
+
+/// Presents egui UI elements for configuring **one** `Window`’s positioning data:
+/// its **Home** and **Target** coordinates, plus actions to **capture** or **move** the window.
+///
+/// # Behavior
+/// - Displays two horizontal rows, one for the `home` position and one for the `target` position.
+/// - In each row:
+///   - Shows editable numeric fields (`DragValue`) for `x`, `y`, `width (w)`, and `height (h)`.
+///   - Offers a **“Capture”** button to read the current on-screen position via
+///     [`get_window_position`](../../window_manager/fn.get_window_position.html).
+///   - Offers a **“Move to ...”** button that calls [`move_window`](../../window_manager/fn.move_window.html)
+///     to reposition the window immediately.
+/// - Any failures (e.g., the window is invalid or moving fails) are logged as warnings.
+///
+/// # Side Effects
+/// - Potentially moves the window on the user’s desktop if “Move” is clicked.
+/// - Updates the `home` or `target` fields in `window` when “Capture” is used.
+/// - Logs messages about actions taken (`info!`, `warn!`) via the `log` crate.
+///
+/// # Example
+/// ```rust
+/// egui::CentralPanel::default().show(ctx, |ui| {
+///     render_window_controls(ui, &mut my_window);
+/// });
+/// ```
+///
+/// # Notes
+/// - This function is called inside `render_details(...)` to iterate over each `Window` in a `Workspace`.
+/// - Relies on Win32 calls under the hood to interact with actual OS-level windows (via `HWND`).
+pub fn render_window_controls(ui: &mut egui::Ui, window: &mut Window, changed: &mut bool) {
+    let mut home_edited = false;
+    let mut target_edited = false;
+
+    // Home position controls
     ui.horizontal(|ui| {
-        ui.label("Target:");
+        ui.label("Home:");
         if ui
-            .add(egui::DragValue::new(&mut window.target.0).prefix("x: "))
+            .add(egui::DragValue::new(&mut window.home.0).prefix("x: "))
             .changed()
         {
             *changed = true;
+            home_edited = true;
         }
         if ui
-            .add(egui::DragValue::new(&mut window.target.1).prefix("y: "))
+            .add(egui::DragValue::new(&mut window.home.1).prefix("y: "))
             .changed()
         {
             *changed = true;
+            home_edited = true;
         }
         if ui
-            .add(egui::DragValue::new(&mut window.target.2).prefix("w: "))
+            .add(egui::DragValue::new(&mut window.home.2).prefix("w: "))
             .changed()
         {
             *changed = true;
+            home_edited = true;
         }
         if ui
-            .add(egui::DragValue::new(&mut window.target.3).prefix("h: "))
+            .add(egui::DragValue::new(&mut window.home.3).prefix("h: "))
             .changed()
         {
             *changed = true;
+            home_edited = true;
+        }
+        egui::ComboBox::from_id_salt(("home_state", window.id))
+            .selected_text(window.home_state.label())
+            .width(90.0)
+            .show_ui(ui, |ui| {
+                for state in [
+                    WindowState::Normal,
+                    WindowState::Minimized,
+                    WindowState::Maximized,
+                ] {
+                    if ui
+                        .selectable_value(&mut window.home_state, state, state.label())
+                        .changed()
+                    {
+                        *changed = true;
+                    }
+                }
+            });
+        if ui.button("Capture Home").clicked() && window.capture_home() {
+            *changed = true;
+        }
+        if ui.button("Move to Home").clicked() {
+            let (x, y, w, h) = window.home_rect();
+            if let Err(e) =
+                move_window_with_state(HWND(window.id as *mut _), x, y, w, h, window.home_state)
+            {
+                warn!("Failed to move window to home: {}", e);
+            }
+        }
+    });
+
+    // Target layout selector
+    ui.horizontal(|ui| {
+        ui.label("Target layout:");
+        let relative = matches!(window.target_layout, TargetLayout::Relative { .. });
+        let centered = matches!(window.target_layout, TargetLayout::Centered { .. });
+        if ui.radio(!relative && !centered, "Absolute").clicked() && (relative || centered) {
+            window.set_target_relative(false);
+            *changed = true;
+        }
+        if ui
+            .radio(relative, "Relative")
+            .on_hover_text("Store the target as fractions of its monitor's work area")
+            .clicked()
+            && !relative
+        {
+            window.set_target_relative(true);
+            *changed = true;
         }
-        if ui.button("Capture Target").clicked() {
-            if let Ok((x, y, w, h)) = get_window_position(HWND(window.id as *mut _)) {
-                window.target = (x, y, w, h);
+        if ui
+            .radio(centered, "Center point")
+            .on_hover_text("Store the target as the point the window is centered on")
+            .clicked()
+            && !centered
+        {
+            window.set_target_centered();
+            *changed = true;
+        }
+    });
+
+    // Target position controls
+    ui.horizontal(|ui| {
+        ui.label("Target:");
+        if let TargetLayout::Relative { fractions, .. } = &mut window.target_layout {
+            for (value, prefix) in [
+                (&mut fractions.0, "x: "),
+                (&mut fractions.1, "y: "),
+                (&mut fractions.2, "w: "),
+                (&mut fractions.3, "h: "),
+            ] {
+                if ui
+                    .add(
+                        egui::DragValue::new(value)
+                            .prefix(prefix)
+                            .speed(0.005)
+                            .range(0.0..=1.0)
+                            .fixed_decimals(3),
+                    )
+                    .changed()
+                {
+                    *changed = true;
+                }
+            }
+        } else if let TargetLayout::Centered { center } = &mut window.target_layout {
+            for (value, prefix) in [
+                (&mut center.0, "cx: "),
+                (&mut center.1, "cy: "),
+                (&mut window.target.2, "w: "),
+                (&mut window.target.3, "h: "),
+            ] {
+                if ui.add(egui::DragValue::new(value).prefix(prefix)).changed() {
+                    *changed = true;
+                }
+            }
+        } else {
+            if ui
+                .add(egui::DragValue::new(&mut window.target.0).prefix("x: "))
+                .changed()
+            {
+                *changed = true;
+                target_edited = true;
+            }
+            if ui
+                .add(egui::DragValue::new(&mut window.target.1).prefix("y: "))
+                .changed()
+            {
+                *changed = true;
+                target_edited = true;
+            }
+            if ui
+                .add(egui::DragValue::new(&mut window.target.2).prefix("w: "))
+                .changed()
+            {
                 *changed = true;
+                target_edited = true;
+            }
+            if ui
+                .add(egui::DragValue::new(&mut window.target.3).prefix("h: "))
+                .changed()
+            {
+                *changed = true;
+                target_edited = true;
             }
         }
+        egui::ComboBox::from_id_salt(("target_state", window.id))
+            .selected_text(window.target_state.label())
+            .width(90.0)
+            .show_ui(ui, |ui| {
+                for state in [
+                    WindowState::Normal,
+                    WindowState::Minimized,
+                    WindowState::Maximized,
+                ] {
+                    if ui
+                        .selectable_value(&mut window.target_state, state, state.label())
+                        .changed()
+                    {
+                        *changed = true;
+                    }
+                }
+            });
+        if ui.button("Capture Target").clicked() && window.capture_target() {
+            *changed = true;
+        }
         if ui.button("Move to Target").clicked() {
-            if let Err(e) = move_window(
-                HWND(window.id as *mut _),
-                window.target.0,
-                window.target.1,
-                window.target.2,
-                window.target.3,
-            ) {
+            let hwnd = HWND(window.id as *mut _);
+            let result = match window.target_layout {
+                // Without a stored size, center the window at its current size.
+                TargetLayout::Centered { center }
+                    if !window.apply_size && window.target_state == WindowState::Normal =>
+                {
+                    move_window_centered_at(hwnd, center.0, center.1)
+                }
+                _ => {
+                    let (x, y, w, h) = window.target_rect();
+                    move_window_with_state(hwnd, x, y, w, h, window.target_state)
+                }
+            };
+            if let Err(e) = result {
                 warn!("Failed to move window to target: {}", e);
             }
         }
     });
+
+    // Quick layouts computed from the monitor the window is on
+    ui.horizontal(|ui| {
+        let selector_id = egui::Id::new(("snap_to_target", window.id));
+        let mut to_target = ui.data_mut(|data| *data.get_temp_mut_or(selector_id, true));
+        ui.label("Snap");
+        ui.selectable_value(&mut to_target, false, "Home");
+        ui.selectable_value(&mut to_target, true, "Target");
+        ui.data_mut(|data| data.insert_temp(selector_id, to_target));
+        ui.label("to:");
+
+        for snap in SnapPosition::ALL {
+            if ui.small_button(snap.label()).clicked() {
+                let current = if to_target {
+                    window.target_rect()
+                } else {
+                    window.home_rect()
+                };
+                let hwnd = HWND(window.id as *mut c_void);
+                let monitor = if unsafe { IsWindow(hwnd).as_bool() } {
+                    monitor_for_window(hwnd)
+                } else {
+                    monitor_for_rect(current)
+                };
+                let Some(monitor) = monitor else {
+                    warn!("No monitor found to snap '{}' to.", window.title);
+                    continue;
+                };
+                let rect = snap.rect_in(monitor.work_area, (current.2, current.3));
+                if to_target {
+                    let layout = std::mem::take(&mut window.target_layout);
+                    window.target = rect;
+                    window.target_monitor = anchor_for_rect(rect);
+                    window.target_dpi = Some(monitor.dpi);
+                    match layout {
+                        TargetLayout::Absolute => {}
+                        TargetLayout::Relative { .. } => window.set_target_relative(true),
+                        TargetLayout::Centered { .. } => window.set_target_centered(),
+                    }
+                } else {
+                    window.home = rect;
+                    home_edited = true;
+                }
+                *changed = true;
+            }
+        }
+    });
+
+    // Manual edits are made in absolute coordinates, so re-anchor them to
+    // whichever monitor the edited rectangle now lies on.
+    if home_edited {
+        window.home_monitor = anchor_for_rect(window.home);
+        window.home_dpi = monitor_for_rect(window.home).map(|monitor| monitor.dpi);
+    }
+    if target_edited {
+        window.target_monitor = anchor_for_rect(window.target);
+        window.target_dpi = monitor_for_rect(window.target).map(|monitor| monitor.dpi);
+    }
+}
+
+/// How [`tile_targets`] arranges windows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileMode {
+    Columns,
+    Rows,
+    Grid,
+}
+
+impl TileMode {
+    pub const ALL: [TileMode; 3] = [TileMode::Columns, TileMode::Rows, TileMode::Grid];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TileMode::Columns => "Columns",
+            TileMode::Rows => "Rows",
+            TileMode::Grid => "Grid",
+        }
+    }
+}
+
+/// Splits `work_area` into `count` tiles, left to right and top to bottom.
+///
+/// `Grid` uses the smallest square-ish grid that fits `count` tiles. Tile edges
+/// are computed from the work area's edges so the tiles cover it without gaps
+/// even when it does not divide evenly.
+pub fn tile_targets(
+    count: usize,
+    work_area: (i32, i32, i32, i32),
+    mode: TileMode,
+) -> Vec<(i32, i32, i32, i32)> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let (columns, rows) = match mode {
+        TileMode::Columns => (count, 1),
+        TileMode::Rows => (1, count),
+        TileMode::Grid => {
+            let columns = (count as f64).sqrt().ceil() as usize;
+            (columns, count.div_ceil(columns))
+        }
+    };
+    let (x, y, w, h) = work_area;
+    let edge = |start: i32, length: i32, index: usize, parts: usize| {
+        start + (length as i64 * index as i64 / parts as i64) as i32
+    };
+    (0..count)
+        .map(|i| {
+            let (column, row) = (i % columns, i / columns);
+            let left = edge(x, w, column, columns);
+            let top = edge(y, h, row, rows);
+            let right = edge(x, w, column + 1, columns);
+            let bottom = edge(y, h, row + 1, rows);
+            (left, top, right - left, bottom - top)
+        })
+        .collect()
+}
+
+/// Preset positions offered by the snap buttons in [`render_window_controls`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapPosition {
+    LeftHalf,
+    RightHalf,
+    TopLeftQuarter,
+    Maximize,
+    Center,
+}
+
+impl SnapPosition {
+    pub const ALL: [SnapPosition; 5] = [
+        SnapPosition::LeftHalf,
+        SnapPosition::RightHalf,
+        SnapPosition::TopLeftQuarter,
+        SnapPosition::Maximize,
+        SnapPosition::Center,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnapPosition::LeftHalf => "Left Half",
+            SnapPosition::RightHalf => "Right Half",
+            SnapPosition::TopLeftQuarter => "Top Left Quarter",
+            SnapPosition::Maximize => "Maximize",
+            SnapPosition::Center => "Center",
+        }
+    }
+
+    /// Computes the rectangle for this preset inside `work_area`.
+    ///
+    /// `Center` keeps the window's `size`, shrunk to fit the work area if needed;
+    /// the other presets ignore it.
+    pub fn rect_in(
+        &self,
+        work_area: (i32, i32, i32, i32),
+        size: (i32, i32),
+    ) -> (i32, i32, i32, i32) {
+        let (x, y, w, h) = work_area;
+        match self {
+            SnapPosition::LeftHalf => (x, y, w / 2, h),
+            SnapPosition::RightHalf => (x + w / 2, y, w - w / 2, h),
+            SnapPosition::TopLeftQuarter => (x, y, w / 2, h / 2),
+            SnapPosition::Maximize => work_area,
+            SnapPosition::Center => {
+                let (cw, ch) = (size.0.min(w), size.1.min(h));
+                (x + (w - cw) / 2, y + (h - ch) / 2, cw, ch)
+            }
+        }
+    }
 }
 
 /// A **logical record** of a window managed by the application, linking its **HWND** (`id`)
@@ -673,6 +1829,12 @@ pub fn render_window_controls(ui: &mut egui::Ui, window: &mut Window, changed: &
 /// - `home`: A tuple `(x, y, width, height)` describing the “home” position (and size) for this window.
 /// - `target`: A tuple `(x, y, width, height)` describing the “target” position (and size).
 /// - `valid`: Indicates whether the window is considered valid (e.g., captured from a real HWND).
+/// - `enabled`: If `false`, the window is kept in the workspace but skipped when toggling or sending home.
+/// - `home_monitor` / `target_monitor`: The monitor each position was captured on, with the rectangle
+///   stored relative to that monitor's work area. Used to keep positions stable when the monitor layout changes.
+/// - `home_dpi` / `target_dpi`: The monitor DPI at capture time, used to rescale positions on mixed-DPI setups.
+/// - `home_state` / `target_state`: Whether the window should be normal, minimized, or maximized at each position.
+/// - `target_layout`: Whether `target` is used as a pixel rectangle or replaced by fractions of a monitor's work area.
 ///
 /// # Behavior
 /// - Used within a `Workspace` to toggle windows between `home` and `target` positions.
@@ -686,6 +1848,7 @@ pub fn render_window_controls(ui: &mut egui::Ui, window: &mut Window, changed: &
 ///     home: (0, 0, 800, 600),
 ///     target: (100, 100, 1024, 768),
 ///     valid: true,
+///     enabled: true,
 /// };
 /// ```
 ///
@@ -703,9 +1866,214 @@ pub struct Window {
     pub home: (i32, i32, i32, i32),
     pub target: (i32, i32, i32, i32),
     pub valid: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub home_monitor: Option<MonitorAnchor>,
+    #[serde(default)]
+    pub target_monitor: Option<MonitorAnchor>,
+    #[serde(default)]
+    pub home_dpi: Option<u32>,
+    #[serde(default)]
+    pub target_dpi: Option<u32>,
+    #[serde(default)]
+    pub home_state: WindowState,
+    #[serde(default)]
+    pub target_state: WindowState,
+    #[serde(default)]
+    pub desktop_index: Option<u32>,
+    #[serde(default)]
+    pub target_layout: TargetLayout,
+    /// Stacking position applied after toggling; 0 is frontmost and the
+    /// frontmost window receives focus.
+    #[serde(default)]
+    pub z_order: usize,
+    /// If `false`, moves keep the window where it is and only resize it.
+    #[serde(default = "default_enabled")]
+    pub apply_position: bool,
+    /// If `false`, moves keep the window's size and only reposition it.
+    #[serde(default = "default_enabled")]
+    pub apply_size: bool,
+    /// Identity of the window at capture time, used to notice when its handle
+    /// has been reused by an unrelated window.
+    #[serde(default)]
+    pub fingerprint: Option<WindowFingerprint>,
+    /// Optional sub-layout name; windows sharing one are listed and moved together.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// If `true`, toggling pins the window above all normal windows.
+    #[serde(default)]
+    pub topmost: bool,
+    /// Opacity applied when toggling, from 0 (invisible) to 255 (opaque, left untouched).
+    #[serde(default = "default_opacity")]
+    pub opacity: u8,
+}
+
+/// Lightweight identity of a window: the executable that owns it and its class.
+///
+/// Neither changes during a window's lifetime, so a mismatch means the handle
+/// now belongs to a different window.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowFingerprint {
+    /// File name of the owning executable, e.g. `notepad.exe`; empty if it
+    /// could not be read.
+    pub process: String,
+    /// Window class name.
+    pub class: String,
+}
+
+impl WindowFingerprint {
+    /// Returns `true` if `live` could be the same window as `self`.
+    ///
+    /// Executable names compare case-insensitively and are ignored when either
+    /// side could not read them, e.g. for an elevated process.
+    pub fn matches(&self, live: &WindowFingerprint) -> bool {
+        self.class == live.class
+            && (self.process.is_empty()
+                || live.process.is_empty()
+                || self.process.eq_ignore_ascii_case(&live.process))
+    }
+}
+
+/// How a window's target position is expressed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TargetLayout {
+    /// Pixel rectangle in `target`, anchored through `target_monitor`.
+    #[default]
+    Absolute,
+    /// Fractions `(x, y, width, height)` of a monitor's work area, so the
+    /// target adapts to that monitor's resolution. `monitor` is the GDI device
+    /// name; the primary monitor is used when it is unset or disconnected.
+    Relative {
+        fractions: (f32, f32, f32, f32),
+        monitor: Option<String>,
+    },
+    /// Screen point the window's center is placed on, with the size taken from
+    /// `target`.
+    Centered { center: (i32, i32) },
+}
+
+/// The show state a window should be placed in when moved to a position.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowState {
+    #[default]
+    Normal,
+    Minimized,
+    Maximized,
+}
+
+impl WindowState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WindowState::Normal => "Normal",
+            WindowState::Minimized => "Minimized",
+            WindowState::Maximized => "Maximized",
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_opacity() -> u8 {
+    u8::MAX
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            title: String::new(),
+            alias: None,
+            home: (0, 0, 800, 600),
+            target: (0, 0, 800, 600),
+            valid: false,
+            enabled: true,
+            home_monitor: None,
+            target_monitor: None,
+            home_dpi: None,
+            target_dpi: None,
+            home_state: WindowState::Normal,
+            target_state: WindowState::Normal,
+            desktop_index: None,
+            target_layout: TargetLayout::Absolute,
+            z_order: 0,
+            apply_position: true,
+            apply_size: true,
+            fingerprint: None,
+            group: None,
+            topmost: false,
+            opacity: u8::MAX,
+        }
+    }
 }
 
 impl Window {
+    /// Creates a window entry for `hwnd` with its current position and show state
+    /// stored as both home and target.
+    pub fn capture(hwnd: HWND, title: String) -> Window {
+        let (rect, state) =
+            capture_window_placement(hwnd).unwrap_or(((0, 0, 800, 600), WindowState::Normal));
+        capture_window_icon(hwnd);
+        Window {
+            id: hwnd.0 as usize,
+            title,
+            alias: None,
+            home: rect,
+            target: rect,
+            valid: true,
+            enabled: true,
+            home_monitor: anchor_for_window(hwnd, rect),
+            target_monitor: anchor_for_window(hwnd, rect),
+            home_dpi: Some(dpi_for_window(hwnd)),
+            target_dpi: Some(dpi_for_window(hwnd)),
+            home_state: state,
+            target_state: state,
+            desktop_index: None,
+            target_layout: TargetLayout::Absolute,
+            z_order: 0,
+            apply_position: true,
+            apply_size: true,
+            fingerprint: window_fingerprint(hwnd),
+            group: None,
+            topmost: false,
+            opacity: u8::MAX,
+        }
+    }
+
+    /// Returns `true` if `hwnd` exists and still looks like the window captured
+    /// into this entry.
+    ///
+    /// Entries saved before fingerprints were recorded only check that the
+    /// handle exists.
+    pub fn matches_live(&self, hwnd: HWND) -> bool {
+        if !unsafe { IsWindow(hwnd).as_bool() } {
+            return false;
+        }
+        match (&self.fingerprint, window_fingerprint(hwnd)) {
+            (Some(stored), Some(live)) => stored.matches(&live),
+            _ => true,
+        }
+    }
+
+    /// Like [`Window::matches_live`], but reuses the live fingerprint of `hwnd`
+    /// for [`LIVE_FINGERPRINT_REFRESH`], since reading it opens the owning
+    /// process. Used by the per-frame validation; toggling and recapturing
+    /// check afresh.
+    pub fn matches_live_cached(&self, hwnd: HWND) -> bool {
+        if !unsafe { IsWindow(hwnd).as_bool() } {
+            return false;
+        }
+        let Some(stored) = &self.fingerprint else {
+            return true;
+        };
+        match cached_window_fingerprint(hwnd) {
+            Some(live) => stored.matches(&live),
+            None => true,
+        }
+    }
+
     pub fn display_name(&self) -> &str {
         self.alias
             .as_deref()
@@ -720,6 +2088,121 @@ impl Window {
         }
     }
 
+    /// Replaces the parts of `rect` this window does not apply with those of
+    /// `current`, according to `apply_position` and `apply_size`.
+    pub fn mask_rect(
+        &self,
+        rect: (i32, i32, i32, i32),
+        current: (i32, i32, i32, i32),
+    ) -> (i32, i32, i32, i32) {
+        let (x, y) = if self.apply_position {
+            (rect.0, rect.1)
+        } else {
+            (current.0, current.1)
+        };
+        let (w, h) = if self.apply_size {
+            (rect.2, rect.3)
+        } else {
+            (current.2, current.3)
+        };
+        (x, y, w, h)
+    }
+
+    /// Returns the absolute home rectangle, translated from the monitor it was captured on
+    /// and scaled for any DPI difference.
+    pub fn home_rect(&self) -> (i32, i32, i32, i32) {
+        resolve_rect(self.home_monitor.as_ref(), self.home, self.home_dpi)
+    }
+
+    /// Returns the absolute target rectangle, translated from the monitor it was captured on
+    /// and scaled for any DPI difference, or computed from the monitor's current work
+    /// area for a [`TargetLayout::Relative`] target.
+    pub fn target_rect(&self) -> (i32, i32, i32, i32) {
+        match &self.target_layout {
+            TargetLayout::Relative { fractions, monitor } => {
+                if let Some(work_area) = work_area_for_device(monitor.as_deref()) {
+                    return fraction_to_rect(work_area, *fractions);
+                }
+            }
+            TargetLayout::Centered { center } => {
+                return centered_rect(*center, (self.target.2, self.target.3));
+            }
+            TargetLayout::Absolute => {}
+        }
+        resolve_rect(self.target_monitor.as_ref(), self.target, self.target_dpi)
+    }
+
+    /// Sets the home position and show state from the window's current
+    /// placement. Returns `false` if the placement could not be read.
+    pub fn capture_home(&mut self) -> bool {
+        let hwnd = HWND(self.id as *mut _);
+        let Ok((rect, state)) = capture_window_placement(hwnd) else {
+            return false;
+        };
+        self.home = rect;
+        self.home_state = state;
+        self.home_monitor = anchor_for_window(hwnd, rect);
+        self.home_dpi = Some(dpi_for_window(hwnd));
+        true
+    }
+
+    /// Sets the target position and show state from the window's current
+    /// placement, keeping a relative or centered target in that form.
+    /// Returns `false` if the placement could not be read.
+    pub fn capture_target(&mut self) -> bool {
+        let hwnd = HWND(self.id as *mut _);
+        let Ok((rect, state)) = capture_window_placement(hwnd) else {
+            return false;
+        };
+        self.target = rect;
+        self.target_state = state;
+        self.target_monitor = anchor_for_window(hwnd, rect);
+        self.target_dpi = Some(dpi_for_window(hwnd));
+        match self.target_layout {
+            TargetLayout::Absolute => {}
+            TargetLayout::Relative { .. } => {
+                self.target_layout = TargetLayout::Absolute;
+                self.set_target_relative(true);
+            }
+            TargetLayout::Centered { .. } => {
+                self.target_layout = TargetLayout::Absolute;
+                self.set_target_centered();
+            }
+        }
+        true
+    }
+
+    /// Switches the target between pixel and relative form, keeping the window's
+    /// resolved target position unchanged.
+    pub fn set_target_relative(&mut self, relative: bool) {
+        let rect = self.target_rect();
+        if relative {
+            let monitor = monitor_for_rect(rect);
+            self.target_layout = TargetLayout::Relative {
+                fractions: monitor
+                    .as_ref()
+                    .map(|m| rect_to_fraction(m.work_area, rect))
+                    .unwrap_or((0.0, 0.0, 0.5, 1.0)),
+                monitor: monitor.map(|m| m.device),
+            };
+        } else {
+            self.target = rect;
+            self.target_monitor = anchor_for_rect(rect);
+            self.target_dpi = monitor_for_rect(rect).map(|monitor| monitor.dpi);
+            self.target_layout = TargetLayout::Absolute;
+        }
+    }
+
+    /// Stores the target as the center of its resolved rectangle, keeping the
+    /// window's resolved target position unchanged.
+    pub fn set_target_centered(&mut self) {
+        self.set_target_relative(false);
+        let (x, y, w, h) = self.target;
+        self.target_layout = TargetLayout::Centered {
+            center: (x + w / 2, y + h / 2),
+        };
+    }
+
     pub fn sync_alias_from_title_if_missing(&mut self) {
         if self
             .alias
@@ -778,7 +2261,7 @@ pub fn is_valid_key_combo(input: &str) -> bool {
 ///     valid: true,
 /// }];
 ///
-/// save_workspaces(&workspaces, "workspaces.json");
+/// save_workspaces(&workspaces, "workspaces.json", 10, true);
 /// ```
 ///
 /// # Dependencies
@@ -788,31 +2271,451 @@ pub fn is_valid_key_combo(input: &str) -> bool {
 /// # Parameters
 /// - `workspaces: &[Workspace]`: A reference to the list of `Workspace` objects to be saved.
 /// - `file_path: &str`: The path to the file where the serialized data will be written.
+/// - `max_backups: usize`: How many timestamped backups of the previous file to keep
+///   in the `backups/` folder next to it; `0` disables backups.
+/// - `pretty: bool`: Whether JSON files are indented; other formats ignore it.
 ///
 /// # Side Effects
-/// - Creates or overwrites the specified file with the serialized workspace data.
+/// - Copies the existing file into `backups/` before replacing it.
+/// - Writes to a temporary file and renames it over the specified file, so a failed
+///   write leaves the previous contents intact.
 ///
 /// # Error Conditions
 /// - Logs an error if:
 ///   - Serialization fails (e.g., due to invalid data).
 ///   - File creation or writing fails (e.g., due to insufficient permissions).
 ///
-/// # Notes
-/// - Ensure the `workspaces` list is properly populated before calling this function.
-/// - The function does not return errors but logs them for debugging purposes.
-pub fn save_workspaces(workspaces: &[Workspace], file_path: &str) {
-    match serde_json::to_string_pretty(workspaces) {
-        Ok(json) => {
-            if let Err(e) =
-                File::create(file_path).and_then(|mut file| file.write_all(json.as_bytes()))
+/// # Notes
+/// - Ensure the `workspaces` list is properly populated before calling this function.
+/// - The function does not return errors but logs them for debugging purposes.
+pub fn save_workspaces(
+    workspaces: &[Workspace],
+    file_path: &str,
+    max_backups: usize,
+    pretty: bool,
+) {
+    match serialize_workspaces(workspaces, WorkspaceFormat::from_path(file_path), pretty) {
+        Ok(contents) => {
+            match backup_file(file_path, max_backups) {
+                Ok(Some(backup)) => debug!("Backed up '{}' to '{}'.", file_path, backup.display()),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to back up '{}': {}", file_path, e),
+            }
+            if let Err(e) = write_atomically(file_path, contents.as_bytes()) {
+                error!("Failed to save workspaces to '{}': {}", file_path, e);
+            } else {
+                info!("Workspaces successfully saved to '{}'.", file_path);
+            }
+        }
+        Err(e) => {
+            error!("Failed to serialize workspaces: {}", e);
+        }
+    }
+}
+
+/// File formats workspaces can be stored in, chosen by file extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkspaceFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl WorkspaceFormat {
+    /// Picks the format for `path` from its extension, defaulting to JSON.
+    pub fn from_path(path: &str) -> Self {
+        let extension = std::path::Path::new(path)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("toml") => WorkspaceFormat::Toml,
+            Some("yaml") | Some("yml") => WorkspaceFormat::Yaml,
+            _ => WorkspaceFormat::Json,
+        }
+    }
+}
+
+/// TOML documents must have a table at the root, so the list is wrapped.
+#[derive(Serialize, Deserialize)]
+struct TomlWorkspaces {
+    #[serde(default)]
+    workspaces: Vec<Workspace>,
+}
+
+/// Serializes `workspaces` in the given `format`.
+///
+/// `pretty` chooses between indented and single-line JSON; TOML and YAML are
+/// always written in their usual layout.
+pub fn serialize_workspaces(
+    workspaces: &[Workspace],
+    format: WorkspaceFormat,
+    pretty: bool,
+) -> Result<String, String> {
+    match format {
+        WorkspaceFormat::Json if pretty => {
+            serde_json::to_string_pretty(workspaces).map_err(|e| e.to_string())
+        }
+        WorkspaceFormat::Json => serde_json::to_string(workspaces).map_err(|e| e.to_string()),
+        WorkspaceFormat::Toml => toml::to_string(&TomlWorkspaces {
+            workspaces: workspaces.to_vec(),
+        })
+        .map_err(|e| e.to_string()),
+        WorkspaceFormat::Yaml => serde_yaml::to_string(workspaces).map_err(|e| e.to_string()),
+    }
+}
+
+/// Serializes a single workspace as pretty-printed JSON, for sharing it on its own.
+pub fn serialize_workspace(workspace: &Workspace) -> Result<String, String> {
+    serde_json::to_string_pretty(workspace).map_err(|e| e.to_string())
+}
+
+/// Parses and validates a single workspace written by [`serialize_workspace`].
+pub fn parse_workspace(content: &str) -> Result<Workspace, LoadError> {
+    let workspace = serde_json::from_str::<Workspace>(content).map_err(|err| {
+        let position = format!(" at line {} column {}", err.line(), err.column());
+        let message = err.to_string();
+        LoadError::Parse {
+            path: failing_field::<Workspace>(
+                &serde_json::from_str::<serde_json::Value>(content).unwrap_or_default(),
+            )
+            .unwrap_or_default(),
+            line: err.line(),
+            column: err.column(),
+            message: message
+                .strip_suffix(&position)
+                .unwrap_or(&message)
+                .to_string(),
+        }
+    })?;
+    validate_loaded_workspaces(std::slice::from_ref(&workspace))?;
+    Ok(workspace)
+}
+
+/// Writes `workspace` on its own to `file_path` as JSON.
+pub fn export_workspace(workspace: &Workspace, file_path: &str) -> Result<(), String> {
+    let contents = serialize_workspace(workspace)?;
+    write_atomically(file_path, contents.as_bytes()).map_err(|e| e.to_string())?;
+    info!(
+        "Exported workspace '{}' to '{}'.",
+        workspace.name, file_path
+    );
+    Ok(())
+}
+
+/// Reads a single workspace exported with [`export_workspace`]. No hotkeys are registered.
+pub fn import_workspace(file_path: &str) -> Result<Workspace, LoadError> {
+    let mut content = String::new();
+    File::open(file_path)?.read_to_string(&mut content)?;
+    parse_workspace(&content)
+}
+
+/// Returns `true` if one of `workspaces` already uses `key_sequence` as its hotkey.
+pub fn hotkey_in_use(workspaces: &[Workspace], key_sequence: &str) -> bool {
+    let normalized = normalize_hotkey(key_sequence).unwrap_or_else(|_| key_sequence.to_string());
+    workspaces
+        .iter()
+        .filter_map(|ws| ws.hotkey.as_ref())
+        .any(|hotkey| {
+            normalize_hotkey(&hotkey.key_sequence)
+                .unwrap_or_else(|_| hotkey.key_sequence.clone())
+                .eq_ignore_ascii_case(&normalized)
+        })
+}
+
+/// Returns `name`, or `name (2)`, `name (3)`, ... if another workspace already
+/// has that name (case-insensitively).
+pub fn unique_workspace_name(workspaces: &[Workspace], name: &str) -> String {
+    let taken = |candidate: &str| {
+        workspaces
+            .iter()
+            .any(|ws| ws.name.eq_ignore_ascii_case(candidate))
+    };
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Errors that can occur when loading workspaces from a file.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not valid workspace data. `path` points at the element that
+    /// failed (e.g. `[1].windows[0].home`) and is empty if it could not be found.
+    Parse {
+        path: String,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The file parsed but contains values the application cannot use.
+    Validation(Vec<String>),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "I/O error: {}", err),
+            LoadError::Parse {
+                path,
+                line,
+                column,
+                message,
+            } => {
+                if path.is_empty() {
+                    write!(
+                        f,
+                        "Parse error at line {}, column {}: {}",
+                        line, column, message
+                    )
+                } else {
+                    write!(
+                        f,
+                        "Parse error at {} (line {}, column {}): {}",
+                        path, line, column, message
+                    )
+                }
+            }
+            LoadError::Validation(problems) => {
+                write!(f, "Invalid workspace data: {}", problems.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(err: std::io::Error) -> Self {
+        LoadError::Io(err)
+    }
+}
+
+/// Parses and validates workspace JSON without registering any hotkeys.
+pub fn parse_workspaces(content: &str) -> Result<Vec<Workspace>, LoadError> {
+    let workspaces = serde_json::from_str::<Vec<Workspace>>(content).map_err(|err| {
+        let path = serde_json::from_str::<serde_json::Value>(content)
+            .ok()
+            .and_then(|value| find_error_path(&value))
+            .unwrap_or_default();
+        let position = format!(" at line {} column {}", err.line(), err.column());
+        let message = err.to_string();
+        LoadError::Parse {
+            path,
+            line: err.line(),
+            column: err.column(),
+            message: message
+                .strip_suffix(&position)
+                .unwrap_or(&message)
+                .to_string(),
+        }
+    })?;
+    validate_loaded_workspaces(&workspaces)?;
+    Ok(workspaces)
+}
+
+/// Parses and validates workspaces stored in `format`.
+pub fn parse_workspaces_as(
+    content: &str,
+    format: WorkspaceFormat,
+) -> Result<Vec<Workspace>, LoadError> {
+    let workspaces = match format {
+        WorkspaceFormat::Json => return parse_workspaces(content),
+        WorkspaceFormat::Toml => toml::from_str::<TomlWorkspaces>(content)
+            .map(|file| file.workspaces)
+            .map_err(|err| {
+                let (line, column) = err
+                    .span()
+                    .map(|span| line_and_column(content, span.start))
+                    .unwrap_or((0, 0));
+                LoadError::Parse {
+                    path: String::new(),
+                    line,
+                    column,
+                    message: err.message().to_string(),
+                }
+            })?,
+        WorkspaceFormat::Yaml => {
+            serde_yaml::from_str::<Vec<Workspace>>(content).map_err(|err| {
+                let (line, column) = err
+                    .location()
+                    .map(|location| (location.line(), location.column()))
+                    .unwrap_or((0, 0));
+                LoadError::Parse {
+                    path: String::new(),
+                    line,
+                    column,
+                    message: err.to_string(),
+                }
+            })?
+        }
+    };
+    validate_loaded_workspaces(&workspaces)?;
+    Ok(workspaces)
+}
+
+/// Converts a byte offset into a 1-based `(line, column)` pair.
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    (line, column)
+}
+
+/// Reads, parses and validates the workspaces stored in `file_path`, using the
+/// format implied by its extension.
+pub fn read_workspaces(file_path: &str) -> Result<Vec<Workspace>, LoadError> {
+    let mut content = String::new();
+    File::open(file_path)?.read_to_string(&mut content)?;
+    parse_workspaces_as(&content, WorkspaceFormat::from_path(file_path))
+}
+
+/// Walks a parsed JSON document to find the element that fails to deserialize,
+/// returning a path such as `[1].windows[0].home`.
+fn find_error_path(value: &serde_json::Value) -> Option<String> {
+    let Some(items) = value.as_array() else {
+        return Some("(root)".to_string());
+    };
+    for (i, item) in items.iter().enumerate() {
+        if serde_json::from_value::<Workspace>(item.clone()).is_ok() {
+            continue;
+        }
+        if let Some(windows) = item.get("windows").and_then(|w| w.as_array()) {
+            for (j, window) in windows.iter().enumerate() {
+                if serde_json::from_value::<Window>(window.clone()).is_err() {
+                    return Some(match failing_field::<Window>(window) {
+                        Some(field) => format!("[{}].windows[{}].{}", i, j, field),
+                        None => format!("[{}].windows[{}]", i, j),
+                    });
+                }
+            }
+        }
+        return Some(match failing_field::<Workspace>(item) {
+            Some(field) => format!("[{}].{}", i, field),
+            None => format!("[{}]", i),
+        });
+    }
+    None
+}
+
+/// Finds the field of a JSON object that prevents it from deserializing as `T`.
+///
+/// Missing fields are reported directly by serde. For other errors each field
+/// is removed in turn; the culprit is the one whose removal either fixes the
+/// object or turns the error into "missing field".
+fn failing_field<T: serde::de::DeserializeOwned>(value: &serde_json::Value) -> Option<String> {
+    let object = value.as_object()?;
+    let err = serde_json::from_value::<T>(value.clone()).err()?;
+    let message = err.to_string();
+    if let Some(field) = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next())
+    {
+        return Some(field.to_string());
+    }
+
+    object.keys().find_map(|key| {
+        let mut trimmed = object.clone();
+        trimmed.remove(key);
+        match serde_json::from_value::<T>(serde_json::Value::Object(trimmed)) {
+            Ok(_) => Some(key.clone()),
+            Err(e)
+                if e.to_string()
+                    .starts_with(&format!("missing field `{}`", key)) =>
             {
-                error!("Failed to save workspaces to '{}': {}", file_path, e);
+                Some(key.clone())
+            }
+            Err(_) => None,
+        }
+    })
+}
+
+/// Checks invariants serde cannot express: workspace names must not be empty
+/// and window rectangles must have non-negative sizes.
+fn validate_loaded_workspaces(workspaces: &[Workspace]) -> Result<(), LoadError> {
+    let mut problems = Vec::new();
+    for (i, workspace) in workspaces.iter().enumerate() {
+        if workspace.name.trim().is_empty() {
+            problems.push(format!("[{}].name is empty", i));
+        }
+        for (j, window) in workspace.windows.iter().enumerate() {
+            for (field, rect) in [("home", window.home), ("target", window.target)] {
+                if rect.2 < 0 || rect.3 < 0 {
+                    problems.push(format!(
+                        "[{}].windows[{}].{} has a negative size {:?}",
+                        i, j, field, rect
+                    ));
+                }
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(LoadError::Validation(problems))
+    }
+}
+
+/// Registers the hotkey of every workspace, using each workspace's index as its id.
+///
+/// Workspaces with `require_focus_member` set are registered last, so they
+/// join a hotkey another workspace registered instead of taking it first.
+pub fn register_workspace_hotkeys(workspaces: &mut [Workspace], app: &App) {
+    let mut order: Vec<usize> = (0..workspaces.len()).collect();
+    order.sort_by_key(|&i| workspaces[i].require_focus_member);
+    for i in order {
+        let workspace = &mut workspaces[i];
+        if let Some(ref mut hotkey) = workspace.hotkey {
+            if !hotkey.register_or_share(app, i as i32, workspace.require_focus_member) {
+                warn!(
+                    "Failed to register hotkey '{}' for workspace '{}'.",
+                    hotkey, workspace.name
+                );
             } else {
-                info!("Workspaces successfully saved to '{}'.", file_path);
+                info!(
+                    "Registered hotkey '{}' for workspace '{}'.",
+                    hotkey, workspace.name
+                );
             }
         }
-        Err(e) => {
-            error!("Failed to serialize workspaces: {}", e);
+        workspace.rotation_offset = 0;
+    }
+}
+
+/// Rewrites each stored hotkey into the canonical form produced by
+/// [`normalize_hotkey`], so files saved before hotkeys were normalized keep
+/// matching. Hotkeys that no longer parse are dropped with a warning; the
+/// workspace itself is kept.
+pub fn normalize_workspace_hotkeys(workspaces: &mut [Workspace]) {
+    for workspace in workspaces.iter_mut() {
+        let Some(hotkey) = workspace.hotkey.as_mut() else {
+            continue;
+        };
+        match normalize_hotkey(&hotkey.key_sequence) {
+            Ok(normalized) if normalized != hotkey.key_sequence => {
+                info!(
+                    "Normalized hotkey '{}' of workspace '{}' to '{}'.",
+                    hotkey.key_sequence, workspace.name, normalized
+                );
+                hotkey.key_sequence = normalized;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!("Dropping hotkey of workspace '{}': {}", workspace.name, e);
+                workspace.hotkey = None;
+            }
         }
     }
 }
@@ -824,9 +2727,10 @@ pub fn save_workspaces(workspaces: &[Workspace], file_path: &str) {
 ///
 /// # Behavior
 /// - Reads the specified file and parses its contents as JSON.
+/// - Validates the loaded data (see [`parse_workspaces`]).
+/// - Normalizes legacy hotkey strings (see [`normalize_workspace_hotkeys`]).
 /// - Registers hotkeys for each workspace if the hotkey is valid and not already registered.
-/// - Logs warnings for invalid or unregistered hotkeys.
-/// - If the file is missing or invalid, returns an empty list.
+/// - Logs warnings for unregistered hotkeys.
 ///
 /// # Example
 /// ```rust
@@ -837,80 +2741,64 @@ pub fn save_workspaces(workspaces: &[Workspace], file_path: &str) {
 ///     initial_validation_done: Arc::new(Mutex::new(false)),
 ///     registered_hotkeys: Arc::new(Mutex::new(HashMap::new())),
 /// };
-/// let workspaces = load_workspaces("workspaces.json", &app);
+/// let workspaces = load_workspaces("workspaces.json", &app)?;
 /// ```
 ///
-/// # Dependencies
-/// - Relies on `serde_json` for deserialization.
-/// - Uses the `register_hotkey` function to attempt hotkey registration.
-///
 /// # Parameters
 /// - `file_path: &str`: The path to the JSON file containing workspace data.
 /// - `app: &App`: Reference to the `App` instance for managing hotkey registration.
 ///
 /// # Returns
-/// - A `Vec<Workspace>` containing the loaded workspaces, with hotkeys registered where possible.
+/// - `Ok(Vec<Workspace>)` with hotkeys registered where possible.
+/// - `Err(LoadError)` describing the I/O, parse or validation failure. No hotkeys
+///   are registered in this case.
 ///
 /// # Side Effects
 /// - Modifies the `registered_hotkeys` field of the `App` instance by adding valid hotkeys.
 ///
-/// # Error Conditions
-/// - Logs warnings if:
-///   - The file cannot be read.
-///   - The JSON is invalid or cannot be deserialized.
-///   - A hotkey cannot be registered.
-///
 /// # Notes
-/// - Ensure the file exists and is in the correct JSON format.
 /// - Hotkeys that fail registration are not removed from the workspace but are logged as invalid.
-pub fn load_workspaces(file_path: &str, app: &App) -> Vec<Workspace> {
-    let mut content = String::new();
-    match File::open(file_path) {
-        Ok(mut file) => {
-            if let Err(e) = file.read_to_string(&mut content) {
-                error!("Failed to read file '{}': {}", file_path, e);
-                return Vec::new();
-            }
-            match serde_json::from_str::<Vec<Workspace>>(&content) {
-                Ok(mut workspaces) => {
-                    info!("Successfully loaded workspaces from '{}'.", file_path);
-
-                    for (i, workspace) in workspaces.iter_mut().enumerate() {
-                        if let Some(ref mut hotkey) = workspace.hotkey {
-                            if !hotkey.register(app, i as i32) {
-                                warn!(
-                                    "Failed to register hotkey '{}' for workspace '{}'.",
-                                    hotkey, workspace.name
-                                );
-                            } else {
-                                info!(
-                                    "Registered hotkey '{}' for workspace '{}'.",
-                                    hotkey, workspace.name
-                                );
-                            }
-                        }
-                        workspace.rotation_offset = 0;
-                    }
+pub fn load_workspaces(file_path: &str, app: &App) -> Result<Vec<Workspace>, LoadError> {
+    let mut workspaces = read_workspaces(file_path)?;
+    info!("Successfully loaded workspaces from '{}'.", file_path);
+    normalize_workspace_hotkeys(&mut workspaces);
+    register_workspace_hotkeys(&mut workspaces, app);
+    Ok(workspaces)
+}
 
-                    workspaces
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to parse JSON in '{}': {}. Returning empty workspace list.",
-                        file_path, e
-                    );
-                    Vec::new()
-                }
-            }
-        }
-        Err(e) => {
-            warn!(
-                "File '{}' not found or cannot be opened: {}. Returning empty workspace list.",
-                file_path, e
-            );
-            Vec::new()
-        }
+/// Returns `(workspace, window)` indices of every window whose title or alias
+/// contains `query`, ignoring case. An empty query matches nothing.
+pub fn find_windows(workspaces: &[Workspace], query: &str) -> Vec<(usize, usize)> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
     }
+    let matches = |text: &str| text.to_lowercase().contains(&query);
+    workspaces
+        .iter()
+        .enumerate()
+        .flat_map(|(i, ws)| {
+            ws.windows
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| matches(&w.title) || w.alias.as_deref().is_some_and(matches))
+                .map(move |(j, _)| (i, j))
+        })
+        .collect()
+}
+
+/// Finds a workspace by name (case-insensitive) or, failing that, by zero-based index.
+pub fn find_workspace(workspaces: &[Workspace], selector: &str) -> Option<usize> {
+    let selector = selector.trim();
+    workspaces
+        .iter()
+        .position(|ws| ws.name.eq_ignore_ascii_case(selector))
+        .or_else(|| {
+            selector
+                .parse::<usize>()
+                .ok()
+                .filter(|&index| index < workspaces.len())
+        })
 }
 
 #[cfg(test)]
@@ -918,7 +2806,8 @@ mod tests {
     use super::*;
     use crate::gui::App;
     use poll_promise::Promise;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::atomic::AtomicBool;
     use std::sync::{Arc, Mutex};
     use std::time::SystemTime;
 
@@ -942,24 +2831,63 @@ mod tests {
             app_title_name: "Test".to_string(),
             workspaces: Arc::new(Mutex::new(Vec::new())),
             last_hotkey_info: Arc::new(Mutex::new(None)),
+            recent: Arc::new(Mutex::new(VecDeque::new())),
             hotkey_promise: Arc::new(Mutex::new(None::<Promise<()>>)),
+            hotkey_thread_stop: Arc::new(AtomicBool::new(false)),
             initial_validation_done: Arc::new(Mutex::new(false)),
             registered_hotkeys: Arc::new(Mutex::new(HashMap::new())),
             rename_dialog: None,
             hotkey_dialog: None,
+            import_dialog: None,
+            add_windows_dialog: None,
+            rescale_dialog: None,
+            workspace_comparison: None,
             all_expanded: false,
             expand_all_signal: None,
+            window_search: String::new(),
+            scroll_to_workspace: None,
             show_settings: false,
+            show_status: false,
+            show_hotkey_help: false,
             auto_save: false,
             unsaved_changes: false,
             save_on_exit: false,
             log_level: "info".to_string(),
+            log_max_size_mb: 10,
+            log_keep_files: 3,
             last_layout_file: None,
             last_workspace_file: None,
             last_bindings_file: None,
+            data_dir: None,
             developer_debugging: false,
+            binding_match_threshold: crate::window_bindings::DEFAULT_FUZZY_THRESHOLD,
+            max_backups: 0,
+            pretty_json: true,
+            move_animation: Arc::new(Mutex::new(crate::window_manager::MoveAnimation::default())),
+            verify_moves: Arc::new(Mutex::new(false)),
+            hotkeys_enabled: Arc::new(Mutex::new(true)),
+            hotkeys_toggle_key: Arc::new(Mutex::new(String::new())),
+            hotkey_poll_ms: Arc::new(Mutex::new(100)),
+            minimize_to_tray: false,
+            start_minimized: false,
+            start_with_windows: false,
+            main_window: None,
+            theme: crate::settings::Theme::System,
+            origin_exclude_processes: Vec::new(),
+            origin_current_desktop_only: false,
             recapture_queue: Vec::new(),
             recapture_active: false,
+            capture_countdown_secs: 3,
+            desktop_switch_delay_ms: 0,
+            capture_countdown: None,
+            auto_rebind: None,
+            desktop_job: None,
+            show_capture_filter: false,
+            desktop_capture_include: Vec::new(),
+            desktop_capture_exclude: Vec::new(),
+            last_deleted: None,
+            last_deleted_at: None,
+            undo_history: Default::default(),
         }
     }
 
@@ -978,14 +2906,40 @@ mod tests {
                 home: (0, 0, 800, 600),
                 target: (100, 100, 800, 600),
                 valid: true,
+                enabled: true,
+                home_monitor: None,
+                target_monitor: None,
+                home_dpi: None,
+                target_dpi: None,
+                home_state: WindowState::Normal,
+                target_state: WindowState::Normal,
+                desktop_index: None,
+                target_layout: TargetLayout::Absolute,
+                z_order: 0,
+                apply_position: true,
+                apply_size: true,
+                fingerprint: None,
+                group: None,
+                topmost: false,
+                opacity: u8::MAX,
             }],
             disabled: false,
             valid: true,
             rotate: false,
+            rotate_reverse: false,
+            rotate_wrap: true,
+            minimize_others: false,
             rotation_offset: 0,
+            offscreen: false,
+            invalid_windows: 0,
+            expanded: true,
+            activation_delay_ms: 0,
+            strict_toggle: false,
+            steal_focus: true,
+            require_focus_member: false,
         }];
 
-        save_workspaces(&workspaces, &path);
+        save_workspaces(&workspaces, &path, 0, true);
         let file_content =
             std::fs::read_to_string(&path).expect("workspace file should be written");
         assert!(
@@ -997,7 +2951,7 @@ mod tests {
             "Serialized workspaces should include the alias field"
         );
 
-        let loaded = load_workspaces(&path, &test_app());
+        let loaded = load_workspaces(&path, &test_app()).expect("should load workspaces");
         assert_eq!(loaded[0].windows[0].alias.as_deref(), Some(alias.as_str()));
         assert_eq!(loaded[0].windows[0].title, window_title);
 
@@ -1029,14 +2983,63 @@ mod tests {
         "#;
         std::fs::write(&path, legacy_json).expect("should write legacy json");
 
-        let loaded = load_workspaces(&path, &test_app());
+        let loaded = load_workspaces(&path, &test_app()).expect("should load workspaces");
         assert_eq!(loaded.len(), 1);
+        assert!(loaded[0].expanded);
+        assert_eq!(loaded[0].activation_delay_ms, 0);
+        assert!(!loaded[0].rotate_reverse);
+        assert!(loaded[0].rotate_wrap);
+        assert!(!loaded[0].minimize_others);
+        assert!(!loaded[0].strict_toggle);
+        assert!(loaded[0].steal_focus);
+        assert!(!loaded[0].require_focus_member);
         assert_eq!(loaded[0].windows.len(), 1);
         let window = &loaded[0].windows[0];
         assert!(window.alias.is_none());
         assert_eq!(window.display_name(), "Legacy Window");
+        assert!(window.enabled);
+        assert!(window.group.is_none());
+        assert!(!window.topmost);
+        assert_eq!(window.opacity, u8::MAX);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn legacy_hotkeys_are_normalized_on_load() {
+        let path = temp_json_path("legacy_hotkeys");
+        let legacy_json = r#"
+        [
+            { "name": "Reordered", "hotkey": { "key_sequence": " shift+ctrl+p " }, "windows": [], "disabled": false, "valid": true },
+            { "name": "Lowercase", "hotkey": { "key_sequence": "h+ctrl" }, "windows": [], "disabled": false, "valid": true },
+            { "name": "Canonical", "hotkey": { "key_sequence": "Ctrl+Alt+F5" }, "windows": [], "disabled": false, "valid": true },
+            { "name": "Broken", "hotkey": { "key_sequence": "Ctrl+NotAKey" }, "windows": [], "disabled": false, "valid": true },
+            { "name": "None", "hotkey": null, "windows": [], "disabled": false, "valid": true }
+        ]
+        "#;
+        std::fs::write(&path, legacy_json).expect("should write legacy json");
 
+        let loaded = load_workspaces(&path, &test_app()).expect("should load workspaces");
         let _ = std::fs::remove_file(&path);
+        let hotkeys: Vec<(&str, Option<&str>)> = loaded
+            .iter()
+            .map(|ws| {
+                (
+                    ws.name.as_str(),
+                    ws.hotkey.as_ref().map(|h| h.key_sequence.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            hotkeys,
+            vec![
+                ("Reordered", Some("Ctrl+Shift+P")),
+                ("Lowercase", Some("Ctrl+H")),
+                ("Canonical", Some("Ctrl+Alt+F5")),
+                ("Broken", None),
+                ("None", None),
+            ]
+        );
     }
 
     #[test]
@@ -1048,6 +3051,22 @@ mod tests {
             home: (0, 0, 100, 100),
             target: (0, 0, 100, 100),
             valid: true,
+            enabled: true,
+            home_monitor: None,
+            target_monitor: None,
+            home_dpi: None,
+            target_dpi: None,
+            home_state: WindowState::Normal,
+            target_state: WindowState::Normal,
+            desktop_index: None,
+            target_layout: TargetLayout::Absolute,
+            z_order: 0,
+            apply_position: true,
+            apply_size: true,
+            fingerprint: None,
+            group: None,
+            topmost: false,
+            opacity: u8::MAX,
         };
 
         let without_alias = Window {
@@ -1057,6 +3076,22 @@ mod tests {
             home: (0, 0, 100, 100),
             target: (0, 0, 100, 100),
             valid: true,
+            enabled: true,
+            home_monitor: None,
+            target_monitor: None,
+            home_dpi: None,
+            target_dpi: None,
+            home_state: WindowState::Normal,
+            target_state: WindowState::Normal,
+            desktop_index: None,
+            target_layout: TargetLayout::Absolute,
+            z_order: 0,
+            apply_position: true,
+            apply_size: true,
+            fingerprint: None,
+            group: None,
+            topmost: false,
+            opacity: u8::MAX,
         };
 
         assert_eq!(with_alias.display_label(), "Alias Name");
@@ -1072,6 +3107,22 @@ mod tests {
             home: (0, 0, 100, 100),
             target: (0, 0, 100, 100),
             valid: true,
+            enabled: true,
+            home_monitor: None,
+            target_monitor: None,
+            home_dpi: None,
+            target_dpi: None,
+            home_state: WindowState::Normal,
+            target_state: WindowState::Normal,
+            desktop_index: None,
+            target_layout: TargetLayout::Absolute,
+            z_order: 0,
+            apply_position: true,
+            apply_size: true,
+            fingerprint: None,
+            group: None,
+            topmost: false,
+            opacity: u8::MAX,
         };
         assert_eq!(with_trailing_space.display_label(), "Alias With Space ");
         with_trailing_space.alias = Some("   ".to_string());
@@ -1083,4 +3134,583 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn import_windows_copies_selected_as_invalid() {
+        let source = Workspace {
+            name: "Source".to_string(),
+            windows: vec![
+                Window {
+                    id: 10,
+                    title: "Editor".to_string(),
+                    home: (0, 0, 800, 600),
+                    target: (800, 0, 800, 600),
+                    valid: true,
+                    ..Default::default()
+                },
+                Window {
+                    id: 20,
+                    title: "Terminal".to_string(),
+                    valid: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let mut target = Workspace {
+            name: "Target".to_string(),
+            ..Default::default()
+        };
+
+        target.import_windows_from(&source, &[0, 5]);
+
+        assert_eq!(target.windows.len(), 1);
+        let imported = &target.windows[0];
+        assert_eq!(imported.title, "Editor");
+        assert_eq!(imported.home, (0, 0, 800, 600));
+        assert_eq!(imported.target, (800, 0, 800, 600));
+        assert!(!imported.valid);
+        assert_eq!(imported.id, 0);
+        assert!(source.windows[0].valid);
+    }
+
+    #[test]
+    fn contains_window_matches_captured_handles() {
+        let workspace = Workspace {
+            windows: vec![
+                Window {
+                    id: 0x1234,
+                    ..Default::default()
+                },
+                Window {
+                    id: 0x5678,
+                    enabled: false,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert!(workspace.contains_window(0x1234));
+        assert!(workspace.contains_window(0x5678));
+        assert!(!workspace.contains_window(0x9ABC));
+    }
+
+    #[test]
+    fn duplicated_clears_hotkey_and_renames() {
+        let original = Workspace {
+            name: "Coding".to_string(),
+            hotkey: Some(Hotkey::new("Ctrl+Alt+C").unwrap()),
+            windows: vec![Window {
+                title: "Editor".to_string(),
+                ..Default::default()
+            }],
+            rotate: true,
+            rotation_offset: 2,
+            ..Default::default()
+        };
+
+        let copy = original.duplicated();
+
+        assert_eq!(copy.name, "Coding (copy)");
+        assert!(copy.hotkey.is_none());
+        assert_eq!(copy.rotation_offset, 0);
+        assert!(copy.rotate);
+        assert_eq!(copy.windows.len(), 1);
+        assert_eq!(copy.windows[0].title, "Editor");
+        assert!(original.hotkey.is_some());
+    }
+
+    #[test]
+    fn parse_error_reports_failing_field() {
+        let json = r#"
+        [
+            {
+                "name": "Broken",
+                "hotkey": null,
+                "windows": [
+                    {
+                        "id": 1,
+                        "title": "Window",
+                        "home": [0, 0, 100, 100],
+                        "target": "not a rect",
+                        "valid": true
+                    }
+                ],
+                "disabled": false,
+                "valid": true
+            }
+        ]
+        "#;
+
+        match parse_workspaces(json) {
+            Err(LoadError::Parse { path, line, .. }) => {
+                assert_eq!(path, "[0].windows[0].target");
+                assert_eq!(line, 11);
+            }
+            other => panic!("expected parse error, got {:?}", other.map(|w| w.len())),
+        }
+
+        match parse_workspaces(
+            r#"[{"hotkey": null, "windows": [], "disabled": false, "valid": true}]"#,
+        ) {
+            Err(LoadError::Parse { path, .. }) => assert_eq!(path, "[0].name"),
+            other => panic!("expected parse error, got {:?}", other.map(|w| w.len())),
+        }
+    }
+
+    #[test]
+    fn validation_rejects_empty_names_and_negative_sizes() {
+        let json = r#"
+        [
+            {
+                "name": " ",
+                "hotkey": null,
+                "windows": [
+                    {
+                        "id": 1,
+                        "title": "Window",
+                        "home": [0, 0, -5, 100],
+                        "target": [0, 0, 100, 100],
+                        "valid": true
+                    }
+                ],
+                "disabled": false,
+                "valid": true
+            }
+        ]
+        "#;
+
+        match parse_workspaces(json) {
+            Err(LoadError::Validation(problems)) => {
+                assert_eq!(problems.len(), 2);
+                assert!(problems[0].contains("[0].name"));
+                assert!(problems[1].contains("[0].windows[0].home"));
+            }
+            other => panic!(
+                "expected validation error, got {:?}",
+                other.map(|w| w.len())
+            ),
+        }
+    }
+
+    fn sample_workspaces() -> Vec<Workspace> {
+        vec![Workspace {
+            name: "Layout".to_string(),
+            hotkey: Some(Hotkey::new("Ctrl+Alt+L").unwrap()),
+            windows: vec![Window {
+                id: 42,
+                title: "Editor".to_string(),
+                alias: Some("Code".to_string()),
+                home: (-1920, 0, 960, 1040),
+                target: (0, 0, 1920, 1040),
+                valid: true,
+                home_monitor: Some(MonitorAnchor {
+                    device: "\\\\.\\DISPLAY2".to_string(),
+                    rect: (0, 0, 960, 1040),
+                }),
+                home_dpi: Some(144),
+                target_state: WindowState::Maximized,
+                desktop_index: Some(1),
+                target_layout: TargetLayout::Absolute,
+                z_order: 0,
+                ..Default::default()
+            }],
+            rotate: true,
+            ..Default::default()
+        }]
+    }
+
+    #[test]
+    fn workspaces_round_trip_through_each_format() {
+        let original = sample_workspaces();
+        for format in [
+            WorkspaceFormat::Json,
+            WorkspaceFormat::Toml,
+            WorkspaceFormat::Yaml,
+        ] {
+            let text = serialize_workspaces(&original, format, true).expect("should serialize");
+            let loaded = parse_workspaces_as(&text, format).expect("should parse");
+            assert_eq!(loaded.len(), 1, "{:?}", format);
+            let (ws, window) = (&loaded[0], &loaded[0].windows[0]);
+            assert_eq!(ws.name, "Layout");
+            assert_eq!(
+                ws.hotkey.as_ref().map(|h| h.key_sequence.as_str()),
+                Some("Ctrl+Alt+L")
+            );
+            assert!(ws.rotate);
+            assert_eq!(window.alias.as_deref(), Some("Code"));
+            assert_eq!(window.home, (-1920, 0, 960, 1040));
+            assert_eq!(window.home_monitor, original[0].windows[0].home_monitor);
+            assert_eq!(window.home_dpi, Some(144));
+            assert_eq!(window.target_state, WindowState::Maximized);
+            assert_eq!(window.desktop_index, Some(1));
+        }
+    }
+
+    #[test]
+    fn compact_json_is_single_line_and_round_trips() {
+        let original = sample_workspaces();
+        let compact = serialize_workspaces(&original, WorkspaceFormat::Json, false)
+            .expect("should serialize");
+        let pretty =
+            serialize_workspaces(&original, WorkspaceFormat::Json, true).expect("should serialize");
+        assert!(!compact.contains('\n'));
+        assert!(compact.len() < pretty.len());
+
+        let loaded = parse_workspaces_as(&compact, WorkspaceFormat::Json).expect("should parse");
+        assert_eq!(
+            serialize_workspaces(&loaded, WorkspaceFormat::Json, true).expect("should serialize"),
+            pretty
+        );
+    }
+
+    #[test]
+    fn format_is_chosen_by_extension() {
+        assert_eq!(WorkspaceFormat::from_path("a.toml"), WorkspaceFormat::Toml);
+        assert_eq!(WorkspaceFormat::from_path("a.YML"), WorkspaceFormat::Yaml);
+        assert_eq!(WorkspaceFormat::from_path("a.yaml"), WorkspaceFormat::Yaml);
+        assert_eq!(WorkspaceFormat::from_path("a.json"), WorkspaceFormat::Json);
+        assert_eq!(
+            WorkspaceFormat::from_path("workspaces"),
+            WorkspaceFormat::Json
+        );
+    }
+
+    #[test]
+    fn find_workspace_matches_name_then_index() {
+        let workspaces = vec![
+            Workspace {
+                name: "Coding".to_string(),
+                ..Default::default()
+            },
+            Workspace {
+                name: "0".to_string(),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(find_workspace(&workspaces, "coding"), Some(0));
+        // A workspace named like a number wins over the index.
+        assert_eq!(find_workspace(&workspaces, "0"), Some(1));
+        assert_eq!(find_workspace(&workspaces, "1"), Some(1));
+        assert_eq!(find_workspace(&workspaces, "5"), None);
+        assert_eq!(find_workspace(&workspaces, "Gaming"), None);
+    }
+
+    #[test]
+    fn relative_target_round_trips_and_old_files_default_to_absolute() {
+        let mut workspaces = sample_workspaces();
+        let layout = TargetLayout::Relative {
+            fractions: (0.0, 0.0, 0.5, 1.0),
+            monitor: Some("\\\\.\\DISPLAY1".to_string()),
+        };
+        workspaces[0].windows[0].target_layout = layout.clone();
+        for format in [
+            WorkspaceFormat::Json,
+            WorkspaceFormat::Toml,
+            WorkspaceFormat::Yaml,
+        ] {
+            let text = serialize_workspaces(&workspaces, format, true).expect("should serialize");
+            let loaded = parse_workspaces_as(&text, format).expect("should parse");
+            assert_eq!(loaded[0].windows[0].target_layout, layout, "{:?}", format);
+        }
+
+        let legacy = r#"[{"name":"Old","hotkey":null,"disabled":false,"valid":false,
+            "windows":[{"id":1,"title":"t","home":[0,0,10,10],"target":[0,0,10,10],"valid":false}]}]"#;
+        let loaded = parse_workspaces(legacy).expect("legacy file should parse");
+        assert_eq!(loaded[0].windows[0].target_layout, TargetLayout::Absolute);
+    }
+
+    #[test]
+    fn snap_positions_fill_the_work_area() {
+        let work_area = (-1920, 40, 1921, 1000);
+        assert_eq!(
+            SnapPosition::LeftHalf.rect_in(work_area, (0, 0)),
+            (-1920, 40, 960, 1000)
+        );
+        assert_eq!(
+            SnapPosition::RightHalf.rect_in(work_area, (0, 0)),
+            (-960, 40, 961, 1000)
+        );
+        assert_eq!(
+            SnapPosition::TopLeftQuarter.rect_in(work_area, (0, 0)),
+            (-1920, 40, 960, 500)
+        );
+        assert_eq!(SnapPosition::Maximize.rect_in(work_area, (0, 0)), work_area);
+        assert_eq!(
+            SnapPosition::Center.rect_in((0, 0, 1920, 1080), (800, 600)),
+            (560, 240, 800, 600)
+        );
+        assert_eq!(
+            SnapPosition::Center.rect_in((0, 0, 1920, 1080), (4000, 600)),
+            (0, 240, 1920, 600)
+        );
+    }
+
+    #[test]
+    fn tile_targets_cover_the_work_area() {
+        let work_area = (0, 0, 1920, 1040);
+        assert_eq!(
+            tile_targets(3, work_area, TileMode::Columns),
+            vec![(0, 0, 640, 1040), (640, 0, 640, 1040), (1280, 0, 640, 1040)]
+        );
+        assert_eq!(
+            tile_targets(2, work_area, TileMode::Rows),
+            vec![(0, 0, 1920, 520), (0, 520, 1920, 520)]
+        );
+        assert_eq!(
+            tile_targets(3, (100, 0, 1001, 1000), TileMode::Grid),
+            vec![(100, 0, 500, 500), (600, 0, 501, 500), (100, 500, 500, 500)]
+        );
+        assert_eq!(tile_targets(5, work_area, TileMode::Grid).len(), 5);
+        assert!(tile_targets(0, work_area, TileMode::Grid).is_empty());
+    }
+
+    #[test]
+    fn header_marks_invalid_windows() {
+        let mut workspace = Workspace {
+            name: "Work".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(workspace.get_header_text().text(), "Work");
+
+        workspace.invalid_windows = 2;
+        assert_eq!(workspace.get_header_text().text(), "Work (⚠ 2 invalid)");
+
+        workspace.offscreen = true;
+        assert_eq!(
+            workspace.get_header_text().text(),
+            "Work (⚠ 2 invalid) (off-screen)"
+        );
+    }
+
+    #[test]
+    fn single_workspace_round_trip() {
+        let workspace = Workspace {
+            name: "Shared".to_string(),
+            hotkey: Some(Hotkey::new("Ctrl+Alt+S").unwrap()),
+            windows: vec![Window {
+                title: "Editor".to_string(),
+                home: (0, 0, 800, 600),
+                ..Window::default()
+            }],
+            ..Default::default()
+        };
+        let content = serialize_workspace(&workspace).unwrap();
+        let loaded = parse_workspace(&content).unwrap();
+        assert_eq!(loaded.name, "Shared");
+        assert_eq!(loaded.windows[0].home, (0, 0, 800, 600));
+
+        let Err(LoadError::Validation(problems)) = parse_workspace(r#"{"name": " "}"#) else {
+            panic!("an unnamed workspace should fail validation");
+        };
+        assert_eq!(problems, vec!["[0].name is empty"]);
+    }
+
+    #[test]
+    fn focus_sensitive_workspaces_share_a_hotkey() {
+        let app = test_app();
+        let hotkey = "Ctrl+Alt+Shift+F23";
+        let mut workspaces: Vec<Workspace> = ["Editor", "Browser"]
+            .into_iter()
+            .map(|name| Workspace {
+                name: name.to_string(),
+                hotkey: Some(Hotkey::new(hotkey).unwrap()),
+                require_focus_member: true,
+                ..Default::default()
+            })
+            .collect();
+
+        register_workspace_hotkeys(&mut workspaces, &app);
+        let registration = app.registered_hotkeys.lock().unwrap().get(hotkey).copied();
+        assert_eq!(registration.map(|r| r.users), Some(2));
+        let ids: Vec<_> = workspaces
+            .iter()
+            .map(|ws| ws.hotkey.as_ref().unwrap().id)
+            .collect();
+        assert!(ids[0].is_some());
+        assert_eq!(ids[0], ids[1]);
+
+        // Releasing one workspace keeps the hotkey registered for the other.
+        assert!(workspaces[0].hotkey.as_ref().unwrap().unregister(&app));
+        let registration = app.registered_hotkeys.lock().unwrap().get(hotkey).copied();
+        assert_eq!(registration.map(|r| r.users), Some(1));
+        assert!(workspaces[1].hotkey.as_ref().unwrap().unregister(&app));
+        assert!(app.registered_hotkeys.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn hotkey_in_use_ignores_case_and_modifier_order() {
+        let workspaces = vec![Workspace {
+            hotkey: Some(Hotkey::new("Ctrl+Alt+S").unwrap()),
+            ..Default::default()
+        }];
+        assert!(hotkey_in_use(&workspaces, "alt+ctrl+s"));
+        assert!(!hotkey_in_use(&workspaces, "Ctrl+Alt+T"));
+    }
+
+    #[test]
+    fn unique_workspace_name_appends_counter() {
+        let workspaces: Vec<Workspace> = ["Work", "work (2)"]
+            .into_iter()
+            .map(|name| Workspace {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        assert_eq!(unique_workspace_name(&workspaces, "Home"), "Home");
+        assert_eq!(unique_workspace_name(&workspaces, "Work"), "Work (3)");
+    }
+
+    #[test]
+    fn windows_are_found_by_title_or_alias() {
+        let window = |title: &str, alias: Option<&str>| Window {
+            title: title.to_string(),
+            alias: alias.map(str::to_string),
+            ..Default::default()
+        };
+        let workspaces = vec![
+            Workspace {
+                windows: vec![window("Inbox - Outlook", None), window("Notes", None)],
+                ..Default::default()
+            },
+            Workspace {
+                windows: vec![window("Untitled - Notepad", Some("Scratch"))],
+                ..Default::default()
+            },
+        ];
+        assert_eq!(find_windows(&workspaces, "note"), vec![(0, 1), (1, 0)]);
+        assert_eq!(find_windows(&workspaces, "SCRATCH"), vec![(1, 0)]);
+        assert!(find_windows(&workspaces, "  ").is_empty());
+    }
+
+    #[test]
+    fn out_of_range_rotation_offset_is_repaired() {
+        let mut workspace = Workspace {
+            windows: vec![Window::default(), Window::default()],
+            rotation_offset: 5,
+            ..Default::default()
+        };
+        assert!(workspace.repair_rotation_offset());
+        assert_eq!(workspace.rotation_offset, 1);
+        assert!(!workspace.repair_rotation_offset());
+
+        workspace.windows.clear();
+        assert!(workspace.repair_rotation_offset());
+        assert_eq!(workspace.rotation_offset, 0);
+    }
+
+    #[test]
+    fn rescale_scales_positions_and_counts_windows() {
+        let workspaces = vec![Workspace {
+            windows: vec![
+                Window {
+                    home: (0, 0, 1920, 1080),
+                    target: (1920, 1080, 960, 540),
+                    home_monitor: Some(MonitorAnchor {
+                        device: "DISPLAY1".to_string(),
+                        rect: (100, 200, 800, 600),
+                    }),
+                    ..Default::default()
+                },
+                Window {
+                    home: (0, 0, 0, 0),
+                    target: (0, 0, 0, 0),
+                    target_layout: TargetLayout::Centered {
+                        center: (1000, 500),
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }];
+
+        let (rescaled, changed) = rescale_workspaces(workspaces, (3840, 2160), (1920, 1080));
+        assert_eq!(changed, 2);
+        let windows = &rescaled[0].windows;
+        assert_eq!(windows[0].home, (0, 0, 960, 540));
+        assert_eq!(windows[0].target, (960, 540, 480, 270));
+        assert_eq!(
+            windows[0].home_monitor.as_ref().map(|anchor| anchor.rect),
+            Some((50, 100, 400, 300))
+        );
+        assert_eq!(
+            windows[1].target_layout,
+            TargetLayout::Centered { center: (500, 250) }
+        );
+
+        let (_, unchanged) = rescale_workspaces(rescaled, (1920, 1080), (1920, 1080));
+        assert_eq!(unchanged, 0);
+    }
+
+    #[test]
+    fn window_groups_keep_first_appearance_order() {
+        let grouped = |group: Option<&str>| Window {
+            group: group.map(str::to_string),
+            ..Default::default()
+        };
+        let windows = vec![
+            grouped(Some("Right")),
+            grouped(None),
+            grouped(Some("Left")),
+            grouped(Some("Right")),
+        ];
+        assert_eq!(
+            window_groups(&windows),
+            vec![Some("Right".to_string()), None, Some("Left".to_string())]
+        );
+    }
+
+    #[test]
+    fn fingerprints_compare_class_and_process() {
+        let stored = WindowFingerprint {
+            process: "Notepad.exe".to_string(),
+            class: "Notepad".to_string(),
+        };
+        let same = WindowFingerprint {
+            process: "notepad.exe".to_string(),
+            class: "Notepad".to_string(),
+        };
+        let other_process = WindowFingerprint {
+            process: "calc.exe".to_string(),
+            class: "Notepad".to_string(),
+        };
+        let unreadable_process = WindowFingerprint {
+            process: String::new(),
+            class: "Notepad".to_string(),
+        };
+        let other_class = WindowFingerprint {
+            process: "notepad.exe".to_string(),
+            class: "Chrome_WidgetWin_1".to_string(),
+        };
+        assert!(stored.matches(&same));
+        assert!(stored.matches(&unreadable_process));
+        assert!(!stored.matches(&other_process));
+        assert!(!stored.matches(&other_class));
+    }
+
+    #[test]
+    fn centered_target_uses_stored_size() {
+        let window = Window {
+            target: (0, 0, 200, 100),
+            target_layout: TargetLayout::Centered { center: (500, 400) },
+            ..Window::default()
+        };
+        assert_eq!(window.target_rect(), (400, 350, 200, 100));
+    }
+
+    #[test]
+    fn mask_rect_keeps_unapplied_parts() {
+        let rect = (100, 200, 800, 600);
+        let current = (0, 0, 1024, 768);
+        let mut window = Window::default();
+        assert_eq!(window.mask_rect(rect, current), rect);
+
+        window.apply_size = false;
+        assert_eq!(window.mask_rect(rect, current), (100, 200, 1024, 768));
+
+        window.apply_size = true;
+        window.apply_position = false;
+        assert_eq!(window.mask_rect(rect, current), (0, 0, 800, 600));
+    }
 }
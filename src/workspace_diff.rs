@@ -0,0 +1,196 @@
+use crate::workspace::{Window, Workspace};
+
+/// A rectangle as stored in a workspace file: `(x, y, width, height)`.
+pub type Rect = (i32, i32, i32, i32);
+
+/// Differences between two lists of workspaces, matched by name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorkspaceDiff {
+    /// Names of workspaces only present in the second list.
+    pub added: Vec<String>,
+    /// Names of workspaces only present in the first list.
+    pub removed: Vec<String>,
+    /// Workspaces present in both lists that differ.
+    pub changed: Vec<WorkspaceChanges>,
+}
+
+impl WorkspaceDiff {
+    /// Returns `true` if both lists describe the same workspaces.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Differences within one workspace present in both lists.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorkspaceChanges {
+    pub name: String,
+    /// Old and new hotkey, if the hotkey changed.
+    pub hotkey: Option<(Option<String>, Option<String>)>,
+    /// Titles of windows only present in the second list.
+    pub added_windows: Vec<String>,
+    /// Titles of windows only present in the first list.
+    pub removed_windows: Vec<String>,
+    /// Windows present in both lists whose stored rectangles changed.
+    pub moved_windows: Vec<WindowChange>,
+}
+
+/// Rectangles of a window that changed between the two lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowChange {
+    pub title: String,
+    /// Old and new home rectangle, if it changed.
+    pub home: Option<(Rect, Rect)>,
+    /// Old and new target rectangle, if it changed.
+    pub target: Option<(Rect, Rect)>,
+}
+
+/// Compares the workspaces in `a` (older) with those in `b` (newer).
+///
+/// Workspaces are matched by name and windows by title; repeated titles are
+/// paired in the order they appear.
+pub fn diff_workspaces(a: &[Workspace], b: &[Workspace]) -> WorkspaceDiff {
+    let mut diff = WorkspaceDiff {
+        added: b
+            .iter()
+            .filter(|ws| !a.iter().any(|old| old.name == ws.name))
+            .map(|ws| ws.name.clone())
+            .collect(),
+        ..Default::default()
+    };
+    for old in a {
+        match b.iter().find(|ws| ws.name == old.name) {
+            Some(new) => {
+                let changes = diff_workspace(old, new);
+                if changes.hotkey.is_some()
+                    || !changes.added_windows.is_empty()
+                    || !changes.removed_windows.is_empty()
+                    || !changes.moved_windows.is_empty()
+                {
+                    diff.changed.push(changes);
+                }
+            }
+            None => diff.removed.push(old.name.clone()),
+        }
+    }
+    diff
+}
+
+fn diff_workspace(old: &Workspace, new: &Workspace) -> WorkspaceChanges {
+    let hotkey = |ws: &Workspace| ws.hotkey.as_ref().map(|h| h.key_sequence.clone());
+    let mut changes = WorkspaceChanges {
+        name: old.name.clone(),
+        hotkey: Some((hotkey(old), hotkey(new))).filter(|(before, after)| before != after),
+        ..Default::default()
+    };
+
+    let mut unmatched: Vec<&Window> = new.windows.iter().collect();
+    for window in &old.windows {
+        match unmatched.iter().position(|w| w.title == window.title) {
+            Some(index) => {
+                let other = unmatched.remove(index);
+                let changed =
+                    |before: Rect, after: Rect| Some((before, after)).filter(|_| before != after);
+                let home = changed(window.home, other.home);
+                let target = changed(window.target, other.target);
+                if home.is_some() || target.is_some() {
+                    changes.moved_windows.push(WindowChange {
+                        title: window.title.clone(),
+                        home,
+                        target,
+                    });
+                }
+            }
+            None => changes.removed_windows.push(window.title.clone()),
+        }
+    }
+    changes.added_windows = unmatched.iter().map(|w| w.title.clone()).collect();
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hotkey::Hotkey;
+
+    fn window(title: &str, home: Rect) -> Window {
+        Window {
+            title: title.to_string(),
+            home,
+            target: (0, 0, 800, 600),
+            ..Default::default()
+        }
+    }
+
+    fn workspace(name: &str, hotkey: Option<&str>, windows: Vec<Window>) -> Workspace {
+        Workspace {
+            name: name.to_string(),
+            hotkey: hotkey.map(|key| Hotkey {
+                key_sequence: key.to_string(),
+                id: None,
+            }),
+            windows,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn identical_lists_have_no_differences() {
+        let list = vec![workspace(
+            "Code",
+            Some("Ctrl+Alt+C"),
+            vec![window("Editor", (0, 0, 960, 1080))],
+        )];
+        assert!(diff_workspaces(&list, &list.clone()).is_empty());
+    }
+
+    #[test]
+    fn differences_are_reported_per_workspace() {
+        let a = vec![
+            workspace(
+                "Code",
+                Some("Ctrl+Alt+C"),
+                vec![
+                    window("Editor", (0, 0, 960, 1080)),
+                    window("Terminal", (960, 0, 960, 1080)),
+                ],
+            ),
+            workspace("Old", None, Vec::new()),
+        ];
+        let b = vec![
+            workspace(
+                "Code",
+                Some("Ctrl+Alt+K"),
+                vec![
+                    window("Editor", (0, 0, 1280, 1080)),
+                    window("Browser", (1280, 0, 640, 1080)),
+                ],
+            ),
+            workspace("New", None, Vec::new()),
+        ];
+
+        let diff = diff_workspaces(&a, &b);
+        assert_eq!(diff.added, vec!["New"]);
+        assert_eq!(diff.removed, vec!["Old"]);
+        assert_eq!(diff.changed.len(), 1);
+
+        let code = &diff.changed[0];
+        assert_eq!(
+            code.hotkey,
+            Some((
+                Some("Ctrl+Alt+C".to_string()),
+                Some("Ctrl+Alt+K".to_string())
+            ))
+        );
+        assert_eq!(code.added_windows, vec!["Browser"]);
+        assert_eq!(code.removed_windows, vec!["Terminal"]);
+        assert_eq!(
+            code.moved_windows,
+            vec![WindowChange {
+                title: "Editor".to_string(),
+                home: Some(((0, 0, 960, 1080), (0, 0, 1280, 1080))),
+                target: None,
+            }]
+        );
+    }
+}